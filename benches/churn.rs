@@ -0,0 +1,61 @@
+//! Multi-threaded churn benchmark: every thread repeatedly acquires and returns
+//! objects of its own pool. The pools are neighboring statics, so without the
+//! cache-line padding of the pool internals their locks would share cache
+//! lines and every operation would invalidate the other threads' caches.
+//!
+//! Run with `cargo bench --bench churn`.
+use std::hint::black_box;
+use std::mem::{align_of, size_of};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use derivable_object_pool::prelude::*;
+
+const ITERATIONS: usize = 1_000_000;
+
+macro_rules! pooled_types {
+    ($($name:ident),+) => {
+        $(
+            #[derive(Default, ObjectPool)]
+            struct $name(u64);
+        )+
+    };
+}
+
+pooled_types!(A, B, C, D);
+
+fn churn<T: ObjectPool>(touch: fn(&mut T)) {
+    for _ in 0..ITERATIONS {
+        let mut obj = T::new();
+        touch(black_box(&mut obj));
+    }
+}
+
+fn run(threads: usize) -> Duration {
+    let start = Instant::now();
+    thread::scope(|scope| {
+        let workers: [fn(); 4] = [
+            || churn::<A>(|obj| obj.0 += 1),
+            || churn::<B>(|obj| obj.0 += 1),
+            || churn::<C>(|obj| obj.0 += 1),
+            || churn::<D>(|obj| obj.0 += 1),
+        ];
+        for worker in workers.into_iter().take(threads) {
+            scope.spawn(worker);
+        }
+    });
+    start.elapsed()
+}
+
+fn main() {
+    println!(
+        "Pool<u64>: size {} bytes, align {} bytes",
+        size_of::<Pool<u64>>(),
+        align_of::<Pool<u64>>()
+    );
+    for threads in 1..=4 {
+        let elapsed = run(threads);
+        let per_op = elapsed / (ITERATIONS as u32);
+        println!("{threads} thread(s): {elapsed:?} ({per_op:?} per acquire/return)");
+    }
+}
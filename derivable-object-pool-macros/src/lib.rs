@@ -1,38 +1,648 @@
 //! # Derivable Object Pool Macro
 //!
 //! Internal crate for [derivable-object-pool](https://crates.io/crates/derivable-object-pool)
-//! crate. That provides a derive macro for [`ObjectPool`] trait.
+//! crate. That provides a derive macro for [`ObjectPool`] trait and the
+//! [`global_pool`] attribute macro for pools of foreign types.
 //!
 //! [`ObjectPool`]: trait.ObjectPool.html
 use proc_macro::TokenStream;
-use syn::DeriveInput;
+use syn::{Attribute, DeriveInput, ItemStatic};
+
+/// Options of the `#[pool(...)]` attribute (and of the `#[generator(...)]` and
+/// `#[generator_try(...)]` attributes, kept for backwards compatibility).
+#[derive(Default)]
+struct PoolAttributes {
+    /// The function that creates new objects, if not [`Default`].
+    generator: Option<syn::Expr>,
+    /// The fallible function that creates new objects, if any.
+    try_generator: Option<syn::Expr>,
+    /// The error of the fallible generator.
+    error: Option<syn::Type>,
+    /// Whether to emit a round-trip unit test for the type.
+    test_roundtrip: bool,
+    /// The function dirtying the object in the round-trip test, if any.
+    roundtrip_mutate: Option<syn::Expr>,
+    /// Whether the pool has no generator function.
+    reuse_only: bool,
+    /// The maximum number of idle objects of the pool.
+    capacity: Option<syn::Expr>,
+    /// The floor of idle objects of the pool.
+    min_idle: Option<syn::Expr>,
+    /// The number of objects created at once when the pool is empty.
+    growth: Option<syn::Expr>,
+    /// The number of objects the pool is filled with when it is first used.
+    prefill: Option<syn::Expr>,
+    /// Whether the returned objects are recycled.
+    recycle: bool,
+    /// The maximum number of times an object is handed out again.
+    max_reuses: Option<syn::Expr>,
+    /// The maximum total heap size of the idle objects.
+    max_bytes: Option<syn::Expr>,
+    /// The predicate the idle objects must pass to be handed out, if any.
+    validator: Option<syn::Expr>,
+    /// The lifecycle hooks of the pool, by the name of their builder.
+    hooks: Vec<(syn::Ident, syn::Expr)>,
+    /// Whether the objects are zeroed before being handed out.
+    zero_on_acquire: bool,
+    /// The number of slots of the lock-free backend of the pool.
+    lock_free: Option<syn::Expr>,
+    /// The human-assigned name of the pool, if any.
+    name: Option<syn::LitStr>,
+    /// The constant objects the pool starts with.
+    const_items: Option<syn::ExprArray>,
+    /// Whether to generate an asynchronous pool.
+    async_pool: bool,
+    /// The capacity of the array-backed pool to generate, if any.
+    static_capacity: Option<syn::Expr>,
+    /// The path of the `derivable_object_pool` crate, if it was renamed or
+    /// re-exported.
+    krate: Option<syn::Path>,
+    /// The name of the generated static, if not the default one.
+    static_name: Option<syn::Ident>,
+    /// The visibility of the generated static, if not private.
+    vis: Option<syn::Visibility>,
+}
+
+impl PoolAttributes {
+    /// Parses the options of all the `#[pool(...)]`, `#[generator(...)]` and
+    /// `#[generator_try(...)]` attributes.
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut options = Self::default();
+        for attr in attrs {
+            if attr.path().is_ident("generator") {
+                let generator = match &attr.meta {
+                    syn::Meta::List(list) => list.parse_args()?,
+                    syn::Meta::NameValue(name_value) => generator_value(&name_value.value)?,
+                    syn::Meta::Path(path) => {
+                        return Err(syn::Error::new_spanned(
+                            path,
+                            "expected `#[generator(function)]` or `#[generator = \"function\"]`",
+                        ));
+                    }
+                };
+                set_once(&mut options.generator, generator, attr, "generator")?;
+            } else if attr.path().is_ident("generator_try") {
+                let TryGenerator { generator, error } = attr.parse_args()?;
+                set_once(&mut options.try_generator, generator, attr, "generator_try")?;
+                set_once(&mut options.error, error, attr, "error")?;
+            }
+        }
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("pool")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("generator") {
+                    let generator = generator_value(&meta.value()?.parse()?)?;
+                    set_once(&mut options.generator, generator, &meta.path, "generator")
+                } else if meta.path.is_ident("generator_try") {
+                    let generator = generator_value(&meta.value()?.parse()?)?;
+                    set_once(
+                        &mut options.try_generator,
+                        generator,
+                        &meta.path,
+                        "generator_try",
+                    )
+                } else if meta.path.is_ident("error") {
+                    let error = meta.value()?.parse()?;
+                    set_once(&mut options.error, error, &meta.path, "error")
+                } else if let Some(hook) = ["on_create", "on_reuse", "on_return", "on_discard"]
+                    .into_iter()
+                    .find(|hook| meta.path.is_ident(hook))
+                {
+                    if options.hooks.iter().any(|(name, _)| name == hook) {
+                        return Err(meta.error(format!("duplicate `{hook}` option")));
+                    }
+                    let name = meta.path.get_ident().unwrap().clone();
+                    options.hooks.push((name, meta.value()?.parse()?));
+                    Ok(())
+                } else if meta.path.is_ident("validator") {
+                    let validator = meta.value()?.parse()?;
+                    set_once(&mut options.validator, validator, &meta.path, "validator")
+                } else if meta.path.is_ident("test_roundtrip") {
+                    options.test_roundtrip = true;
+                    if !meta.input.peek(syn::Token![=]) {
+                        return Ok(());
+                    }
+                    let mutate = meta.value()?.parse()?;
+                    set_once(
+                        &mut options.roundtrip_mutate,
+                        mutate,
+                        &meta.path,
+                        "test_roundtrip",
+                    )
+                } else if meta.path.is_ident("reuse_only") {
+                    options.reuse_only = true;
+                    Ok(())
+                } else if meta.path.is_ident("zero_on_acquire") {
+                    options.zero_on_acquire = true;
+                    Ok(())
+                } else if meta.path.is_ident("recycle") {
+                    options.recycle = true;
+                    Ok(())
+                } else if meta.path.is_ident("capacity") {
+                    let capacity = meta.value()?.parse()?;
+                    set_once(&mut options.capacity, capacity, &meta.path, "capacity")
+                } else if meta.path.is_ident("static_capacity") {
+                    let static_capacity = meta.value()?.parse()?;
+                    set_once(
+                        &mut options.static_capacity,
+                        static_capacity,
+                        &meta.path,
+                        "static_capacity",
+                    )
+                } else if meta.path.is_ident("async") {
+                    options.async_pool = true;
+                    Ok(())
+                } else if meta.path.is_ident("const_items") {
+                    let const_items = meta.value()?.parse()?;
+                    set_once(
+                        &mut options.const_items,
+                        const_items,
+                        &meta.path,
+                        "const_items",
+                    )
+                } else if meta.path.is_ident("min_idle") {
+                    let min_idle = meta.value()?.parse()?;
+                    set_once(&mut options.min_idle, min_idle, &meta.path, "min_idle")
+                } else if meta.path.is_ident("prefill") {
+                    let prefill = meta.value()?.parse()?;
+                    set_once(&mut options.prefill, prefill, &meta.path, "prefill")
+                } else if meta.path.is_ident("growth") {
+                    let growth = meta.value()?.parse()?;
+                    set_once(&mut options.growth, growth, &meta.path, "growth")
+                } else if meta.path.is_ident("max_reuses") {
+                    let max_reuses = meta.value()?.parse()?;
+                    set_once(
+                        &mut options.max_reuses,
+                        max_reuses,
+                        &meta.path,
+                        "max_reuses",
+                    )
+                } else if meta.path.is_ident("max_bytes") {
+                    let max_bytes = meta.value()?.parse()?;
+                    set_once(&mut options.max_bytes, max_bytes, &meta.path, "max_bytes")
+                } else if meta.path.is_ident("name") {
+                    let name = meta.value()?.parse()?;
+                    set_once(&mut options.name, name, &meta.path, "name")
+                } else if meta.path.is_ident("static_name") {
+                    let name: syn::LitStr = meta.value()?.parse()?;
+                    set_once(
+                        &mut options.static_name,
+                        name.parse()?,
+                        &meta.path,
+                        "static_name",
+                    )
+                } else if meta.path.is_ident("vis") {
+                    let vis: syn::LitStr = meta.value()?.parse()?;
+                    set_once(&mut options.vis, vis.parse()?, &meta.path, "vis")
+                } else if meta.path.is_ident("crate") {
+                    let krate = meta.value()?.parse()?;
+                    set_once(&mut options.krate, krate, &meta.path, "crate")
+                } else if meta.path.is_ident("lock_free") {
+                    let lock_free = meta.value()?.parse()?;
+                    set_once(&mut options.lock_free, lock_free, &meta.path, "lock_free")
+                } else {
+                    Err(meta.error("unsupported pool attribute"))
+                }
+            })?;
+        }
+        options.validate()?;
+        Ok(options)
+    }
+
+    /// Checks that the generators can be combined with the other options.
+    fn validate(&self) -> syn::Result<()> {
+        for generator in self.generator.iter().chain(&self.try_generator) {
+            if let syn::Expr::Closure(closure) = generator {
+                if !closure.inputs.is_empty() {
+                    return Err(syn::Error::new_spanned(
+                        &closure.inputs,
+                        "generator closures can't take arguments",
+                    ));
+                }
+            }
+        }
+        match (&self.try_generator, &self.error) {
+            (Some(generator), None) => {
+                return Err(syn::Error::new_spanned(
+                    generator,
+                    "a fallible generator requires its `error = Type`",
+                ));
+            }
+            (None, Some(error)) => {
+                return Err(syn::Error::new_spanned(
+                    error,
+                    "`error` is only supported with `generator_try`",
+                ));
+            }
+            _ => {}
+        }
+        match &self.generator {
+            Some(generator) if self.try_generator.is_some() => Err(syn::Error::new_spanned(
+                generator,
+                "a pool can't have both a generator and a fallible generator",
+            )),
+            Some(generator) if self.reuse_only => Err(syn::Error::new_spanned(
+                generator,
+                "a reuse-only pool can't have a generator",
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the infallible generator of the pool, which defaults to the
+    /// [`Default`] implementation of the type.
+    fn generator(&self, ident: &syn::Ident) -> proc_macro2::TokenStream {
+        match &self.generator {
+            Some(generator) => generator_fn(generator),
+            None => quote::quote! { <#ident as ::core::default::Default>::default },
+        }
+    }
+
+    /// Returns the path of the `derivable_object_pool` crate, used to refer to
+    /// its items regardless of the imports of the module of the type.
+    fn krate(&self) -> syn::Path {
+        self.krate
+            .clone()
+            .unwrap_or_else(|| syn::parse_quote! { ::derivable_object_pool })
+    }
+
+    /// Returns `true` if any option of the synchronous pools is set.
+    fn has_sync_options(&self) -> bool {
+        self.test_roundtrip
+            || self.try_generator.is_some()
+            || self.reuse_only
+            || self.capacity.is_some()
+            || self.min_idle.is_some()
+            || self.growth.is_some()
+            || self.prefill.is_some()
+            || self.recycle
+            || self.max_reuses.is_some()
+            || self.max_bytes.is_some()
+            || !self.hooks.is_empty()
+            || self.validator.is_some()
+            || self.zero_on_acquire
+            || self.lock_free.is_some()
+            || self.const_items.is_some()
+            || self.name.is_some()
+    }
+}
+
+/// Parses the value of a generator option, which is either an expression or a
+/// string literal containing the expression (`generator = "Type::new"`).
+fn generator_value(value: &syn::Expr) -> syn::Result<syn::Expr> {
+    match value {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(lit),
+            ..
+        }) => lit.parse(),
+        syn::Expr::Lit(lit) => Err(syn::Error::new_spanned(
+            lit,
+            "expected a generator function or a string literal containing it",
+        )),
+        value => Ok(value.clone()),
+    }
+}
+
+/// Converts a generator expression into a function: paths (like
+/// `Type::new`) and closures are used as they are, while any other expression
+/// (like `Type(42)`) is evaluated each time a new object is created.
+fn generator_fn(generator: &syn::Expr) -> proc_macro2::TokenStream {
+    match generator {
+        syn::Expr::Paren(syn::ExprParen { expr, .. })
+        | syn::Expr::Group(syn::ExprGroup { expr, .. }) => generator_fn(expr),
+        syn::Expr::Path(_) | syn::Expr::Closure(_) => quote::quote! { #generator },
+        _ => quote::quote! { || #generator },
+    }
+}
+
+/// Sets an option, failing if it was already set.
+fn set_once<T>(
+    option: &mut Option<T>,
+    value: T,
+    tokens: impl quote::ToTokens,
+    name: &str,
+) -> syn::Result<()> {
+    if option.is_some() {
+        return Err(syn::Error::new_spanned(
+            tokens,
+            format!("duplicate `{name}` option"),
+        ));
+    }
+    *option = Some(value);
+    Ok(())
+}
+
+/// Arguments of the `#[generator_try(function, error = Type)]` attribute.
+struct TryGenerator {
+    generator: syn::Expr,
+    error: syn::Type,
+}
+
+impl syn::parse::Parse for TryGenerator {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let generator = input.parse()?;
+        input.parse::<syn::Token![,]>()?;
+        let key: syn::Ident = input.parse()?;
+        if key != "error" {
+            return Err(syn::Error::new_spanned(key, "expected `error = Type`"));
+        }
+        input.parse::<syn::Token![=]>()?;
+        let error = input.parse()?;
+        Ok(Self { generator, error })
+    }
+}
+
+/// Converts an identifier in camel case to snake case.
+fn to_snake_case(ident: &str) -> String {
+    let mut snake = String::with_capacity(ident.len());
+    for (i, c) in ident.chars().enumerate() {
+        if c.is_uppercase() {
+            if i != 0 {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+/// Generates a unit test that round-trips an object through the pool (see
+/// `Pool::roundtrip_test`). The reacquired object is compared with a new one
+/// if the type implements [`PartialEq`], which is detected by preferring the
+/// method of the trait implemented by value over the one implemented by
+/// reference.
+fn roundtrip_test(
+    ident: &syn::Ident,
+    krate: &syn::Path,
+    mutate: Option<&syn::Expr>,
+) -> proc_macro2::TokenStream {
+    let test = quote::format_ident!(
+        "{}_object_pool_roundtrip",
+        to_snake_case(&ident.to_string())
+    );
+    let mutate = match mutate {
+        Some(mutate) => quote::quote! { ::core::option::Option::Some::<fn(&mut #ident)>(#mutate) },
+        None => quote::quote! { ::core::option::Option::None },
+    };
+
+    quote::quote! {
+        #[cfg(test)]
+        #[test]
+        fn #test() {
+            struct Reset<'a, T>(&'a T, &'a T);
+
+            trait Compared {
+                fn is_reset(&self) -> bool;
+            }
+
+            impl<T: ::core::cmp::PartialEq> Compared for Reset<'_, T> {
+                fn is_reset(&self) -> bool {
+                    self.0 == self.1
+                }
+            }
+
+            trait Uncompared {
+                fn is_reset(&self) -> bool;
+            }
+
+            impl<T> Uncompared for &Reset<'_, T> {
+                fn is_reset(&self) -> bool {
+                    true
+                }
+            }
+
+            <#ident as #krate::ObjectPool>::pool()
+                .roundtrip_test(#mutate, |item, new| (&Reset(item, new)).is_reset());
+        }
+    }
+}
 
 fn impl_object_pool_derive_macro(ast: DeriveInput) -> TokenStream {
     let ident = ast.ident;
     let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
-    let ident_capital = ident.to_string().to_ascii_uppercase();
-    let pool = quote::format_ident!("{ident_capital}_OBJECT_POOL");
 
     let attrs = ast.attrs;
+    let options = match PoolAttributes::parse(&attrs) {
+        Ok(options) => options,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let krate = options.krate();
+    let vis = &options.vis;
+    let pool = options.static_name.clone().unwrap_or_else(|| {
+        let ident_capital = ident.to_string().to_ascii_uppercase();
+        quote::format_ident!("{ident_capital}_OBJECT_POOL")
+    });
+
+    let generic = !ast.generics.params.is_empty();
+    if generic
+        && (options.async_pool
+            || options.static_capacity.is_some()
+            || options.const_items.is_some()
+            || options.test_roundtrip
+            || options.static_name.is_some()
+            || options.vis.is_some())
+    {
+        return syn::Error::new_spanned(
+            &ast.generics,
+            "generic types don't support `async`, `static_capacity`, `const_items`, \
+             `test_roundtrip`, `static_name` or `vis`",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    if options.async_pool {
+        return impl_async_object_pool(&ident, &pool, &options);
+    }
+    if let Some(capacity) = &options.static_capacity {
+        return impl_static_object_pool(&ident, &pool, &options, capacity);
+    }
+
+    let try_object_pool = options.try_generator.as_ref().map(|generator| {
+        let generator = generator_fn(generator);
+        let error = &options.error;
+        quote::quote! {
+            impl #impl_generics #krate::TryObjectPool for #ident #ty_generics #where_clause {
+                type Error = #error;
 
-    let generator = {
-        // Find attribute generator
-        let attr = attrs.iter().find(|attr| attr.path().is_ident("generator"));
-        match attr {
-            Some(attr) => {
-                let generator = attr.parse_args::<syn::Expr>().unwrap();
-                quote::quote! { #generator }
+                #[inline]
+                fn try_generate() -> ::core::result::Result<Self, Self::Error> {
+                    (#generator)()
+                }
             }
-            None => quote::quote! { #ident::default },
         }
+    });
+
+    let default_generator =
+        options.generator.is_none() && !options.reuse_only && try_object_pool.is_none();
+    let new_pool = match &options.generator {
+        Some(generator) => {
+            let generator = generator_fn(generator);
+            quote::quote! { #krate::Pool::new(#generator) }
+        }
+        None if options.reuse_only || try_object_pool.is_some() => {
+            quote::quote! { #krate::Pool::reuse_only() }
+        }
+        None => quote::quote! {
+            #krate::Pool::new(<#ident #ty_generics as ::core::default::Default>::default)
+        },
     };
 
+    let capacity = options
+        .capacity
+        .map(|capacity| quote::quote! { .with_capacity(#capacity) });
+    let min_idle = options
+        .min_idle
+        .map(|min_idle| quote::quote! { .with_min_idle(#min_idle) });
+    let growth = options
+        .growth
+        .map(|growth| quote::quote! { .with_growth(#growth) });
+    let prefill = options
+        .prefill
+        .map(|prefill| quote::quote! { .with_prefill(#prefill) });
+    let recycle = options.recycle.then(|| quote::quote! { .with_recycle() });
+    let max_reuses = options
+        .max_reuses
+        .map(|max_reuses| quote::quote! { .with_max_reuses(#max_reuses) });
+    let max_bytes = options
+        .max_bytes
+        .map(|max_bytes| quote::quote! { .with_max_bytes(#max_bytes) });
+    let hooks = options
+        .hooks
+        .iter()
+        .map(|(name, hook)| quote::quote! { .#name(#hook) });
+    let validator = options
+        .validator
+        .as_ref()
+        .map(|validator| quote::quote! { .with_validator(#validator) });
+    let zero_on_acquire = options
+        .zero_on_acquire
+        .then(|| quote::quote! { .zero_on_acquire() });
+    let lock_free = options
+        .lock_free
+        .map(|slots| quote::quote! { .with_lock_free(#slots) });
+    let const_items = options.const_items.map(|items| {
+        quote::quote! {
+            .with_initial_items(|pool| {
+                const ITEMS: &[#ident] = &#items;
+                pool.extend_from_slice(ITEMS);
+            })
+        }
+    });
+    let name = options
+        .name
+        .as_ref()
+        .map(|name| quote::quote! { .with_name(#name) });
+    let test = options
+        .test_roundtrip
+        .then(|| roundtrip_test(&ident, &krate, options.roundtrip_mutate.as_ref()));
+
+    let new_pool = quote::quote! { #new_pool #capacity #min_idle #growth #prefill #recycle #max_reuses #max_bytes #(#hooks)* #validator #zero_on_acquire #lock_free #const_items #name };
+
+    if generic {
+        // Statics can't be generic, so each instantiation gets its own pool
+        let mut generics = ast.generics.clone();
+        generics
+            .make_where_clause()
+            .predicates
+            .push(syn::parse_quote! { #ident #ty_generics: ::core::marker::Send + 'static });
+        if default_generator {
+            // `#[derive(Default)]` only implements it for some instantiations
+            generics
+                .make_where_clause()
+                .predicates
+                .push(syn::parse_quote! { #ident #ty_generics: ::core::default::Default });
+        }
+        let where_clause = &generics.where_clause;
+        return quote::quote! {
+            impl #impl_generics #krate::ObjectPool for #ident #ty_generics #where_clause {
+                #[inline]
+                fn pool<'a>() -> &'a #krate::Pool<Self> {
+                    let pool = #krate::Pool::for_type(|| #new_pool);
+                    pool.register(::core::any::type_name::<Self>());
+                    pool.current()
+                }
+            }
+
+            #try_object_pool
+        }
+        .into();
+    }
+
     quote::quote! {
-        static #pool: Pool<#ident> = Pool::new(#generator);
+        #vis static #pool: #krate::Pool<#ident> = #new_pool;
 
-        impl #impl_generics ObjectPool for #ident #ty_generics #where_clause {
+        impl #impl_generics #krate::ObjectPool for #ident #ty_generics #where_clause {
             #[inline]
-            fn pool<'a>() -> &'a Pool<Self> {
+            fn pool<'a>() -> &'a #krate::Pool<Self> {
+                #pool.register(::core::stringify!(#pool));
+                #pool.current()
+            }
+        }
+
+        #try_object_pool
+
+        #test
+    }
+    .into()
+}
+
+/// Generates an asynchronous pool (`#[pool(async)]`) for the type.
+fn impl_async_object_pool(
+    ident: &syn::Ident,
+    pool: &syn::Ident,
+    options: &PoolAttributes,
+) -> TokenStream {
+    if options.has_sync_options() {
+        return syn::Error::new_spanned(ident, "async pools don't support other pool options")
+            .to_compile_error()
+            .into();
+    }
+    let krate = options.krate();
+    let vis = &options.vis;
+    let generator = options.generator(ident);
+
+    quote::quote! {
+        #vis static #pool: #krate::AsyncPool<#ident> = #krate::AsyncPool::new(#generator);
+
+        impl #krate::AsyncObjectPool for #ident {
+            #[inline]
+            fn pool<'a>() -> &'a #krate::AsyncPool<Self> {
+                &#pool
+            }
+        }
+    }
+    .into()
+}
+
+/// Generates an array-backed pool (`#[pool(static_capacity = N)]`) for the
+/// type.
+fn impl_static_object_pool(
+    ident: &syn::Ident,
+    pool: &syn::Ident,
+    options: &PoolAttributes,
+    capacity: &syn::Expr,
+) -> TokenStream {
+    if options.has_sync_options() || options.async_pool {
+        return syn::Error::new_spanned(
+            ident,
+            "static capacity pools don't support other pool options",
+        )
+        .to_compile_error()
+        .into();
+    }
+    let krate = options.krate();
+    let vis = &options.vis;
+    let generator = options.generator(ident);
+
+    quote::quote! {
+        #vis static #pool: #krate::StaticPool<#ident, { #capacity }> =
+            #krate::StaticPool::new(#generator);
+
+        impl #krate::StaticObjectPool<{ #capacity }> for #ident {
+            #[inline]
+            fn pool() -> &'static #krate::StaticPool<Self, { #capacity }> {
                 &#pool
             }
         }
@@ -46,7 +656,12 @@ fn impl_object_pool_derive_macro(ast: DeriveInput) -> TokenStream {
 ///
 /// Internally, the macro will generate a static variable with the name of
 /// `#[ident]_OBJECT_POOL` (where `#[ident]` is the name of the type in
-/// uppercase) and implement the [ObjectPool] trait for the type.
+/// uppercase) and implement the [ObjectPool] trait for the type. The generated
+/// code refers to the items of the crate by their full path, so it doesn't
+/// depend on the imports of the module of the type. As statics
+/// can't be generic, the pools of generic types are created on first use
+/// instead, one for each instantiation of the type (see `Pool::for_type`), so
+/// the type must be `Send + 'static`.
 ///
 /// # Example
 ///
@@ -85,11 +700,12 @@ fn impl_object_pool_derive_macro(ast: DeriveInput) -> TokenStream {
 ///#     }
 ///# }
 ///#
-/// static TEST2_OBJECT_POOL: Pool<Test2> = Pool::new(Test2::new_item);
+/// static TEST2_OBJECT_POOL: ::derivable_object_pool::Pool<Test2> =
+///     ::derivable_object_pool::Pool::new(Test2::new_item);
 ///
-/// impl ObjectPool for Test2 {
+/// impl ::derivable_object_pool::ObjectPool for Test2 {
 ///     #[inline]
-///     fn pool<'a>() -> &'a Pool<Self> {
+///     fn pool<'a>() -> &'a ::derivable_object_pool::Pool<Self> {
 ///         &TEST2_OBJECT_POOL
 ///     }
 /// }
@@ -105,17 +721,223 @@ fn impl_object_pool_derive_macro(ast: DeriveInput) -> TokenStream {
 ///
 /// # Attributes
 ///
+/// ## pool
+///
+/// Configure the generated pool with a list of options, which can be split
+/// across several `#[pool(...)]` attributes:
+///
+/// - `generator = function`: the generator function of the pool. If not
+///   specified, the trait will try to use [`Default`] trait implementation.
+///   Besides paths to functions, the generator can be a closure without
+///   captures (`|| Type(42)`) or any other expression (`Type(42)`), which is
+///   evaluated each time a new object is created.
+/// - `generator_try = function, error = Type`: a fallible generator function
+///   for the pool, along with its error type. The macro will also implement
+///   the `TryObjectPool` trait, so objects can be created with
+///   `TryObjectPool::try_new`, which propagates the errors of the generator.
+///   The pool has no infallible generator, so `ObjectPool::new` panics if the
+///   pool is empty.
+/// - `test_roundtrip`: emits a `#[cfg(test)]` unit test (named
+///   `<type>_object_pool_roundtrip` in snake case) that round-trips an object
+///   through the pool, so every pooled type gets baseline correctness
+///   coverage for free. The test checks that the returned object is handed
+///   out again and passes the validator of the pool. With
+///   `test_roundtrip = function`, the object is dirtied with the function
+///   (taking a mutable reference to it) before being returned, and if the type implements
+///   `PartialEq`, the reacquired object must equal a new one.
+/// - `reuse_only`: the pool has no generator function, so it can only hand out
+///   objects that were inserted into it (see `Pool::reuse_only`).
+/// - `capacity = N`: the pool never stores more than `N` idle objects, the
+///   extra returned objects are dropped (see `Pool::with_capacity`).
+/// - `min_idle = N`: each maintenance pass tops the pool up to `N` idle objects
+///   (see `Pool::with_min_idle`).
+/// - `growth = N`: when the pool is empty, `N` objects are created at once,
+///   one is handed out and the rest are stored in the pool (see
+///   `Pool::with_growth`).
+/// - `prefill = N`: the pool is filled with `N` objects the first time it is
+///   used, or when `ObjectPool::warm_pool` is called (see
+///   `Pool::with_prefill`).
+/// - `recycle`: the returned objects are reset with the `Recycle` trait before
+///   being stored in the pool (see `Pool::with_recycle`).
+/// - `max_reuses = N`: objects that were handed out again `N` times are
+///   dropped instead of being returned to the pool (see
+///   `Pool::with_max_reuses`).
+/// - `max_bytes = N`: returned objects that would make the total heap size of
+///   the idle objects exceed `N` bytes are dropped, the type must implement
+///   the `HeapSize` trait (see `Pool::with_max_bytes`).
+/// - `on_create = function`, `on_reuse = function`, `on_return = function`
+///   and `on_discard = function`: lifecycle hooks called with each object
+///   newly created, handed out again, returned or dropped by the pool (see
+///   `Pool::on_create`, `Pool::on_reuse`, `Pool::on_return` and
+///   `Pool::on_discard`).
+/// - `validator = function`: idle objects that fail the predicate are
+///   discarded instead of being handed out again (see `Pool::with_validator`).
+/// - `zero_on_acquire`: the objects are zeroed before being handed out, the
+///   type must be `Copy` and implement the unsafe `Zeroable` trait (see
+///   `Pool::zero_on_acquire`).
+/// - `lock_free = N`: the pool uses a lock-free backend of `N` slots, so
+///   acquiring and returning objects doesn't lock the pool under contention
+///   (see `Pool::with_lock_free`).
+/// - `const_items = [...]`: the pool starts with the specified objects, which
+///   are evaluated at compile time, so the type must be `Copy` (see
+///   `Pool::with_initial_items`).
+/// - `async`: generates an asynchronous pool (an `AsyncPool` and an
+///   `AsyncObjectPool` implementation) instead, whose objects are acquired with
+///   `T::acquire().await`. Requires the `tokio` feature and can only be
+///   combined with a generator.
+/// - `static_capacity = N`: generates a pool backed by a fixed-size array of
+///   `N` objects (a `StaticPool` and a `StaticObjectPool` implementation)
+///   instead, which never allocates on the heap. Can only be combined with a
+///   generator.
+/// - `name = "name"`: the name of the pool, which identifies it in its
+///   statistics, logs and the listings of the registry instead of the name of
+///   its static (see `Pool::with_name`).
+/// - `static_name = "NAME"`: the name of the generated static, instead of
+///   `<TYPE>_OBJECT_POOL`.
+/// - `vis = "pub(crate)"`: the visibility of the generated static, which is
+///   private by default, so the pool can be used directly by other modules.
+/// - `crate = path`: the path of the `derivable_object_pool` crate, used by the
+///   generated code instead of `::derivable_object_pool` (for example if the
+///   crate is renamed or re-exported by another crate).
+///
+/// ```rust
+/// use derivable_object_pool::prelude::*;
+///
+/// #[derive(ObjectPool)]
+/// #[pool(generator = Buffer::with_capacity, capacity = 64, test_roundtrip)]
+/// struct Buffer(Vec<u8>);
+///
+/// impl Buffer {
+///     fn with_capacity() -> Self {
+///         Buffer(Vec::with_capacity(1024))
+///     }
+/// }
+/// ```
+///
 /// ## generator
 ///
-/// Specify a generator function for the pool. If not specified, the trait will
-/// try to use [`Default`] trait implementation.
+/// Same as the `generator` option of `#[pool(...)]`: `#[generator(function)]`
+/// or `#[generator = "function"]` (the string literal form is also accepted by
+/// the options of `#[pool(...)]`).
 ///
+/// ## generator_try
+///
+/// Same as the `generator_try` and `error` options of `#[pool(...)]`:
+/// `#[generator_try(function, error = Type)]`.
 ///
 /// [`ObjectPool`]: trait.ObjectPool.html
 /// [`Default`]: https://doc.rust-lang.org/std/default/trait.Default.html
-#[proc_macro_derive(ObjectPool, attributes(generator))]
+#[proc_macro_derive(ObjectPool, attributes(generator, generator_try, pool))]
 pub fn object_pool_derive_macro(tokens: TokenStream) -> TokenStream {
-    let ast: DeriveInput = syn::parse(tokens).unwrap();
+    let ast = syn::parse_macro_input!(tokens as DeriveInput);
 
     impl_object_pool_derive_macro(ast)
 }
+
+/// Returns the type of the objects of a `Pool<T>` type.
+fn pool_item_type(ty: &syn::Type) -> Option<&syn::Type> {
+    let syn::Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Pool" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    args.args.iter().find_map(|arg| match arg {
+        syn::GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    })
+}
+
+fn impl_global_pool_macro(item: ItemStatic, krate: syn::Path) -> TokenStream {
+    let vis = &item.vis;
+    let ident = &item.ident;
+    let ty = &item.ty;
+    let module = quote::format_ident!("{}", ident.to_string().to_ascii_lowercase());
+    let doc = format!("Accessors for the [`{ident}`] pool.");
+    let Some(item_ty) = pool_item_type(ty) else {
+        return syn::Error::new_spanned(ty, "global_pool static must be of type `Pool<T>`")
+            .to_compile_error()
+            .into();
+    };
+
+    quote::quote! {
+        #item
+
+        #[doc = #doc]
+        #vis mod #module {
+            #[allow(unused_imports)]
+            use super::*;
+
+            /// Returns a reference to the pool.
+            #[inline]
+            pub fn pool() -> &'static #ty {
+                super::#ident.register(::core::stringify!(#ident));
+                &super::#ident
+            }
+
+            /// Returns a cloneable handle to the pool.
+            #[inline]
+            pub fn handle() -> #krate::PoolHandle<#item_ty> {
+                #krate::PoolHandle::from_static(pool())
+            }
+        }
+    }
+    .into()
+}
+
+/// Attribute macro for static pools of types that can't derive [`ObjectPool`]
+/// (for example foreign types like `Vec<u8>`). The static is left untouched
+/// and a module with the name of the static in lowercase is generated next to
+/// it, containing accessor functions for the pool:
+///
+/// - `pool()`: returns a `&'static` reference to the pool.
+/// - `handle()`: returns a [`PoolHandle`] to the pool, which can be used to
+///   acquire objects that are returned to the pool when dropped.
+///
+/// The generated module has the same visibility as the static. The pool is
+/// registered (see the `registry` module) with the name of the static the first
+/// time it is accessed through the module.
+///
+/// If the `derivable_object_pool` crate was renamed or re-exported, its path
+/// can be specified with `#[global_pool(crate = path)]`.
+///
+/// # Example
+///
+/// ```rust
+/// use derivable_object_pool::prelude::*;
+/// use derivable_object_pool::registry;
+///
+/// #[global_pool]
+/// static BUF_POOL: Pool<Vec<u8>> = Pool::new(Vec::new);
+///
+/// fn main() {
+///     let mut buf = buf_pool::handle().acquire();
+///     buf.push(1);
+///     drop(buf);
+///     assert_eq!(buf_pool::pool().len(), 1);
+///     assert!(registry::iter().any(|pool| pool.name() == "BUF_POOL"));
+/// }
+/// ```
+///
+/// [`ObjectPool`]: trait.ObjectPool.html
+/// [`PoolHandle`]: struct.PoolHandle.html
+#[proc_macro_attribute]
+pub fn global_pool(attr: TokenStream, tokens: TokenStream) -> TokenStream {
+    let mut krate = None;
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("crate") {
+            set_once(&mut krate, meta.value()?.parse()?, &meta.path, "crate")
+        } else {
+            Err(meta.error("unsupported global_pool attribute"))
+        }
+    });
+    syn::parse_macro_input!(attr with parser);
+    let item = syn::parse_macro_input!(tokens as ItemStatic);
+    let krate = krate.unwrap_or_else(|| syn::parse_quote! { ::derivable_object_pool });
+
+    impl_global_pool_macro(item, krate)
+}
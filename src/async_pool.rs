@@ -0,0 +1,209 @@
+use core::borrow::{Borrow, BorrowMut};
+use core::mem::{forget, ManuallyDrop};
+use core::ops::{Deref, DerefMut};
+
+use tokio::sync::{Mutex, MutexGuard};
+
+use crate::inbox::Inbox;
+
+/// Trait that allows a type to be used in an [`AsyncPool`]. It is the
+/// asynchronous equivalent of [`ObjectPool`](crate::ObjectPool): acquiring an
+/// object awaits the lock of the pool instead of blocking the thread, so it
+/// doesn't block the executor under contention.
+///
+/// The trait can be derived with
+/// [`#[derive(ObjectPool)]`](derive@crate::ObjectPool) and the `#[pool(async)]`
+/// attribute.
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::prelude::*;
+///
+/// #[derive(Default, ObjectPool)]
+/// #[pool(async)]
+/// struct Buffer(Vec<u8>);
+///
+/// #[tokio::main(flavor = "current_thread")]
+/// async fn main() {
+///     let mut buffer = Buffer::acquire().await;
+///     buffer.0.push(1);
+///     drop(buffer); // buffer is returned to the pool
+///     assert_eq!(Buffer::pool().len().await, 1);
+/// }
+/// ```
+pub trait AsyncObjectPool: Sized {
+    /// Returns a reference to the pool of the type.
+    fn pool<'a>() -> &'a AsyncPool<Self>;
+
+    /// Acquires an object from the pool, or creates a new one if the pool is
+    /// empty, awaiting the lock of the pool.
+    #[inline]
+    fn acquire() -> impl core::future::Future<Output = AsyncReusable<Self>> + Send
+    where
+        Self: Send + 'static,
+    {
+        async { AsyncReusable::new(Self::pool().take_or_generate().await) }
+    }
+}
+
+/// A pool of objects whose lock is a [`tokio::sync::Mutex`], so it can be held
+/// across `.await` points and acquiring objects doesn't block the executor.
+/// Returning an object never waits: if the pool is locked, the object is pushed
+/// into a lock-free inbox that is drained the next time the pool is locked.
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::AsyncPool;
+///
+/// static POOL: AsyncPool<Vec<u8>> = AsyncPool::new(Vec::new);
+///
+/// #[tokio::main(flavor = "current_thread")]
+/// async fn main() {
+///     let buffer = POOL.take_or_generate().await;
+///     POOL.insert(buffer);
+///     assert_eq!(POOL.len().await, 1);
+/// }
+/// ```
+pub struct AsyncPool<T> {
+    /// The idle objects of the pool.
+    pool: Mutex<Vec<T>>,
+    /// The objects returned while the pool was locked.
+    inbox: Inbox<T>,
+    /// The function used to create new objects.
+    generator: fn() -> T,
+}
+
+impl<T> AsyncPool<T> {
+    /// Creates a new asynchronous pool of objects. The pool will use the
+    /// specified generator function to create new objects.
+    #[must_use]
+    #[inline]
+    pub const fn new(generator: fn() -> T) -> Self {
+        Self {
+            pool: Mutex::const_new(Vec::new()),
+            inbox: Inbox::new(),
+            generator,
+        }
+    }
+
+    /// Locks the pool, moving the objects of the inbox into it.
+    async fn get_pool(&self) -> MutexGuard<'_, Vec<T>> {
+        let mut pool = self.pool.lock().await;
+        self.inbox.drain(|item| pool.push(item));
+        pool
+    }
+
+    /// Removes an object from the pool or, if the pool is empty, creates a new
+    /// one using the generator function.
+    pub async fn take_or_generate(&self) -> T {
+        // The lock must be released before calling the generator
+        let item = self.get_pool().await.pop();
+        item.unwrap_or_else(self.generator)
+    }
+
+    /// Removes an object from the pool, if any.
+    pub async fn remove(&self) -> Option<T> {
+        self.get_pool().await.pop()
+    }
+
+    /// Inserts an object into the pool without waiting for its lock.
+    pub fn insert(&self, item: T) {
+        match self.pool.try_lock() {
+            Ok(mut pool) => pool.push(item),
+            Err(_) => self.inbox.push(item),
+        }
+    }
+
+    /// Returns the number of objects in the pool.
+    pub async fn len(&self) -> usize {
+        self.get_pool().await.len()
+    }
+
+    /// Returns `true` if the pool is empty.
+    pub async fn is_empty(&self) -> bool {
+        self.get_pool().await.is_empty()
+    }
+
+    /// Removes all objects from the pool.
+    pub async fn clear(&self) {
+        self.get_pool().await.clear();
+    }
+}
+
+/// A wrapper for an object acquired from an [`AsyncPool`] that returns the
+/// object to the pool when it is dropped. It is the asynchronous equivalent of
+/// [`Reusable`](crate::Reusable).
+pub struct AsyncReusable<T: AsyncObjectPool> {
+    item: ManuallyDrop<T>,
+}
+
+impl<T: AsyncObjectPool> AsyncReusable<T> {
+    /// Creates a new wrapper for the specified object.
+    #[inline]
+    const fn new(item: T) -> Self {
+        Self {
+            item: ManuallyDrop::new(item),
+        }
+    }
+
+    /// Returns the owned object inside the wrapper without returning it to
+    /// the pool.
+    #[inline]
+    pub fn into_inner(mut self) -> T {
+        let ret = unsafe { ManuallyDrop::take(&mut self.item) };
+        forget(self);
+        ret
+    }
+}
+
+impl<T: AsyncObjectPool> Borrow<T> for AsyncReusable<T> {
+    #[inline]
+    fn borrow(&self) -> &T {
+        &self.item
+    }
+}
+
+impl<T: AsyncObjectPool> BorrowMut<T> for AsyncReusable<T> {
+    #[inline]
+    fn borrow_mut(&mut self) -> &mut T {
+        &mut self.item
+    }
+}
+
+impl<T: AsyncObjectPool> AsRef<T> for AsyncReusable<T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        &self.item
+    }
+}
+
+impl<T: AsyncObjectPool> AsMut<T> for AsyncReusable<T> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut T {
+        &mut self.item
+    }
+}
+
+impl<T: AsyncObjectPool> Deref for AsyncReusable<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.item
+    }
+}
+
+impl<T: AsyncObjectPool> DerefMut for AsyncReusable<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.item
+    }
+}
+
+impl<T: AsyncObjectPool> Drop for AsyncReusable<T> {
+    #[inline]
+    fn drop(&mut self) {
+        let item = unsafe { ManuallyDrop::take(&mut self.item) };
+        T::pool().insert(item);
+    }
+}
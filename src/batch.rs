@@ -0,0 +1,126 @@
+use crate::{ObjectPool, Pool, Reusable};
+
+/// Allows acquiring objects of several pooled types at once. This trait is
+/// implemented for tuples of up to six types implementing [`ObjectPool`].
+///
+/// The objects are always acquired in a canonical order (sorted by the address
+/// of their pools) regardless of the order of the types in the tuple. This way,
+/// handlers that need several pooled resources at the same time acquire them
+/// in the same order, so pools that make acquirers wait can't deadlock when two
+/// handlers request the same resources in different orders. The objects of the
+/// pools bounded with [`Pool::with_max_outstanding`] are acquired like
+/// [`Pool::acquire_blocking`], waiting for an object to be returned while the
+/// pool reached its maximum number of checked out objects.
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::prelude::*;
+///
+/// #[derive(Default, ObjectPool)]
+/// struct Buffer(Vec<u8>);
+///
+/// #[derive(Default, ObjectPool)]
+/// struct Parser(usize);
+///
+/// fn main() {
+///     let (buffer, parser) = <(Buffer, Parser)>::acquire_all();
+///     let (parser2, buffer2) = <(Parser, Buffer)>::acquire_all();
+///     drop((buffer, parser, parser2, buffer2));
+///     assert_eq!(Buffer::pool().len(), 2);
+///     assert_eq!(Parser::pool().len(), 2);
+/// }
+/// ```
+pub trait AcquireAll {
+    /// The tuple of reusable objects that are acquired.
+    type Output;
+
+    /// Acquires one object of each type in the tuple, in a canonical order.
+    fn acquire_all() -> Self::Output;
+}
+
+/// Returns the address of the pool of the specified type, which is used as the
+/// canonical acquisition order.
+#[inline]
+fn pool_address<T: ObjectPool>() -> usize {
+    T::pool() as *const Pool<T> as usize
+}
+
+/// Acquires an object of the specified type, waiting for an object to be
+/// returned if its pool is bounded (see [`Pool::with_max_outstanding`]).
+#[inline]
+fn acquire<T: ObjectPool>() -> Reusable<T> {
+    #[cfg(feature = "std")]
+    {
+        let pool = T::pool();
+        if pool.max_outstanding() != usize::MAX {
+            let item = pool.take_until(None).unwrap_or_else(|err| panic!("{err}"));
+            return Reusable::reserved(pool, item);
+        }
+    }
+    T::new()
+}
+
+macro_rules! impl_acquire_all {
+    ($($name:ident $idx:tt),+) => {
+        impl<$($name: ObjectPool),+> AcquireAll for ($($name,)+) {
+            type Output = ($(Reusable<$name>,)+);
+
+            fn acquire_all() -> Self::Output {
+                let mut order = [$((pool_address::<$name>(), $idx)),+];
+                order.sort_unstable();
+
+                let mut acquired = ($(None::<Reusable<$name>>,)+);
+                for (_, idx) in order {
+                    match idx {
+                        $($idx => acquired.$idx = Some(acquire::<$name>()),)+
+                        _ => unreachable!(),
+                    }
+                }
+                ($(acquired.$idx.unwrap(),)+)
+            }
+        }
+    };
+}
+
+impl_acquire_all!(A 0);
+impl_acquire_all!(A 0, B 1);
+impl_acquire_all!(A 0, B 1, C 2);
+impl_acquire_all!(A 0, B 1, C 2, D 3);
+impl_acquire_all!(A 0, B 1, C 2, D 3, E 4);
+impl_acquire_all!(A 0, B 1, C 2, D 3, E 4, F 5);
+
+/// Acquires one object of each of the two specified types in a canonical order.
+/// This is a shorthand for [`AcquireAll::acquire_all`] on the tuple `(A, B)`.
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::prelude::*;
+/// use derivable_object_pool::acquire_two;
+///
+/// #[derive(Default, ObjectPool)]
+/// struct Request(String);
+///
+/// #[derive(Default, ObjectPool)]
+/// struct Response(String);
+///
+/// fn main() {
+///     let (mut request, mut response) = acquire_two::<Request, Response>();
+///     request.0.push_str("ping");
+///     response.0.push_str("pong");
+/// }
+/// ```
+#[must_use]
+#[inline]
+pub fn acquire_two<A: ObjectPool, B: ObjectPool>() -> (Reusable<A>, Reusable<B>) {
+    <(A, B)>::acquire_all()
+}
+
+/// Acquires one object of each of the three specified types in a canonical
+/// order. This is a shorthand for [`AcquireAll::acquire_all`] on the tuple
+/// `(A, B, C)`.
+#[must_use]
+#[inline]
+pub fn acquire_three<A: ObjectPool, B: ObjectPool, C: ObjectPool>(
+) -> (Reusable<A>, Reusable<B>, Reusable<C>) {
+    <(A, B, C)>::acquire_all()
+}
@@ -0,0 +1,187 @@
+use alloc::collections::VecDeque;
+use core::mem::forget;
+use core::sync::atomic::Ordering;
+use core::time::Duration;
+use std::sync::{Condvar, Mutex, MutexGuard};
+use std::time::Instant;
+
+use crate::stamp::Stamp;
+use crate::{AcquireError, AcquireErrorKind, Fairness, Pool};
+
+/// Where the blocking acquisitions of a pool wait for objects to be returned.
+pub(crate) struct Gate {
+    queue: Mutex<Queue>,
+    available: Condvar,
+}
+
+/// The tickets of the acquisitions waiting in arrival order, used by pools
+/// with [`Fairness::Fifo`].
+struct Queue {
+    next: u64,
+    waiting: VecDeque<u64>,
+}
+
+impl Queue {
+    /// Returns `true` if the acquisition with the ticket (if any) is the next
+    /// one to be served.
+    #[inline]
+    fn is_next(&self, ticket: Option<u64>) -> bool {
+        ticket.is_none() || self.waiting.front().copied() == ticket
+    }
+}
+
+impl Gate {
+    #[inline]
+    pub(crate) const fn new() -> Self {
+        Self {
+            queue: Mutex::new(Queue {
+                next: 0,
+                waiting: VecDeque::new(),
+            }),
+            available: Condvar::new(),
+        }
+    }
+
+    #[inline]
+    fn lock(&self) -> MutexGuard<'_, Queue> {
+        self.queue.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    #[inline]
+    fn wait<'a>(&self, guard: MutexGuard<'a, Queue>) -> MutexGuard<'a, Queue> {
+        self.available
+            .wait(guard)
+            .unwrap_or_else(|err| err.into_inner())
+    }
+
+    #[inline]
+    fn wait_timeout<'a>(
+        &self,
+        guard: MutexGuard<'a, Queue>,
+        timeout: Duration,
+    ) -> MutexGuard<'a, Queue> {
+        self.available
+            .wait_timeout(guard, timeout)
+            .map_or_else(|err| err.into_inner().0, |(guard, _)| guard)
+    }
+
+    /// Wakes up a waiting acquisition. The lock is taken so the notification
+    /// can't be lost between the checks of a waiter and its wait.
+    #[inline]
+    fn notify_one(&self) {
+        drop(self.lock());
+        self.available.notify_one();
+    }
+
+    /// Wakes up all the waiting acquisitions.
+    #[inline]
+    pub(crate) fn notify_all(&self) {
+        drop(self.lock());
+        self.available.notify_all();
+    }
+}
+
+/// A slot of checked out object reserved by a blocking acquisition, which is
+/// given back if the acquisition fails (or panics).
+struct Reservation<'a, T>(&'a Pool<T>);
+
+impl<T> Drop for Reservation<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.0.outstanding.fetch_sub(1, Ordering::Relaxed);
+        self.0.wake_waiters();
+    }
+}
+
+/// The place of an acquisition in the queue of a fair pool, which is left
+/// when the acquisition finishes (or panics).
+struct Ticket<'a, T> {
+    pool: &'a Pool<T>,
+    id: u64,
+}
+
+impl<T> Drop for Ticket<'_, T> {
+    fn drop(&mut self) {
+        self.pool.gate.lock().waiting.retain(|&id| id != self.id);
+        // The next acquisition in the queue may be able to proceed
+        self.pool.gate.available.notify_all();
+    }
+}
+
+impl<T> Pool<T> {
+    /// Wakes up the blocking acquisitions waiting for an object. Fair pools
+    /// wake all of them, so the next one in the queue is never missed.
+    #[inline]
+    pub(crate) fn wake_waiters(&self) {
+        match self.fairness {
+            Fairness::Unfair => self.gate.notify_one(),
+            Fairness::Fifo => self.gate.notify_all(),
+        }
+    }
+
+    /// Reserves a slot of checked out object, if the pool didn't reach its
+    /// maximum number of checked out objects.
+    #[inline]
+    fn try_reserve(&self) -> Option<Reservation<'_, T>> {
+        self.outstanding
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |outstanding| {
+                (outstanding < self.max_outstanding()).then_some(outstanding + 1)
+            })
+            .ok()
+            .map(|outstanding| {
+                self.counters.observe_outstanding(outstanding + 1);
+                Reservation(self)
+            })
+    }
+
+    /// Removes an object from the pool or creates a new one, waiting for an
+    /// object to be returned while the pool reached its maximum number of
+    /// checked out objects or it is empty and can't create new objects. Fails
+    /// if the deadline (if any) passes first. The object is already counted as
+    /// outstanding.
+    pub(crate) fn take_until(&self, deadline: Option<Instant>) -> Result<(T, Stamp), AcquireError> {
+        let mut gate = self.gate.lock();
+        let ticket = match self.fairness {
+            Fairness::Unfair => None,
+            Fairness::Fifo => {
+                let id = gate.next;
+                gate.next += 1;
+                gate.waiting.push_back(id);
+                Some(Ticket { pool: self, id })
+            }
+        };
+        let id = ticket.as_ref().map(|ticket| ticket.id);
+        loop {
+            if gate.is_next(id) {
+                if let Some(reservation) = self.try_reserve() {
+                    // The generator is called without holding the gate
+                    drop(gate);
+                    if let Ok(item) = self.try_take_or_generate() {
+                        forget(reservation);
+                        return Ok(item);
+                    }
+                    drop(reservation);
+                    gate = self.gate.lock();
+                    // An object may have been returned before taking the gate
+                    if !self.is_empty() {
+                        continue;
+                    }
+                }
+            }
+            #[cfg(feature = "tracing")]
+            tracing::debug!(pool = self.diagnostic_name(), "waiting for an object");
+            gate = match deadline {
+                None => self.gate.wait(gate),
+                Some(deadline) => {
+                    let timeout = deadline.saturating_duration_since(Instant::now());
+                    if timeout.is_zero() {
+                        // The ticket takes the gate to leave the queue
+                        drop(gate);
+                        return Err(self.acquire_error(AcquireErrorKind::TimedOut));
+                    }
+                    self.gate.wait_timeout(gate, timeout)
+                }
+            };
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use alloc::vec::Vec;
+use core::borrow::{Borrow, BorrowMut};
+use core::mem::{forget, ManuallyDrop};
+use core::ops::{Deref, DerefMut};
+
+use crate::sync::Mutex;
+
+/// The capacity of the buffers of each size class of a [`BufferPool`].
+const CLASSES: [usize; 3] = [4 * 1024, 64 * 1024, 1024 * 1024];
+
+/// A pool of byte buffers bucketed by capacity class (4 KiB, 64 KiB and
+/// 1 MiB). Acquiring a buffer returns the smallest pooled buffer that fits the
+/// requested size, and buffers are shrunk to their class on return, so a
+/// single huge request doesn't keep a huge buffer alive in the pool.
+///
+/// Requests bigger than the largest class are served by a new buffer of the
+/// exact size, which is shrunk to the largest class when returned. Buffers
+/// smaller than the smallest class are not pooled.
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::BufferPool;
+///
+/// static BUFFERS: BufferPool = BufferPool::new();
+///
+/// fn main() {
+///     let mut buffer = BUFFERS.get(1000);
+///     assert!(buffer.capacity() >= 4096);
+///     buffer.extend_from_slice(b"hello");
+///     drop(buffer);
+///
+///     // The buffer is cleared and reused for any request of its class
+///     let buffer = BUFFERS.get(4096);
+///     assert!(buffer.is_empty());
+///     assert_eq!(BUFFERS.len(), 0);
+/// }
+/// ```
+pub struct BufferPool {
+    classes: [Mutex<Vec<Vec<u8>>>; CLASSES.len()],
+}
+
+impl BufferPool {
+    /// Creates a new empty buffer pool.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            classes: [const { Mutex::new(Vec::new()) }; CLASSES.len()],
+        }
+    }
+
+    /// Returns the index of the smallest class that fits the size.
+    #[inline]
+    fn class_for(size: usize) -> Option<usize> {
+        CLASSES.iter().position(|&class| class >= size)
+    }
+
+    /// Acquires an empty buffer with a capacity of at least `size` bytes. The
+    /// buffer is returned to the pool when the wrapper is dropped.
+    pub fn get(&self, size: usize) -> BufferReusable<'_> {
+        BufferReusable::new(self.take_or_allocate(size), self)
+    }
+
+    /// Removes an empty buffer with a capacity of at least `size` bytes from
+    /// the pool or, if there is none, allocates a new one.
+    pub fn take_or_allocate(&self, size: usize) -> Vec<u8> {
+        let Some(class) = Self::class_for(size) else {
+            return Vec::with_capacity(size);
+        };
+        // Larger classes also fit the size, but taking from them would leave
+        // the requests that need them without buffers
+        let buffer = self.classes[class].lock().pop();
+        buffer.unwrap_or_else(|| Vec::with_capacity(CLASSES[class]))
+    }
+
+    /// Inserts a buffer into the pool, clearing it and shrinking it to the
+    /// largest class it fits. Buffers smaller than the smallest class are
+    /// dropped.
+    pub fn insert(&self, mut buffer: Vec<u8>) {
+        let Some(class) = CLASSES
+            .iter()
+            .rposition(|&class| class <= buffer.capacity())
+        else {
+            return;
+        };
+        buffer.clear();
+        buffer.shrink_to(CLASSES[class]);
+        self.classes[class].lock().push(buffer);
+    }
+
+    /// Returns the number of buffers in the pool.
+    pub fn len(&self) -> usize {
+        self.classes.iter().map(|class| class.lock().len()).sum()
+    }
+
+    /// Returns `true` if the pool is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all buffers from the pool.
+    pub fn clear(&self) {
+        for class in &self.classes {
+            let removed = core::mem::take(&mut *class.lock());
+            drop(removed);
+        }
+    }
+}
+
+impl Default for BufferPool {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A wrapper for a buffer acquired from a [`BufferPool`] that returns the
+/// buffer to the pool when it is dropped.
+pub struct BufferReusable<'a> {
+    buffer: ManuallyDrop<Vec<u8>>,
+    pool: &'a BufferPool,
+}
+
+impl<'a> BufferReusable<'a> {
+    #[inline]
+    fn new(buffer: Vec<u8>, pool: &'a BufferPool) -> Self {
+        Self {
+            buffer: ManuallyDrop::new(buffer),
+            pool,
+        }
+    }
+
+    /// Returns the owned buffer inside the wrapper without returning it to
+    /// the pool.
+    #[inline]
+    pub fn into_inner(mut self) -> Vec<u8> {
+        let ret = unsafe { ManuallyDrop::take(&mut self.buffer) };
+        forget(self);
+        ret
+    }
+}
+
+impl Borrow<Vec<u8>> for BufferReusable<'_> {
+    #[inline]
+    fn borrow(&self) -> &Vec<u8> {
+        &self.buffer
+    }
+}
+
+impl BorrowMut<Vec<u8>> for BufferReusable<'_> {
+    #[inline]
+    fn borrow_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.buffer
+    }
+}
+
+impl AsRef<[u8]> for BufferReusable<'_> {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.buffer
+    }
+}
+
+impl AsMut<[u8]> for BufferReusable<'_> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.buffer
+    }
+}
+
+impl Deref for BufferReusable<'_> {
+    type Target = Vec<u8>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.buffer
+    }
+}
+
+impl DerefMut for BufferReusable<'_> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.buffer
+    }
+}
+
+impl Drop for BufferReusable<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        let buffer = unsafe { ManuallyDrop::take(&mut self.buffer) };
+        self.pool.insert(buffer);
+    }
+}
@@ -0,0 +1,159 @@
+#[cfg(feature = "leak-detection")]
+use alloc::collections::BTreeMap;
+#[cfg(feature = "leak-detection")]
+use alloc::vec::Vec;
+#[cfg(feature = "leak-detection")]
+use core::fmt;
+#[cfg(feature = "leak-detection")]
+use core::sync::atomic::{AtomicU64, Ordering};
+#[cfg(feature = "leak-detection")]
+use core::time::Duration;
+#[cfg(feature = "debug-tracking")]
+use std::backtrace::Backtrace;
+#[cfg(feature = "leak-detection")]
+use std::sync::Mutex;
+#[cfg(feature = "leak-detection")]
+use std::time::Instant;
+
+use crate::stamp::Stamp;
+
+/// A checked out object held for longer than the threshold of
+/// [`Pool::report_overdue`]. Objects that are never returned to the pool are
+/// effectively leaks, and they show up here. Only tracked with the
+/// `leak-detection` feature.
+///
+/// [`Pool::report_overdue`]: crate::Pool::report_overdue
+#[cfg(feature = "leak-detection")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Overdue {
+    /// The type of the object.
+    pub type_name: &'static str,
+    /// The label of the checkout, if it was labeled (see
+    /// [`Reusable::labeled`](crate::Reusable::labeled)).
+    pub label: Option<&'static str>,
+    /// How long the object has been checked out.
+    pub held_for: Duration,
+    /// Where the object was acquired, rendered from the backtrace captured at
+    /// checkout. Only captured with the `debug-tracking` feature.
+    #[cfg(feature = "debug-tracking")]
+    pub backtrace: String,
+}
+
+#[cfg(feature = "leak-detection")]
+impl fmt::Display for Overdue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.type_name)?;
+        if let Some(label) = self.label {
+            write!(f, " ({label})")?;
+        }
+        write!(f, " held for {:?}", self.held_for)?;
+        #[cfg(feature = "debug-tracking")]
+        write!(f, ", acquired at:\n{}", self.backtrace)?;
+        Ok(())
+    }
+}
+
+/// A tracked checkout.
+#[cfg(feature = "leak-detection")]
+struct Checkout {
+    since: Instant,
+    label: Option<&'static str>,
+    #[cfg(feature = "debug-tracking")]
+    backtrace: Backtrace,
+}
+
+/// The live checkouts of a pool. Without the `leak-detection` feature this is
+/// a zero-sized type and tracking a checkout compiles to nothing.
+pub(crate) struct Checkouts {
+    /// The identifier of the next checkout (zero marks untracked objects).
+    #[cfg(feature = "leak-detection")]
+    next: AtomicU64,
+    #[cfg(feature = "leak-detection")]
+    live: Mutex<BTreeMap<u64, Checkout>>,
+}
+
+impl Checkouts {
+    #[inline]
+    pub(crate) const fn new() -> Self {
+        Self {
+            #[cfg(feature = "leak-detection")]
+            next: AtomicU64::new(1),
+            #[cfg(feature = "leak-detection")]
+            live: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Starts tracking a checkout, recording its identifier in the stamp.
+    #[inline(always)]
+    pub(crate) fn track(&self, stamp: &mut Stamp) {
+        #[cfg(feature = "leak-detection")]
+        {
+            let id = self.next.fetch_add(1, Ordering::Relaxed);
+            let checkout = Checkout {
+                since: Instant::now(),
+                label: None,
+                #[cfg(feature = "debug-tracking")]
+                backtrace: Backtrace::force_capture(),
+            };
+            self.live().insert(id, checkout);
+            stamp.checkout = id;
+        }
+        #[cfg(not(feature = "leak-detection"))]
+        let _ = stamp;
+    }
+
+    /// Stops tracking a checkout, once the object is returned or taken out of
+    /// its guard.
+    #[inline(always)]
+    pub(crate) fn untrack(&self, stamp: Stamp) {
+        #[cfg(feature = "leak-detection")]
+        if stamp.checkout != 0 {
+            self.live().remove(&stamp.checkout);
+        }
+        #[cfg(not(feature = "leak-detection"))]
+        let _ = stamp;
+    }
+
+    /// Labels a tracked checkout.
+    #[inline(always)]
+    pub(crate) fn label(&self, stamp: Stamp, label: &'static str) {
+        #[cfg(feature = "leak-detection")]
+        if let Some(checkout) = self.live().get_mut(&stamp.checkout) {
+            checkout.label = Some(label);
+        }
+        #[cfg(not(feature = "leak-detection"))]
+        let _ = (stamp, label);
+    }
+
+    /// Returns the checkouts held for longer than the threshold, the oldest
+    /// first.
+    #[cfg(feature = "leak-detection")]
+    pub(crate) fn overdue(&self, threshold: Duration, type_name: &'static str) -> Vec<Overdue> {
+        let now = Instant::now();
+        // The identifiers are increasing, so the oldest checkouts come first
+        self.live()
+            .values()
+            .filter_map(|checkout| {
+                let held_for = now.saturating_duration_since(checkout.since);
+                if held_for <= threshold {
+                    return None;
+                }
+                Some(Overdue {
+                    type_name,
+                    label: checkout.label,
+                    held_for,
+                    // The backtrace is only resolved for the reported objects
+                    #[cfg(feature = "debug-tracking")]
+                    backtrace: checkout.backtrace.to_string(),
+                })
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "leak-detection")]
+    #[inline]
+    fn live(&self) -> std::sync::MutexGuard<'_, BTreeMap<u64, Checkout>> {
+        self.live.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
@@ -0,0 +1,226 @@
+#[cfg(feature = "counters")]
+use core::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+/// An event of the lifecycle of the pooled objects, recorded by the enabled
+/// counters of the pool.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Event {
+    /// An idle object was reused by an acquisition.
+    Hit,
+    /// An acquisition found no idle objects.
+    Miss,
+    /// An object was created by the generator of the pool.
+    Created,
+    /// An object was created to be stored idle in the pool (like by
+    /// [`Pool::prefill`](crate::Pool::prefill)) instead of being handed out.
+    Prefilled,
+    /// A returned object was stored in the pool.
+    Returned,
+    /// A returned object was dropped by the policies of the pool.
+    Rejected,
+}
+
+impl Event {
+    /// Returns the name of the event, as reported by the diagnostics of the
+    /// pool.
+    #[cfg(feature = "tracing")]
+    pub(crate) const fn name(self) -> &'static str {
+        match self {
+            Self::Hit => "reused",
+            Self::Miss => "missed",
+            Self::Created => "created",
+            Self::Prefilled => "prefilled",
+            Self::Returned => "returned",
+            Self::Rejected => "rejected",
+        }
+    }
+}
+
+/// A snapshot of the statistics of a pool (see [`Pool::stats`]), which tells
+/// whether pooling is helping a workload. The statistics are only tracked with
+/// the `counters` feature.
+///
+/// [`Pool::stats`]: crate::Pool::stats
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::prelude::*;
+///
+/// #[derive(Default, ObjectPool)]
+/// struct Test(i32);
+///
+/// fn main() {
+///     drop(Test::new());
+///     drop(Test::new());
+///
+///     let stats = Test::pool().stats();
+///     assert_eq!(stats.created, 1);
+///     assert_eq!(stats.hits, 1);
+///     assert_eq!(stats.misses, 1);
+///     assert_eq!(stats.returned, 2);
+///     assert_eq!(stats.hit_rate(), 0.5);
+///     assert_eq!(stats.peak_outstanding, 1);
+///     assert_eq!(stats.peak_idle, 1);
+/// }
+/// ```
+#[cfg(feature = "counters")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Stats {
+    /// The name of the pool, if it was assigned one (see
+    /// [`Pool::with_name`]).
+    ///
+    /// [`Pool::with_name`]: crate::Pool::with_name
+    pub name: Option<&'static str>,
+    /// The number of objects created by the pool, including the ones stored
+    /// idle right away (like by [`Pool::prefill`]).
+    ///
+    /// [`Pool::prefill`]: crate::Pool::prefill
+    pub created: u64,
+    /// The number of acquisitions that reused an idle object.
+    pub hits: u64,
+    /// The number of acquisitions that found no idle objects.
+    pub misses: u64,
+    /// The number of returned objects stored in the pool.
+    pub returned: u64,
+    /// The number of returned objects dropped by the policies of the pool.
+    pub rejected: u64,
+    /// The peak number of objects checked out at the same time (see
+    /// [`Pool::outstanding`]), a hint for the capacity of the pool.
+    ///
+    /// [`Pool::outstanding`]: crate::Pool::outstanding
+    pub peak_outstanding: usize,
+    /// The peak number of idle objects in the pool, a hint for how many
+    /// objects to prefill.
+    pub peak_idle: usize,
+}
+
+#[cfg(feature = "counters")]
+impl Stats {
+    /// Returns the fraction of acquisitions that reused an idle object, or
+    /// zero if there were no acquisitions.
+    #[inline]
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            total => self.hits as f64 / total as f64,
+        }
+    }
+}
+
+/// The counters of a pool. Without the `counters` feature this is a zero-sized
+/// type and recording an event compiles to nothing, so the pools don't pay
+/// for the counters unless they are enabled.
+pub(crate) struct Counters {
+    #[cfg(feature = "counters")]
+    created: AtomicU64,
+    #[cfg(feature = "counters")]
+    hits: AtomicU64,
+    #[cfg(feature = "counters")]
+    misses: AtomicU64,
+    #[cfg(feature = "counters")]
+    returned: AtomicU64,
+    #[cfg(feature = "counters")]
+    rejected: AtomicU64,
+    #[cfg(feature = "counters")]
+    peak_outstanding: AtomicUsize,
+    #[cfg(feature = "counters")]
+    peak_idle: AtomicUsize,
+}
+
+impl Counters {
+    /// Creates new counters set to zero.
+    #[inline]
+    pub(crate) const fn new() -> Self {
+        Self {
+            #[cfg(feature = "counters")]
+            created: AtomicU64::new(0),
+            #[cfg(feature = "counters")]
+            hits: AtomicU64::new(0),
+            #[cfg(feature = "counters")]
+            misses: AtomicU64::new(0),
+            #[cfg(feature = "counters")]
+            returned: AtomicU64::new(0),
+            #[cfg(feature = "counters")]
+            rejected: AtomicU64::new(0),
+            #[cfg(feature = "counters")]
+            peak_outstanding: AtomicUsize::new(0),
+            #[cfg(feature = "counters")]
+            peak_idle: AtomicUsize::new(0),
+        }
+    }
+
+    /// Sets the counters back to zero.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub(crate) fn reset(&self) {
+        #[cfg(feature = "counters")]
+        {
+            for counter in [
+                &self.created,
+                &self.hits,
+                &self.misses,
+                &self.returned,
+                &self.rejected,
+            ] {
+                counter.store(0, Ordering::Relaxed);
+            }
+            self.peak_outstanding.store(0, Ordering::Relaxed);
+            self.peak_idle.store(0, Ordering::Relaxed);
+        }
+    }
+
+    /// Records the specified event.
+    #[inline(always)]
+    pub(crate) fn record(&self, event: Event) {
+        #[cfg(feature = "counters")]
+        {
+            let counter = match event {
+                Event::Hit => &self.hits,
+                Event::Miss => &self.misses,
+                Event::Created | Event::Prefilled => &self.created,
+                Event::Returned => &self.returned,
+                Event::Rejected => &self.rejected,
+            };
+            counter.fetch_add(1, Ordering::Relaxed);
+        }
+        #[cfg(not(feature = "counters"))]
+        let _ = event;
+    }
+
+    /// Records the current number of checked out objects, keeping the peak.
+    #[inline(always)]
+    pub(crate) fn observe_outstanding(&self, outstanding: usize) {
+        #[cfg(feature = "counters")]
+        self.peak_outstanding
+            .fetch_max(outstanding, Ordering::Relaxed);
+        #[cfg(not(feature = "counters"))]
+        let _ = outstanding;
+    }
+
+    /// Records the current number of idle objects, keeping the peak.
+    #[inline(always)]
+    pub(crate) fn observe_idle(&self, idle: usize) {
+        #[cfg(feature = "counters")]
+        self.peak_idle.fetch_max(idle, Ordering::Relaxed);
+        #[cfg(not(feature = "counters"))]
+        let _ = idle;
+    }
+
+    /// Returns a snapshot of the counters of the pool with the specified
+    /// name.
+    #[cfg(feature = "counters")]
+    #[inline]
+    pub(crate) fn snapshot(&self, name: Option<&'static str>) -> Stats {
+        Stats {
+            name,
+            created: self.created.load(Ordering::Relaxed),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            returned: self.returned.load(Ordering::Relaxed),
+            rejected: self.rejected.load(Ordering::Relaxed),
+            peak_outstanding: self.peak_outstanding.load(Ordering::Relaxed),
+            peak_idle: self.peak_idle.load(Ordering::Relaxed),
+        }
+    }
+}
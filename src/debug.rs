@@ -0,0 +1,151 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::time::Duration;
+
+/// A structured snapshot of the state of a [`Pool`], returned by
+/// [`Pool::debug_state`]. It is the single source of truth of every
+/// introspection surface of the pool (its [`Display`] implementation renders a
+/// human-readable report), and can be consumed by debugger extensions.
+///
+/// [`Pool`]: crate::Pool
+/// [`Pool::debug_state`]: crate::Pool::debug_state
+/// [`Display`]: fmt::Display
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::prelude::*;
+///
+/// static POOL: Pool<Vec<u8>> = Pool::new(Vec::new).with_max_object_size(1024);
+///
+/// fn main() {
+///     POOL.insert(Vec::with_capacity(8));
+///     let state = POOL.debug_state();
+///     assert_eq!(state.idle, 1);
+///     assert_eq!(state.max_object_size, Some(1024));
+///     assert_eq!(state.items[0].heap_size, Some(8));
+///     println!("{state}");
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PoolDebugState {
+    /// The name of the pool, if it was assigned one (see
+    /// [`Pool::with_name`](crate::Pool::with_name)).
+    pub name: Option<&'static str>,
+    /// The name of the type of the pooled objects.
+    pub type_name: &'static str,
+    /// The number of idle objects stored in the pool.
+    pub idle: usize,
+    /// The number of objects whose return to the pool is staged.
+    pub staged: usize,
+    /// The maximum heap size of the objects stored by the pool, if limited.
+    pub max_object_size: Option<usize>,
+    /// The maximum total heap size of the idle objects, if limited.
+    pub max_bytes: Option<usize>,
+    /// The total heap size of the idle objects, if the pool measures them.
+    pub idle_bytes: usize,
+    /// Whether the pool has no generator function.
+    pub reuse_only: bool,
+    /// Whether the returned objects are recycled.
+    pub recycle: bool,
+    /// Whether the objects are zeroed before being handed out.
+    pub zero_on_acquire: bool,
+    /// The number of lock-free slots of the pool, if enabled.
+    pub lock_free_slots: Option<usize>,
+    /// Whether the objects are forbidden from escaping the pool.
+    pub escape_forbidden: bool,
+    /// The soft limit of idle objects of the pool, if any.
+    pub soft_limit: Option<usize>,
+    /// The maximum number of idle objects of the pool, if bounded.
+    pub capacity: Option<usize>,
+    /// The floor of idle objects the pool is topped up to.
+    pub min_idle: usize,
+    /// The number of objects created at once when an acquisition finds the
+    /// pool empty.
+    pub growth: usize,
+    /// The number of objects the pool is filled with the first time it is
+    /// used.
+    pub prefill: usize,
+    /// The time after which idle objects are evicted by the maintenance, if
+    /// any.
+    pub idle_timeout: Option<Duration>,
+    /// The maximum number of times an object is handed out again, if bounded.
+    pub max_reuses: Option<u32>,
+    /// The maximum number of objects checked out at the same time for the
+    /// blocking acquisitions, if bounded.
+    pub max_outstanding: Option<usize>,
+    /// The number of returned objects that were dropped for exceeding the
+    /// maximum heap size.
+    pub oversized_rejections: usize,
+    /// The metadata of each idle object, in insertion order.
+    pub items: Vec<ItemDebugState>,
+}
+
+/// The metadata of an idle object of a pool, part of a [`PoolDebugState`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ItemDebugState {
+    /// The heap size of the object, if the pool measures it.
+    pub heap_size: Option<usize>,
+    /// The number of times the object was already handed out again.
+    pub reuses: u32,
+}
+
+impl fmt::Display for PoolDebugState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.name {
+            Some(name) => writeln!(f, "Pool<{}> {name:?}", self.type_name)?,
+            None => writeln!(f, "Pool<{}>", self.type_name)?,
+        }
+        match self.capacity {
+            Some(capacity) => writeln!(f, "  idle: {}/{capacity}", self.idle)?,
+            None => writeln!(f, "  idle: {}", self.idle)?,
+        }
+        if self.min_idle > 0 {
+            writeln!(f, "  min idle: {}", self.min_idle)?;
+        }
+        if self.growth > 1 {
+            writeln!(f, "  growth: {}", self.growth)?;
+        }
+        if self.prefill > 0 {
+            writeln!(f, "  prefill: {}", self.prefill)?;
+        }
+        if let Some(idle_timeout) = self.idle_timeout {
+            writeln!(f, "  idle timeout: {idle_timeout:?}")?;
+        }
+        if let Some(max_reuses) = self.max_reuses {
+            writeln!(f, "  max reuses: {max_reuses}")?;
+        }
+        if let Some(max_outstanding) = self.max_outstanding {
+            writeln!(f, "  max outstanding: {max_outstanding}")?;
+        }
+        writeln!(f, "  staged: {}", self.staged)?;
+        match self.max_object_size {
+            Some(max) => writeln!(f, "  max object size: {max} bytes")?,
+            None => writeln!(f, "  max object size: unlimited")?,
+        }
+        match self.max_bytes {
+            Some(max) => writeln!(f, "  idle bytes: {}/{max}", self.idle_bytes)?,
+            None => writeln!(f, "  idle bytes: {}", self.idle_bytes)?,
+        }
+        writeln!(f, "  reuse only: {}", self.reuse_only)?;
+        writeln!(f, "  recycle: {}", self.recycle)?;
+        writeln!(f, "  zero on acquire: {}", self.zero_on_acquire)?;
+        if let Some(slots) = self.lock_free_slots {
+            writeln!(f, "  lock-free slots: {slots}")?;
+        }
+        writeln!(f, "  escape forbidden: {}", self.escape_forbidden)?;
+        if let Some(soft_limit) = self.soft_limit {
+            writeln!(f, "  soft limit: {soft_limit}")?;
+        }
+        writeln!(f, "  oversized rejections: {}", self.oversized_rejections)?;
+        for (index, item) in self.items.iter().enumerate() {
+            write!(f, "  [{index}] reuses: {}", item.reuses)?;
+            match item.heap_size {
+                Some(size) => writeln!(f, ", heap size: {size} bytes")?,
+                None => writeln!(f)?,
+            }
+        }
+        Ok(())
+    }
+}
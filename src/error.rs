@@ -0,0 +1,131 @@
+use core::error::Error;
+use core::fmt;
+
+#[cfg(feature = "counters")]
+use crate::Stats;
+
+/// The kind of an [`AcquireError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum AcquireErrorKind {
+    /// The pool has no idle objects and it can't create new ones (see
+    /// [`Pool::reuse_only`]).
+    ///
+    /// [`Pool::reuse_only`]: crate::Pool::reuse_only
+    Exhausted,
+    /// No object was returned to the pool before the timeout of the
+    /// acquisition elapsed (see [`Pool::acquire_timeout`]).
+    ///
+    /// [`Pool::acquire_timeout`]: crate::Pool::acquire_timeout
+    TimedOut,
+}
+
+impl fmt::Display for AcquireErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Exhausted => {
+                f.write_str("the pool has no idle objects and can't create new ones")
+            }
+            Self::TimedOut => f.write_str("timed out waiting for an object to be returned"),
+        }
+    }
+}
+
+/// The error returned when an object can't be acquired from a pool. Besides
+/// its [kind](AcquireErrorKind), the error holds a snapshot of the pool at the
+/// time of the failure, so logs can tell whether the failure was driven by
+/// leaked objects or by load without a separate lookup.
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::prelude::*;
+/// use derivable_object_pool::AcquireErrorKind;
+///
+/// #[derive(ObjectPool)]
+/// #[pool(reuse_only)]
+/// struct License(u32);
+///
+/// fn main() {
+///     License::pool().insert(License(1));
+///     let license = License::acquire().unwrap();
+///
+///     let Err(err) = License::acquire() else {
+///         panic!("the pool should be exhausted");
+///     };
+///     assert_eq!(err.kind(), AcquireErrorKind::Exhausted);
+///     assert_eq!(err.outstanding(), 1);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AcquireError {
+    kind: AcquireErrorKind,
+    idle: usize,
+    outstanding: usize,
+    #[cfg(feature = "counters")]
+    stats: Stats,
+}
+
+impl AcquireError {
+    /// Creates a new error with the specified snapshot of the pool.
+    #[inline]
+    pub(crate) fn new(
+        kind: AcquireErrorKind,
+        idle: usize,
+        outstanding: usize,
+        #[cfg(feature = "counters")] stats: Stats,
+    ) -> Self {
+        Self {
+            kind,
+            idle,
+            outstanding,
+            #[cfg(feature = "counters")]
+            stats,
+        }
+    }
+
+    /// Returns the kind of the error.
+    #[inline]
+    pub fn kind(&self) -> AcquireErrorKind {
+        self.kind
+    }
+
+    /// Returns the number of idle objects of the pool when the acquisition
+    /// failed.
+    #[inline]
+    pub fn idle(&self) -> usize {
+        self.idle
+    }
+
+    /// Returns the number of objects checked out of the pool (and not
+    /// returned) when the acquisition failed.
+    #[inline]
+    pub fn outstanding(&self) -> usize {
+        self.outstanding
+    }
+
+    /// Returns the statistics of the pool when the acquisition failed.
+    #[cfg(feature = "counters")]
+    #[inline]
+    pub fn stats(&self) -> &Stats {
+        &self.stats
+    }
+}
+
+impl PartialEq<AcquireErrorKind> for AcquireError {
+    #[inline]
+    fn eq(&self, other: &AcquireErrorKind) -> bool {
+        self.kind == *other
+    }
+}
+
+impl fmt::Display for AcquireError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (idle: {}, outstanding: {})",
+            self.kind, self.idle, self.outstanding
+        )
+    }
+}
+
+impl Error for AcquireError {}
@@ -0,0 +1,55 @@
+use crate::{ObjectPool, Reusable};
+
+/// Allows creating pooled objects with a fallible generator, for objects whose
+/// construction can fail (like database connections). The errors of the
+/// generator are propagated by [`TryObjectPool::try_new`] instead of forcing
+/// the generator to panic.
+///
+/// This trait can be derived along with [`ObjectPool`] using the
+/// `#[generator_try(function, error = Type)]` attribute. A pool with a
+/// fallible generator has no infallible generator, so [`ObjectPool::new`]
+/// panics if the pool is empty: use [`TryObjectPool::try_new`] instead.
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::prelude::*;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct ConnectError;
+///
+/// #[derive(ObjectPool)]
+/// #[generator_try(Connection::connect, error = ConnectError)]
+/// struct Connection {
+///     id: u32,
+/// }
+///
+/// impl Connection {
+///     fn connect() -> Result<Self, ConnectError> {
+///         Ok(Self { id: 1 })
+///     }
+/// }
+///
+/// fn main() -> Result<(), ConnectError> {
+///     let conn = Connection::try_new()?;
+///     assert_eq!(conn.id, 1);
+///     Ok(())
+/// }
+/// ```
+pub trait TryObjectPool: ObjectPool {
+    /// The error returned by the generator.
+    type Error;
+
+    /// Creates a new object for the pool, or returns an error if it can't be
+    /// created.
+    fn try_generate() -> Result<Self, Self::Error>;
+
+    /// Creates a new object. If there are any objects in the pool, one of them
+    /// will be returned. Otherwise, a new object will be created using
+    /// [`TryObjectPool::try_generate`], propagating its error.
+    #[inline]
+    fn try_new() -> Result<Reusable<Self>, Self::Error> {
+        Self::pool()
+            .take_or_try_generate(Self::try_generate)
+            .map(Reusable::checked_out)
+    }
+}
@@ -0,0 +1,35 @@
+/// Declares static pools of types that can't derive
+/// [`ObjectPool`](derive@crate::ObjectPool), like foreign types such as
+/// `Vec<u8>`, without wrapping them in a newtype. Each static is declared as a
+/// [`Pool`](crate::Pool) of the type, using the expression as generator, and
+/// objects are acquired with [`Pool::acquire`](crate::Pool::acquire), which
+/// wraps them in a [`Pooled`](crate::Pooled) that returns them to the static
+/// pool when dropped.
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::prelude::*;
+///
+/// pool! {
+///     static BUF_POOL: Vec<u8> = Vec::new;
+///     /// Pool of the names of the users.
+///     pub(crate) static NAME_POOL: String = String::new;
+/// }
+///
+/// fn main() {
+///     let mut buf = BUF_POOL.acquire();
+///     buf.push(1);
+///     drop(buf);
+///     assert_eq!(BUF_POOL.len(), 1);
+///     assert_eq!(NAME_POOL.len(), 0);
+/// }
+/// ```
+#[macro_export]
+macro_rules! pool {
+    ($($(#[$attr:meta])* $vis:vis static $name:ident: $ty:ty = $generator:expr;)*) => {
+        $(
+            $(#[$attr])*
+            $vis static $name: $crate::Pool<$ty> = $crate::Pool::new($generator);
+        )*
+    };
+}
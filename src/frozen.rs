@@ -0,0 +1,64 @@
+use core::ops::Deref;
+
+use crate::idle::IdleGuard;
+
+/// A frozen view of the idle objects of a [`Pool`](crate::Pool), created by
+/// [`Pool::freeze`](crate::Pool::freeze). The pool stays locked while the view
+/// exists, so the idle objects can't be acquired or modified, and the view
+/// only hands out shared references, which the borrow checker guarantees
+/// can't outlive the freeze.
+///
+/// The view is [`Sync`] (if the objects are), so it can be shared between
+/// threads to read the idle objects concurrently. Acquiring or returning
+/// objects of the same pool while it is frozen waits until the freeze ends,
+/// so it deadlocks if done from the thread that holds the view.
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::prelude::*;
+///
+/// #[derive(Default, ObjectPool)]
+/// struct Block(Vec<u8>);
+///
+/// fn main() {
+///     Block::pool().insert(Block(vec![1, 2]));
+///     Block::pool().insert(Block(vec![3]));
+///
+///     let frozen = Block::pool().freeze();
+///     let sums = std::thread::scope(|scope| {
+///         let workers: Vec<_> = frozen
+///             .iter()
+///             .map(|block| scope.spawn(move || block.0.iter().sum::<u8>()))
+///             .collect();
+///         workers.into_iter().map(|worker| worker.join().unwrap()).collect::<Vec<_>>()
+///     });
+///     assert_eq!(sums, [3, 3]);
+/// }
+/// ```
+pub struct FrozenPool<'a, T> {
+    pool: IdleGuard<'a, T>,
+}
+
+impl<'a, T> FrozenPool<'a, T> {
+    /// Freezes the locked pool.
+    #[inline]
+    pub(crate) fn new(pool: IdleGuard<'a, T>) -> Self {
+        Self { pool }
+    }
+}
+
+impl<T> Deref for FrozenPool<'_, T> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.pool.items()
+    }
+}
+
+impl<T> AsRef<[T]> for FrozenPool<'_, T> {
+    #[inline]
+    fn as_ref(&self) -> &[T] {
+        self.pool.items()
+    }
+}
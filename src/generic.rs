@@ -0,0 +1,51 @@
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use core::any::{Any, TypeId};
+
+use crate::sync::RwLock;
+use crate::Pool;
+
+/// The pools of each type created with [`Pool::for_type`], which are leaked so
+/// they live as long as a static.
+static POOLS: RwLock<BTreeMap<TypeId, &'static (dyn Any + Send + Sync)>> =
+    RwLock::new(BTreeMap::new());
+
+impl<T: Send + 'static> Pool<T> {
+    /// Returns the pool of the type, creating it with the specified function
+    /// the first time it is called for the type. This gives each instantiation
+    /// of a generic type its own pool, as statics can't be generic, and it is
+    /// used by [`#[derive(ObjectPool)]`](derive@crate::ObjectPool) for generic
+    /// types.
+    ///
+    /// Only the first function called for a type is used, so all the calls for
+    /// the same type should create the same pool.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// #[derive(Default, ObjectPool)]
+    /// struct Buf<T>(Vec<T>);
+    ///
+    /// fn main() {
+    ///     let mut buf = Buf::<u8>::new();
+    ///     buf.0.push(1);
+    ///     drop(buf);
+    ///     assert_eq!(Buf::<u8>::pool().len(), 1);
+    ///     assert_eq!(Buf::<u16>::pool().len(), 0);
+    /// }
+    /// ```
+    pub fn for_type(init: fn() -> Self) -> &'static Self {
+        let key = TypeId::of::<Self>();
+        if let Some(pool) = POOLS.read().get(&key) {
+            return pool.downcast_ref().unwrap();
+        }
+        // The lock must be released before creating the pool
+        let pool = init();
+        let pool = *POOLS
+            .write()
+            .entry(key)
+            .or_insert_with(|| Box::leak(Box::new(pool)));
+        pool.downcast_ref().unwrap()
+    }
+}
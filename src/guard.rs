@@ -0,0 +1,131 @@
+use alloc::vec::Vec;
+use core::mem::ManuallyDrop;
+use core::ops::Deref;
+
+use crate::idle::IdleGuard;
+use crate::Pool;
+
+/// Exclusive access to the idle objects of a [`Pool`], created by
+/// [`Pool::lock`]. The pool stays locked while the guard exists, so several
+/// operations can be performed atomically (like taking two matching objects or
+/// none) without racing with other threads between individual
+/// [`Pool::remove`] and [`Pool::insert`] calls.
+///
+/// The guard derefs to the idle objects, the last one being the next to be
+/// handed out. The objects are moved as-is: the hooks of the pool that run
+/// when objects are acquired or returned are not called. Acquiring or
+/// returning objects of the same pool while the guard exists deadlocks if done
+/// from the thread that holds it.
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::prelude::*;
+///
+/// fn main() {
+///     let pool = Pool::new(Vec::<u8>::new);
+///     pool.insert(vec![1]);
+///     pool.insert(vec![2, 2]);
+///     pool.insert(vec![3]);
+///
+///     // Take the two single-byte buffers, or none of them
+///     let mut guard = pool.lock();
+///     let matching: Vec<usize> = (0..guard.len()).filter(|&i| guard[i].len() == 1).collect();
+///     let pair = match matching[..] {
+///         [first, second] => Some((guard.remove(second), guard.remove(first))),
+///         _ => None,
+///     };
+///     drop(guard);
+///
+///     assert_eq!(pair, Some((vec![3], vec![1])));
+///     assert_eq!(pool.len(), 1);
+/// }
+/// ```
+pub struct PoolGuard<'a, T> {
+    pool: &'a Pool<T>,
+    /// The lock of the pool, released before discarding the removed objects.
+    idle: ManuallyDrop<IdleGuard<'a, T>>,
+    /// The objects discarded while holding the lock.
+    discarded: Vec<T>,
+}
+
+impl<'a, T> PoolGuard<'a, T> {
+    /// Takes exclusive access to the locked pool.
+    #[inline]
+    pub(crate) fn new(pool: &'a Pool<T>, idle: IdleGuard<'a, T>) -> Self {
+        Self {
+            pool,
+            idle: ManuallyDrop::new(idle),
+            discarded: Vec::new(),
+        }
+    }
+
+    /// Removes the next object to be handed out.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        self.idle.pop().map(|(item, _)| item)
+    }
+
+    /// Removes the object at the specified index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds.
+    #[inline]
+    pub fn remove(&mut self, index: usize) -> T {
+        self.idle.remove(index).0
+    }
+
+    /// Pushes an object, which will be the next one to be handed out. The
+    /// object is given back if the pool rejects it (because of its capacity,
+    /// byte budget or maximum object size).
+    #[inline]
+    pub fn push(&mut self, item: T) -> Result<(), T> {
+        if !self.pool.admits(&item) {
+            return Err(item);
+        }
+        self.pool.push_idle(&mut self.idle, item, 0)
+    }
+
+    /// Retains only the objects for which the predicate returns `true`,
+    /// keeping their order. The other objects are discarded (see
+    /// [`Pool::on_discard`]) once the guard is dropped. Returns the number of
+    /// removed objects.
+    pub fn retain(&mut self, mut f: impl FnMut(&T) -> bool) -> usize {
+        let mut removed = self.idle.remove_where(|item| !f(item));
+        let count = removed.len();
+        self.discarded.append(&mut removed);
+        count
+    }
+
+    /// Removes and drops all the objects, like [`Pool::clear`].
+    #[inline]
+    pub fn clear(&mut self) {
+        self.idle.clear();
+    }
+}
+
+impl<T> Deref for PoolGuard<'_, T> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.idle.items()
+    }
+}
+
+impl<T> AsRef<[T]> for PoolGuard<'_, T> {
+    #[inline]
+    fn as_ref(&self) -> &[T] {
+        self.idle.items()
+    }
+}
+
+impl<T> Drop for PoolGuard<'_, T> {
+    fn drop(&mut self) {
+        // SAFETY: the lock is not used after being released
+        unsafe { ManuallyDrop::drop(&mut self.idle) };
+        for item in core::mem::take(&mut self.discarded) {
+            self.pool.discard(item);
+        }
+    }
+}
@@ -0,0 +1,404 @@
+use alloc::sync::Arc;
+use core::borrow::{Borrow, BorrowMut};
+use core::mem::{forget, ManuallyDrop};
+use core::ops::{Deref, DerefMut};
+
+#[cfg(feature = "std")]
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+use crate::stamp::Stamp;
+#[cfg(feature = "std")]
+use crate::AcquireError;
+use crate::Pool;
+
+/// The pool referenced by a [`PoolHandle`].
+enum Inner<T: 'static> {
+    /// A pool stored in a static variable (e.g. a derived pool).
+    Static(&'static Pool<T>),
+    /// A pool owned by all the handles that reference it.
+    Shared(Arc<Pool<T>>),
+}
+
+/// A cheap, cloneable handle to a [`Pool`]. The handle can either reference a
+/// static pool (like the ones generated by
+/// [`#[derive(ObjectPool)]`](derive@crate::ObjectPool)) or own a pool through
+/// an [`Arc`]. This is useful to store a pool in structs or to pass it to
+/// workers, decoupling the code from the [`ObjectPool::pool`] static accessor
+/// (for example to inject a different pool in tests).
+///
+/// The handle implements [`Deref`] to [`Pool`], so all the methods of the pool
+/// can be called directly on the handle. Objects acquired through the handle
+/// are wrapped in a [`Pooled`] struct, which will return the object to the
+/// same pool when dropped.
+///
+/// [`ObjectPool::pool`]: crate::ObjectPool::pool
+///
+/// # Example
+///
+/// ```
+/// use derivable_object_pool::prelude::*;
+///
+/// #[derive(Default)]
+/// struct Test(i32);
+///
+/// struct Worker {
+///     pool: PoolHandle<Test>,
+/// }
+///
+/// fn main() {
+///     let handle = PoolHandle::new(Pool::new(Test::default));
+///     let worker = Worker { pool: handle.clone() };
+///
+///     let mut obj = worker.pool.acquire();
+///     obj.0 = 1;
+///     drop(obj); // obj is returned to the shared pool
+///     assert_eq!(handle.len(), 1);
+/// }
+/// ```
+pub struct PoolHandle<T: 'static> {
+    inner: Inner<T>,
+}
+
+impl<T: 'static> PoolHandle<T> {
+    /// Creates a new handle that owns the specified pool. The pool will be
+    /// dropped when the last handle (or [`Pooled`] object) is dropped.
+    #[must_use]
+    #[inline]
+    pub fn new(pool: Pool<T>) -> Self {
+        Self::from_arc(Arc::new(pool))
+    }
+
+    /// Creates a new handle that shares ownership of the specified pool.
+    #[must_use]
+    #[inline]
+    pub fn from_arc(pool: Arc<Pool<T>>) -> Self {
+        Self {
+            inner: Inner::Shared(pool),
+        }
+    }
+
+    /// Creates a new handle that references a static pool.
+    #[must_use]
+    #[inline]
+    pub const fn from_static(pool: &'static Pool<T>) -> Self {
+        Self {
+            inner: Inner::Static(pool),
+        }
+    }
+
+    /// Creates a new object. If there are any objects in the referenced pool,
+    /// one of them will be returned. Otherwise, a new object will be created
+    /// using the generator function of the pool.
+    #[must_use]
+    #[inline]
+    pub fn acquire(&self) -> Pooled<T> {
+        Pooled::checked_out(self.take_or_generate(), self.clone())
+    }
+
+    /// Removes an object from the pool and returns a pooled wrapper for it,
+    /// which will return the object to the pool when it is dropped.
+    #[inline]
+    pub fn remove_pooled(&self) -> Option<Pooled<T>> {
+        self.remove_stamped()
+            .map(|item| Pooled::checked_out(item, self.clone()))
+    }
+
+    /// Acquires an idle object from the pool like [`PoolHandle::acquire`], but
+    /// returns `None` instead of creating a new object if the pool is empty
+    /// (see [`Pool::try_acquire`]).
+    #[inline]
+    pub fn try_acquire(&self) -> Option<Pooled<T>> {
+        self.try_take()
+            .map(|item| Pooled::checked_out(item, self.clone()))
+    }
+
+    /// Creates a new object like [`PoolHandle::acquire`], but waits for an
+    /// object to be returned if the pool reached its maximum number of checked
+    /// out objects (see [`Pool::acquire_blocking`]).
+    #[cfg(feature = "std")]
+    #[must_use]
+    #[inline]
+    pub fn acquire_blocking(&self) -> Pooled<T> {
+        let item = self.take_until(None).unwrap_or_else(|err| panic!("{err}"));
+        Pooled::reserved(item, self.clone())
+    }
+
+    /// Creates a new object like [`PoolHandle::acquire_blocking`], but fails
+    /// if no object is available before the timeout elapses (see
+    /// [`Pool::acquire_timeout`]).
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn acquire_timeout(&self, timeout: Duration) -> Result<Pooled<T>, AcquireError> {
+        let deadline = Instant::now().checked_add(timeout);
+        self.take_until(deadline)
+            .map(|item| Pooled::reserved(item, self.clone()))
+    }
+
+    /// Returns `true` if both handles reference the same pool.
+    #[inline]
+    pub fn same_pool(&self, other: &Self) -> bool {
+        core::ptr::eq::<Pool<T>>(&**self, &**other)
+    }
+}
+
+impl<T: 'static> Pool<T> {
+    /// Creates a new object from a static pool, wrapped in a [`Pooled`] that
+    /// returns it to the pool when dropped. This is the way to acquire objects
+    /// of types that can't implement [`ObjectPool`](crate::ObjectPool), like
+    /// the ones of the pools declared with [`pool!`](crate::pool!).
+    #[must_use]
+    #[inline]
+    pub fn acquire(&'static self) -> Pooled<T> {
+        PoolHandle::from_static(self).acquire()
+    }
+
+    /// Creates a new object from a static pool like [`Pool::acquire`], but
+    /// waits for an object to be returned if the pool reached its maximum
+    /// number of checked out objects (see [`Pool::with_max_outstanding`]) or
+    /// if it is empty and it can't create new objects (see
+    /// [`Pool::reuse_only`]), instead of creating a new one or failing.
+    #[cfg(feature = "std")]
+    #[must_use]
+    #[inline]
+    pub fn acquire_blocking(&'static self) -> Pooled<T> {
+        PoolHandle::from_static(self).acquire_blocking()
+    }
+
+    /// Creates a new object from a static pool like
+    /// [`Pool::acquire_blocking`], but stops waiting once the timeout elapses,
+    /// so callers waiting on a saturated pool can fail fast and shed load.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`AcquireErrorKind::TimedOut`] error if no object is
+    /// available before the timeout elapses.
+    ///
+    /// [`AcquireErrorKind::TimedOut`]: crate::AcquireErrorKind::TimedOut
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    /// use derivable_object_pool::AcquireErrorKind;
+    /// use std::time::Duration;
+    ///
+    /// static POOL: Pool<Vec<u8>> = Pool::new(Vec::new).with_max_outstanding(1);
+    ///
+    /// fn main() {
+    ///     let buffer = POOL.acquire_timeout(Duration::from_millis(10)).unwrap();
+    ///     let Err(err) = POOL.acquire_timeout(Duration::from_millis(10)) else {
+    ///         panic!("the pool should be saturated");
+    ///     };
+    ///     assert_eq!(err.kind(), AcquireErrorKind::TimedOut);
+    ///     assert_eq!(err.outstanding(), 1);
+    ///     drop(buffer);
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn acquire_timeout(&'static self, timeout: Duration) -> Result<Pooled<T>, AcquireError> {
+        PoolHandle::from_static(self).acquire_timeout(timeout)
+    }
+}
+
+impl<T: 'static> Clone for PoolHandle<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        let inner = match &self.inner {
+            Inner::Static(pool) => Inner::Static(pool),
+            Inner::Shared(pool) => Inner::Shared(Arc::clone(pool)),
+        };
+        Self { inner }
+    }
+}
+
+impl<T: 'static> Deref for PoolHandle<T> {
+    type Target = Pool<T>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        match &self.inner {
+            Inner::Static(pool) => pool,
+            Inner::Shared(pool) => pool,
+        }
+    }
+}
+
+impl<T: 'static> AsRef<Pool<T>> for PoolHandle<T> {
+    #[inline]
+    fn as_ref(&self) -> &Pool<T> {
+        self
+    }
+}
+
+impl<T: 'static> From<&'static Pool<T>> for PoolHandle<T> {
+    #[inline]
+    fn from(pool: &'static Pool<T>) -> Self {
+        Self::from_static(pool)
+    }
+}
+
+impl<T: 'static> From<Arc<Pool<T>>> for PoolHandle<T> {
+    #[inline]
+    fn from(pool: Arc<Pool<T>>) -> Self {
+        Self::from_arc(pool)
+    }
+}
+
+impl<T: 'static> From<Pool<T>> for PoolHandle<T> {
+    #[inline]
+    fn from(pool: Pool<T>) -> Self {
+        Self::new(pool)
+    }
+}
+
+/// A wrapper for an object acquired through a [`PoolHandle`] that will return
+/// the object to the pool referenced by the handle when it is dropped. It is
+/// the equivalent of [`Reusable`] for pools that are not accessed through the
+/// [`ObjectPool`] trait.
+///
+/// [`Reusable`]: crate::Reusable
+/// [`ObjectPool`]: crate::ObjectPool
+///
+/// # Example
+///
+/// ```
+/// use derivable_object_pool::prelude::*;
+///
+/// fn main() {
+///     let handle = PoolHandle::new(Pool::new(Vec::<u8>::new));
+///     let mut buf = handle.acquire();
+///     buf.push(1);
+///     drop(buf);
+///     assert_eq!(handle.len(), 1);
+///
+///     let buf = handle.acquire();
+///     assert_eq!(*buf, [1]);
+/// }
+/// ```
+pub struct Pooled<T: 'static> {
+    /// The wrapped object. This is a `ManuallyDrop` to ensure that the object
+    /// is not dropped when the wrapper is dropped.
+    item: ManuallyDrop<T>,
+    /// The pool where the object will be returned.
+    pool: PoolHandle<T>,
+    /// The bookkeeping of the checkout of the object.
+    stamp: Stamp,
+}
+
+impl<T: 'static> Pooled<T> {
+    /// Creates a new pooled wrapper for an object checked out from the pool
+    /// with the specified stamp.
+    #[inline]
+    fn checked_out(item: (T, Stamp), pool: PoolHandle<T>) -> Self {
+        pool.count_checkouts(1);
+        Self::reserved(item, pool)
+    }
+
+    /// Creates a new pooled wrapper for an object checked out from the pool
+    /// with the specified stamp, which was already counted as outstanding.
+    #[inline]
+    fn reserved((item, mut stamp): (T, Stamp), pool: PoolHandle<T>) -> Self {
+        pool.checkouts.track(&mut stamp);
+        Self {
+            item: ManuallyDrop::new(item),
+            pool,
+            stamp,
+        }
+    }
+
+    /// Returns the handle of the pool where the object will be returned.
+    #[inline]
+    pub fn handle(&self) -> &PoolHandle<T> {
+        &self.pool
+    }
+
+    /// Returns the number of times the object was handed out again by the
+    /// pool before this checkout (see [`Pool::with_max_reuses`]).
+    #[inline]
+    pub const fn reuses(&self) -> u32 {
+        self.stamp.reuses
+    }
+
+    /// Returns the object to the pool immediately, like dropping the wrapper.
+    /// Returns `true` if the object was pooled or `false` if it was discarded
+    /// (see [`Reusable::release`](crate::Reusable::release)).
+    #[inline]
+    pub fn release(self) -> bool {
+        let (item, stamp, pool) = self.into_parts();
+        pool.release_stamped(item, stamp)
+    }
+
+    /// Returns the owned object inside the wrapper. This will return the object
+    /// without returning it to the pool. This is useful if you want to take
+    /// ownership of the object.
+    pub fn into_inner(self) -> T {
+        let (item, stamp, pool) = self.into_parts();
+        pool.checkouts.untrack(stamp);
+        item
+    }
+
+    /// Takes the object, the stamp of its checkout and the handle of its pool
+    /// out of the wrapper, without returning the object to the pool.
+    #[inline]
+    fn into_parts(mut self) -> (T, Stamp, PoolHandle<T>) {
+        let item = unsafe { ManuallyDrop::take(&mut self.item) };
+        let pool = unsafe { core::ptr::read(&self.pool) };
+        let stamp = self.stamp;
+        forget(self);
+        (item, stamp, pool)
+    }
+}
+
+impl<T: 'static> Borrow<T> for Pooled<T> {
+    #[inline]
+    fn borrow(&self) -> &T {
+        &self.item
+    }
+}
+
+impl<T: 'static> BorrowMut<T> for Pooled<T> {
+    #[inline]
+    fn borrow_mut(&mut self) -> &mut T {
+        &mut self.item
+    }
+}
+
+impl<T: 'static> AsRef<T> for Pooled<T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        &self.item
+    }
+}
+
+impl<T: 'static> AsMut<T> for Pooled<T> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut T {
+        &mut self.item
+    }
+}
+
+impl<T: 'static> Deref for Pooled<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.item
+    }
+}
+
+impl<T: 'static> DerefMut for Pooled<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.item
+    }
+}
+
+impl<T: 'static> Drop for Pooled<T> {
+    #[inline]
+    fn drop(&mut self) {
+        let item = unsafe { ManuallyDrop::take(&mut self.item) };
+        self.pool.release_stamped(item, self.stamp);
+    }
+}
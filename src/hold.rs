@@ -0,0 +1,89 @@
+use alloc::boxed::Box;
+use core::marker::PhantomData;
+use core::ptr::NonNull;
+
+use crate::stamp::Stamp;
+use crate::{ObjectPool, Reusable};
+
+/// An escrow token for a pooled object that has been handed to external code
+/// (like a C callback or a GPU queue) through [`Reusable::hold`]. The object
+/// is kept at a stable address and is not returned to the pool until the token
+/// is redeemed with [`HoldToken::redeem`], preventing it from being reused
+/// while it is still referenced externally.
+///
+/// If the token is dropped without being redeemed, the object is leaked
+/// instead of being returned to the pool, as the external code may still be
+/// referencing it.
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::prelude::*;
+///
+/// #[derive(Default, ObjectPool)]
+/// struct Buffer([u8; 16]);
+///
+/// fn main() {
+///     let buffer = Buffer::new();
+///     let token = buffer.hold();
+///     let ptr = token.as_mut_ptr(); // handed to external code
+///     unsafe { (*ptr).0[0] = 1 };
+///     assert_eq!(Buffer::pool().len(), 0);
+///
+///     // Once the external code is done with the object
+///     let buffer = token.redeem();
+///     assert_eq!(buffer.0[0], 1);
+///     drop(buffer);
+///     assert_eq!(Buffer::pool().len(), 1);
+/// }
+/// ```
+#[must_use = "dropping a hold token leaks the object, use `redeem` to return it"]
+pub struct HoldToken<T: ObjectPool> {
+    /// The held object, allocated on the heap to keep its address stable.
+    item: NonNull<T>,
+    /// The bookkeeping of the checkout of the object.
+    stamp: Stamp,
+    /// The token owns the held object.
+    _marker: PhantomData<T>,
+}
+
+// SAFETY: The token uniquely owns the held object, like a `Box<T>`.
+unsafe impl<T: ObjectPool + Send> Send for HoldToken<T> {}
+// SAFETY: The token only exposes raw pointers to the object.
+unsafe impl<T: ObjectPool + Sync> Sync for HoldToken<T> {}
+
+impl<T: ObjectPool> HoldToken<T> {
+    /// Places the object in escrow.
+    #[inline]
+    pub(crate) fn new(item: T, stamp: Stamp) -> Self {
+        Self {
+            item: NonNull::from(Box::leak(Box::new(item))),
+            stamp,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns a pointer to the held object. The pointer is valid until the
+    /// token is redeemed.
+    #[inline]
+    pub fn as_ptr(&self) -> *const T {
+        self.item.as_ptr()
+    }
+
+    /// Returns a mutable pointer to the held object. The pointer is valid until
+    /// the token is redeemed.
+    #[inline]
+    pub fn as_mut_ptr(&self) -> *mut T {
+        self.item.as_ptr()
+    }
+
+    /// Ends the escrow and returns the object inside a [`Reusable`] wrapper,
+    /// which will return the object to the pool when dropped. The caller must
+    /// ensure that the external code no longer references the object.
+    #[inline]
+    pub fn redeem(self) -> Reusable<T> {
+        // SAFETY: The pointer was created from a leaked box and it is only
+        // reclaimed once, as the token is consumed.
+        let item = unsafe { Box::from_raw(self.item.as_ptr()) };
+        Reusable::stamped(*item, self.stamp)
+    }
+}
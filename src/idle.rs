@@ -0,0 +1,293 @@
+use alloc::vec::Vec;
+use core::ops::{Deref, DerefMut};
+use core::sync::atomic::Ordering;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+use crate::sync::MutexGuard;
+use crate::Pool;
+
+/// The function that measures the heap size of the objects, if the pool
+/// measures them.
+pub(crate) type HeapSizeFn<T> = Option<fn(&T) -> usize>;
+
+/// The idle objects of a [`Pool`](crate::Pool), along with the metadata of
+/// each of them. The objects are stored as a stack, so the last one is the next
+/// one to be handed out.
+pub(crate) struct Idle<T> {
+    items: Vec<T>,
+    /// The metadata of each object, at the same index as the object.
+    meta: Vec<Meta>,
+    /// The total heap size of the objects.
+    bytes: usize,
+}
+
+/// The metadata of an idle object.
+#[derive(Clone, Copy)]
+struct Meta {
+    /// The number of times the object was already reused.
+    reuses: u32,
+    /// The heap size of the object when it entered the pool (or was last
+    /// updated in place), if the pool measures it.
+    bytes: usize,
+    /// The instant the object entered the pool.
+    #[cfg(feature = "std")]
+    since: Instant,
+}
+
+impl Meta {
+    #[inline]
+    fn new(reuses: u32, bytes: usize) -> Self {
+        Self {
+            reuses,
+            bytes,
+            #[cfg(feature = "std")]
+            since: Instant::now(),
+        }
+    }
+}
+
+impl<T> Idle<T> {
+    #[inline]
+    pub(crate) const fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            meta: Vec::new(),
+            bytes: 0,
+        }
+    }
+
+    /// Returns the idle objects, the last one being the next to be handed out.
+    #[inline]
+    pub(crate) fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Returns an iterator over the idle objects and their reuse counts, in
+    /// the same order as [`Idle::items`].
+    #[inline]
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&T, u32)> {
+        self.items
+            .iter()
+            .zip(self.meta.iter().map(|meta| meta.reuses))
+    }
+
+    #[inline]
+    pub(crate) fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns the total heap size of the objects, as measured when they
+    /// entered the pool.
+    #[inline]
+    pub(crate) fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    /// Pushes an object reused the specified number of times, with the
+    /// specified heap size.
+    #[inline]
+    pub(crate) fn push(&mut self, item: T, reuses: u32, bytes: usize) {
+        let meta = Meta::new(reuses, bytes);
+        self.bytes += bytes;
+        self.items.push(item);
+        self.meta.push(meta);
+    }
+
+    /// Pops the next object to be handed out, along with its reuse count.
+    #[inline]
+    pub(crate) fn pop(&mut self) -> Option<(T, u32)> {
+        let item = self.items.pop()?;
+        let meta = self.meta.pop()?;
+        self.bytes -= meta.bytes;
+        Some((item, meta.reuses))
+    }
+
+    /// Removes the object at the specified index, along with its reuse count.
+    #[inline]
+    pub(crate) fn remove(&mut self, index: usize) -> (T, u32) {
+        let meta = self.meta.remove(index);
+        self.bytes -= meta.bytes;
+        (self.items.remove(index), meta.reuses)
+    }
+
+    /// Pushes new objects, which will be handed out before the stored ones.
+    pub(crate) fn extend(&mut self, items: Vec<T>, heap_size: HeapSizeFn<T>) {
+        let meta = self.measure(&items, heap_size);
+        self.meta.extend(meta);
+        self.items.extend(items);
+    }
+
+    /// Inserts new objects below the stored ones, so they will be handed out
+    /// after them.
+    pub(crate) fn extend_below(&mut self, items: Vec<T>, heap_size: HeapSizeFn<T>) {
+        let meta = self.measure(&items, heap_size);
+        self.meta.splice(0..0, meta);
+        self.items.splice(0..0, items);
+    }
+
+    /// Measures new objects, adding their heap size to the total.
+    fn measure(&mut self, items: &[T], heap_size: HeapSizeFn<T>) -> Vec<Meta> {
+        let meta: Vec<Meta> = items
+            .iter()
+            .map(|item| Meta::new(0, heap_size.map_or(0, |heap_size| heap_size(item))))
+            .collect();
+        self.bytes += meta.iter().map(|meta| meta.bytes).sum::<usize>();
+        meta
+    }
+
+    /// Calls the closure with each object mutably, measuring them again
+    /// afterwards.
+    pub(crate) fn apply(&mut self, mut f: impl FnMut(&mut T), heap_size: HeapSizeFn<T>) {
+        for (item, meta) in self.items.iter_mut().zip(&mut self.meta) {
+            f(item);
+            if let Some(heap_size) = heap_size {
+                self.bytes -= meta.bytes;
+                meta.bytes = heap_size(item);
+                self.bytes += meta.bytes;
+            }
+        }
+    }
+
+    /// Removes the `n` objects that would be handed out last.
+    pub(crate) fn split_bottom(&mut self, n: usize) -> Vec<T> {
+        let n = n.min(self.len());
+        self.bytes -= self.meta.drain(..n).map(|meta| meta.bytes).sum::<usize>();
+        self.items.drain(..n).collect()
+    }
+
+    /// Removes the objects that would be handed out last while the total heap
+    /// size of the objects exceeds `max_bytes`.
+    pub(crate) fn split_bottom_bytes(&mut self, max_bytes: usize) -> Vec<T> {
+        let mut excess = self.bytes.saturating_sub(max_bytes);
+        let n = self
+            .meta
+            .iter()
+            .take_while(|meta| {
+                let over = excess > 0;
+                excess = excess.saturating_sub(meta.bytes);
+                over
+            })
+            .count();
+        self.split_bottom(n)
+    }
+
+    /// Removes the `n` objects that would be handed out next, keeping their
+    /// order.
+    pub(crate) fn split_top(&mut self, n: usize) -> Vec<T> {
+        let start = self.len().saturating_sub(n);
+        self.bytes -= self
+            .meta
+            .drain(start..)
+            .map(|meta| meta.bytes)
+            .sum::<usize>();
+        self.items.split_off(start)
+    }
+
+    /// Removes the objects that entered the pool before the deadline, starting
+    /// with the ones that would be handed out last, while more than `keep`
+    /// objects remain.
+    #[cfg(feature = "std")]
+    pub(crate) fn evict(&mut self, deadline: Instant, keep: usize) -> Vec<T> {
+        let mut excess = self.len().saturating_sub(keep);
+        let expired: Vec<bool> = self
+            .meta
+            .iter()
+            .map(|meta| {
+                let expired = excess > 0 && meta.since < deadline;
+                excess -= usize::from(expired);
+                expired
+            })
+            .collect();
+        self.remove_flagged(&expired)
+    }
+
+    /// Removes the objects that match the predicate, keeping the order of the
+    /// rest.
+    pub(crate) fn remove_where(&mut self, mut f: impl FnMut(&T) -> bool) -> Vec<T> {
+        let matched: Vec<bool> = self.items.iter().map(&mut f).collect();
+        self.remove_flagged(&matched)
+    }
+
+    /// Removes the objects whose flag, at the same index, is `true`.
+    fn remove_flagged(&mut self, flags: &[bool]) -> Vec<T> {
+        if !flags.contains(&true) {
+            return Vec::new();
+        }
+        let mut flag = flags.iter();
+        self.meta.retain(|_| flag.next() == Some(&false));
+        self.bytes = self.meta.iter().map(|meta| meta.bytes).sum();
+        let mut removed = Vec::new();
+        let items = core::mem::take(&mut self.items);
+        for (item, &flag) in items.into_iter().zip(flags) {
+            match flag {
+                true => removed.push(item),
+                false => self.items.push(item),
+            }
+        }
+        removed
+    }
+
+    /// Drops all the objects.
+    #[inline]
+    pub(crate) fn clear(&mut self) {
+        self.items.clear();
+        self.meta.clear();
+        self.bytes = 0;
+    }
+
+    /// Removes all the objects, along with their reuse counts.
+    pub(crate) fn take_with_reuses(&mut self) -> Vec<(T, u32)> {
+        self.bytes = 0;
+        let reuses = self.meta.drain(..).map(|meta| meta.reuses);
+        self.items.drain(..).zip(reuses).collect()
+    }
+
+    /// Removes all the objects.
+    #[inline]
+    pub(crate) fn take(&mut self) -> Vec<T> {
+        self.meta.clear();
+        self.bytes = 0;
+        core::mem::take(&mut self.items)
+    }
+}
+
+/// The locked [`Idle`] objects of a pool, which publish their number when the
+/// lock is released, so it can be read without locking.
+pub(crate) struct IdleGuard<'a, T> {
+    idle: MutexGuard<'a, Idle<T>>,
+    pool: &'a Pool<T>,
+}
+
+impl<'a, T> IdleGuard<'a, T> {
+    #[inline]
+    pub(crate) fn new(idle: MutexGuard<'a, Idle<T>>, pool: &'a Pool<T>) -> Self {
+        Self { idle, pool }
+    }
+}
+
+impl<T> Deref for IdleGuard<'_, T> {
+    type Target = Idle<T>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.idle
+    }
+}
+
+impl<T> DerefMut for IdleGuard<'_, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.idle
+    }
+}
+
+impl<T> Drop for IdleGuard<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        // The lock is still held, so the stores happen in the order of the
+        // changes
+        self.pool.len.store(self.idle.len(), Ordering::Relaxed);
+        self.pool.counters.observe_idle(self.pool.idle_len());
+    }
+}
@@ -0,0 +1,91 @@
+use alloc::boxed::Box;
+use core::marker::PhantomData;
+use core::ptr::null_mut;
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+/// A node of the [`Inbox`] linked list.
+struct Node<T> {
+    item: T,
+    next: *mut Node<T>,
+}
+
+/// A lock-free multi-producer inbox of returned objects. Producers push
+/// objects without blocking (a Treiber stack), while the consumer takes all
+/// the objects at once, so the ABA problem of popping single nodes can't
+/// happen.
+pub(crate) struct Inbox<T> {
+    head: AtomicPtr<Node<T>>,
+    /// The inbox owns the nodes of the list.
+    _marker: PhantomData<Box<Node<T>>>,
+}
+
+// SAFETY: The objects are only moved between threads through the inbox, never
+// shared, so the inbox is thread-safe as long as the objects can be sent.
+unsafe impl<T: Send> Send for Inbox<T> {}
+// SAFETY: See above.
+unsafe impl<T: Send> Sync for Inbox<T> {}
+
+impl<T> Inbox<T> {
+    /// Creates a new empty inbox.
+    #[inline]
+    pub(crate) const fn new() -> Self {
+        Self {
+            head: AtomicPtr::new(null_mut()),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns `true` if the inbox is empty.
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.head.load(Ordering::Relaxed).is_null()
+    }
+
+    /// Pushes an object into the inbox without blocking.
+    pub(crate) fn push(&self, item: T) {
+        let node = Box::into_raw(Box::new(Node {
+            item,
+            next: null_mut(),
+        }));
+        let mut head = self.head.load(Ordering::Relaxed);
+        loop {
+            // SAFETY: The node is not shared until the exchange succeeds.
+            unsafe { (*node).next = head };
+            match self
+                .head
+                .compare_exchange_weak(head, node, Ordering::Release, Ordering::Relaxed)
+            {
+                Ok(_) => break,
+                Err(current) => head = current,
+            }
+        }
+    }
+
+    /// Takes all the objects of the inbox, calling the function with each of
+    /// them in the order they were pushed.
+    pub(crate) fn drain(&self, mut f: impl FnMut(T)) {
+        // The detached list is in reverse push order, so it is reversed first
+        let mut node = self.head.swap(null_mut(), Ordering::Acquire);
+        let mut reversed = null_mut();
+        while !node.is_null() {
+            // SAFETY: The list was detached from the inbox, so the nodes are
+            // uniquely owned.
+            let next = unsafe { (*node).next };
+            unsafe { (*node).next = reversed };
+            reversed = node;
+            node = next;
+        }
+        while !reversed.is_null() {
+            // SAFETY: Each node was allocated by `push` and is uniquely owned.
+            let boxed = unsafe { Box::from_raw(reversed) };
+            reversed = boxed.next;
+            f(boxed.item);
+        }
+    }
+}
+
+impl<T> Drop for Inbox<T> {
+    fn drop(&mut self) {
+        self.drain(drop);
+    }
+}
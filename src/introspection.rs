@@ -0,0 +1,198 @@
+use core::ffi::c_void;
+use core::ptr::null_mut;
+use core::sync::atomic::{AtomicPtr, AtomicU64, AtomicUsize, Ordering};
+
+use crate::counters::Event;
+
+/// The maximum number of pools that can be registered in the introspection
+/// table. Pools first used once the table is full are not registered.
+pub const MAX_INTROSPECTED_POOLS: usize = 256;
+
+/// The magic number at the start of the introspection table (`"DOPOOL01"` in
+/// little endian), so external readers can validate the memory they read.
+pub const INTROSPECTION_MAGIC: u64 = u64::from_le_bytes(*b"DOPOOL01");
+
+/// The process-global introspection table. External profilers and agents can
+/// locate it through the `DERIVABLE_OBJECT_POOL_INTROSPECTION` symbol and read
+/// it without calling into Rust.
+#[no_mangle]
+pub static DERIVABLE_OBJECT_POOL_INTROSPECTION: IntrospectionTable = IntrospectionTable {
+    magic: INTROSPECTION_MAGIC,
+    version: 1,
+    capacity: MAX_INTROSPECTED_POOLS as u32,
+    len: AtomicUsize::new(0),
+    entries: [const { IntrospectionEntry::empty() }; MAX_INTROSPECTED_POOLS],
+};
+
+/// A C-compatible table that enumerates the pools of the process and their
+/// counters, enabling out-of-process monitoring of pooled memory.
+///
+/// Readers must only consider the first `min(len, capacity)` entries, and
+/// skip the entries whose pool address is null (pools not yet registered or
+/// already dropped). All the fields are updated atomically, so the table can
+/// be read at any time by other threads or processes.
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::prelude::*;
+/// use derivable_object_pool::introspection_table;
+///
+/// #[derive(Default, ObjectPool)]
+/// struct Frame(Vec<u8>);
+///
+/// fn main() {
+///     drop(Frame::new());
+///
+///     let entry = introspection_table()
+///         .entries()
+///         .find(|entry| entry.type_name() == Some(std::any::type_name::<Frame>()))
+///         .unwrap();
+///     assert_eq!(entry.acquired(), 1);
+///     assert_eq!(entry.returned(), 1);
+/// }
+/// ```
+#[repr(C)]
+pub struct IntrospectionTable {
+    magic: u64,
+    version: u32,
+    capacity: u32,
+    len: AtomicUsize,
+    entries: [IntrospectionEntry; MAX_INTROSPECTED_POOLS],
+}
+
+impl IntrospectionTable {
+    /// Returns an iterator over the live entries of the table.
+    pub fn entries(&self) -> impl Iterator<Item = &IntrospectionEntry> {
+        let len = self.len.load(Ordering::Acquire).min(MAX_INTROSPECTED_POOLS);
+        self.entries[..len]
+            .iter()
+            .filter(|entry| !entry.pool.load(Ordering::Acquire).is_null())
+    }
+
+    /// Claims a new entry for the pool at the specified address.
+    pub(crate) fn register(
+        &'static self,
+        pool: *const c_void,
+        type_name: &'static str,
+    ) -> Option<&'static IntrospectionEntry> {
+        let index = self.len.fetch_add(1, Ordering::AcqRel);
+        let entry = self.entries.get(index)?;
+        entry
+            .type_name
+            .store(type_name.as_ptr().cast_mut(), Ordering::Relaxed);
+        entry
+            .type_name_len
+            .store(type_name.len(), Ordering::Relaxed);
+        entry.pool.store(pool.cast_mut(), Ordering::Release);
+        Some(entry)
+    }
+}
+
+/// A C-compatible entry of the [`IntrospectionTable`], describing a pool and
+/// its counters.
+#[repr(C)]
+pub struct IntrospectionEntry {
+    /// The address of the pool, or null if the entry is not live.
+    pool: AtomicPtr<c_void>,
+    /// The UTF-8 name of the pooled type (not null-terminated).
+    type_name: AtomicPtr<u8>,
+    /// The length in bytes of the name of the pooled type.
+    type_name_len: AtomicUsize,
+    /// The number of objects handed out by the pool.
+    acquired: AtomicU64,
+    /// The number of objects created by the generator of the pool.
+    generated: AtomicU64,
+    /// The number of objects returned to the pool.
+    returned: AtomicU64,
+    /// The number of returned objects dropped by the policies of the pool.
+    rejected: AtomicU64,
+}
+
+impl IntrospectionEntry {
+    /// Creates an entry that is not live.
+    const fn empty() -> Self {
+        Self {
+            pool: AtomicPtr::new(null_mut()),
+            type_name: AtomicPtr::new(null_mut()),
+            type_name_len: AtomicUsize::new(0),
+            acquired: AtomicU64::new(0),
+            generated: AtomicU64::new(0),
+            returned: AtomicU64::new(0),
+            rejected: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the address of the pool, or null if the entry is not live.
+    #[inline]
+    pub fn pool_address(&self) -> *const c_void {
+        self.pool.load(Ordering::Acquire)
+    }
+
+    /// Returns the name of the pooled type, if the entry is live.
+    pub fn type_name(&self) -> Option<&'static str> {
+        if self.pool_address().is_null() {
+            return None;
+        }
+        let ptr = self.type_name.load(Ordering::Relaxed);
+        let len = self.type_name_len.load(Ordering::Relaxed);
+        // SAFETY: The name was copied from a `&'static str` before the pool
+        // address was published.
+        let bytes = unsafe { core::slice::from_raw_parts(ptr, len) };
+        core::str::from_utf8(bytes).ok()
+    }
+
+    /// Returns the number of objects handed out by the pool.
+    #[inline]
+    pub fn acquired(&self) -> u64 {
+        self.acquired.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of objects created by the generator of the pool.
+    #[inline]
+    pub fn generated(&self) -> u64 {
+        self.generated.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of objects returned to the pool.
+    #[inline]
+    pub fn returned(&self) -> u64 {
+        self.returned.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of returned objects dropped by the policies of the
+    /// pool (like its capacity or maximum object size).
+    #[inline]
+    pub fn rejected(&self) -> u64 {
+        self.rejected.load(Ordering::Relaxed)
+    }
+
+    /// Records the specified event in the counters of the entry.
+    #[inline]
+    pub(crate) fn record(&self, event: Event) {
+        // Each acquisition is either a hit or a created object
+        let counter = match event {
+            Event::Hit => &self.acquired,
+            Event::Miss => return,
+            Event::Created => {
+                self.acquired.fetch_add(1, Ordering::Relaxed);
+                &self.generated
+            }
+            Event::Prefilled => &self.generated,
+            Event::Returned => &self.returned,
+            Event::Rejected => &self.rejected,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Marks the entry as no longer live.
+    #[inline]
+    pub(crate) fn unregister(&self) {
+        self.pool.store(null_mut(), Ordering::Release);
+    }
+}
+
+/// Returns the process-global introspection table.
+#[inline]
+pub fn introspection_table() -> &'static IntrospectionTable {
+    &DERIVABLE_OBJECT_POOL_INTROSPECTION
+}
@@ -0,0 +1,109 @@
+use std::fmt;
+use std::io::{BufRead, IoSlice, IoSliceMut, Read, Result, Write};
+
+use crate::{ObjectPool, Reusable};
+
+/// Reads from the pooled object, so pooled readers can be used directly (for
+/// example with [`std::io::copy`]).
+impl<T: ObjectPool + Read> Read for Reusable<T> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        (**self).read(buf)
+    }
+
+    #[inline]
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<usize> {
+        (**self).read_vectored(bufs)
+    }
+
+    #[inline]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
+        (**self).read_to_end(buf)
+    }
+
+    #[inline]
+    fn read_to_string(&mut self, buf: &mut String) -> Result<usize> {
+        (**self).read_to_string(buf)
+    }
+
+    #[inline]
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+        (**self).read_exact(buf)
+    }
+}
+
+impl<T: ObjectPool + BufRead> BufRead for Reusable<T> {
+    #[inline]
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        (**self).fill_buf()
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        (**self).consume(amt);
+    }
+
+    #[inline]
+    fn read_until(&mut self, byte: u8, buf: &mut Vec<u8>) -> Result<usize> {
+        (**self).read_until(byte, buf)
+    }
+
+    #[inline]
+    fn read_line(&mut self, buf: &mut String) -> Result<usize> {
+        (**self).read_line(buf)
+    }
+}
+
+/// Writes to the pooled object, so pooled writers can be used directly (for
+/// example with [`write!`]).
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::prelude::*;
+/// use std::io::Write;
+///
+/// #[derive(Default, ObjectPool)]
+/// struct Output(Vec<u8>);
+///
+/// impl Write for Output {
+///     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+///         self.0.write(buf)
+///     }
+///
+///     fn flush(&mut self) -> std::io::Result<()> {
+///         Ok(())
+///     }
+/// }
+///
+/// fn main() {
+///     let mut output = Output::new();
+///     write!(output, "{}-{}", 1, 2).unwrap();
+///     assert_eq!(output.0, b"1-2");
+/// }
+/// ```
+impl<T: ObjectPool + Write> Write for Reusable<T> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        (**self).write(buf)
+    }
+
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> Result<usize> {
+        (**self).write_vectored(bufs)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        (**self).flush()
+    }
+
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        (**self).write_all(buf)
+    }
+
+    #[inline]
+    fn write_fmt(&mut self, fmt: fmt::Arguments<'_>) -> Result<()> {
+        (**self).write_fmt(fmt)
+    }
+}
@@ -0,0 +1,222 @@
+use core::borrow::{Borrow, BorrowMut};
+use core::hash::Hash;
+use core::mem::{forget, ManuallyDrop};
+use core::ops::{Deref, DerefMut};
+use std::collections::HashMap;
+
+use crate::sync::{Mutex, MutexGuard};
+
+/// A pool that stores its objects per key, so objects that depend on some
+/// parameter (like parser states per file extension) can be pooled without a
+/// separate pooled type per key. New objects are created by a generator
+/// function that receives the key.
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::KeyedPool;
+///
+/// struct Parser {
+///     extension: &'static str,
+///     buffer: String,
+/// }
+///
+/// static PARSERS: KeyedPool<&'static str, Parser> = KeyedPool::new(|extension| Parser {
+///     extension,
+///     buffer: String::new(),
+/// });
+///
+/// fn main() {
+///     let mut parser = PARSERS.get("json");
+///     assert_eq!(parser.extension, "json");
+///     parser.buffer.push_str("{}");
+///     drop(parser); // returned to the pool of its key
+///
+///     assert_eq!(PARSERS.len_of(&"json"), 1);
+///     assert_eq!(PARSERS.len_of(&"toml"), 0);
+///     assert_eq!(PARSERS.get("json").buffer, "{}");
+/// }
+/// ```
+pub struct KeyedPool<K, T> {
+    /// The idle objects of each key, created on first use so the pool can be
+    /// constructed in a constant context.
+    pools: Mutex<Option<HashMap<K, Vec<T>>>>,
+    generator: fn(&K) -> T,
+}
+
+impl<K: Eq + Hash, T> KeyedPool<K, T> {
+    /// Creates a new keyed pool. The pool will use the specified generator
+    /// function to create new objects for a key.
+    #[must_use]
+    #[inline]
+    pub const fn new(generator: fn(&K) -> T) -> Self {
+        Self {
+            pools: Mutex::new(None),
+            generator,
+        }
+    }
+
+    /// Returns a locked reference to the objects of each key.
+    #[inline]
+    fn get_pools(&self) -> MutexGuard<'_, Option<HashMap<K, Vec<T>>>> {
+        self.pools.lock()
+    }
+
+    /// Acquires an object for the specified key, or creates a new one if there
+    /// are no idle objects for the key. The object is returned to the pool of
+    /// the key when the wrapper is dropped.
+    pub fn get(&self, key: K) -> KeyedReusable<'_, K, T> {
+        // The lock must be released before calling the generator
+        let item = self.remove(&key);
+        let item = item.unwrap_or_else(|| (self.generator)(&key));
+        KeyedReusable::new(key, item, self)
+    }
+
+    /// Removes an idle object of the specified key, if any.
+    pub fn remove<Q>(&self, key: &Q) -> Option<T>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.get_pools().as_mut()?.get_mut(key)?.pop()
+    }
+
+    /// Inserts an object into the pool of the specified key.
+    pub fn insert(&self, key: K, item: T) {
+        self.get_pools()
+            .get_or_insert_with(HashMap::new)
+            .entry(key)
+            .or_default()
+            .push(item);
+    }
+
+    /// Returns the number of idle objects of the specified key.
+    pub fn len_of<Q>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.get_pools()
+            .as_ref()
+            .and_then(|pools| pools.get(key))
+            .map_or(0, Vec::len)
+    }
+
+    /// Returns the number of idle objects of all the keys.
+    pub fn len(&self) -> usize {
+        self.get_pools()
+            .as_ref()
+            .map_or(0, |pools| pools.values().map(Vec::len).sum())
+    }
+
+    /// Returns `true` if there are no idle objects for any key.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all the objects of the specified key.
+    pub fn clear_key<Q>(&self, key: &Q)
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let removed = self
+            .get_pools()
+            .as_mut()
+            .and_then(|pools| pools.remove(key));
+        drop(removed);
+    }
+
+    /// Removes all the objects of all the keys.
+    pub fn clear(&self) {
+        let removed = self.get_pools().take();
+        drop(removed);
+    }
+}
+
+/// A wrapper for an object acquired from a [`KeyedPool`] that returns the
+/// object to the pool of its key when it is dropped.
+pub struct KeyedReusable<'a, K: Eq + Hash, T> {
+    item: ManuallyDrop<T>,
+    key: ManuallyDrop<K>,
+    pool: &'a KeyedPool<K, T>,
+}
+
+impl<'a, K: Eq + Hash, T> KeyedReusable<'a, K, T> {
+    #[inline]
+    fn new(key: K, item: T, pool: &'a KeyedPool<K, T>) -> Self {
+        Self {
+            item: ManuallyDrop::new(item),
+            key: ManuallyDrop::new(key),
+            pool,
+        }
+    }
+
+    /// Returns the key of the object.
+    #[inline]
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Returns the key and the owned object inside the wrapper without
+    /// returning the object to the pool.
+    pub fn into_inner(mut self) -> (K, T) {
+        let key = unsafe { ManuallyDrop::take(&mut self.key) };
+        let item = unsafe { ManuallyDrop::take(&mut self.item) };
+        forget(self);
+        (key, item)
+    }
+}
+
+impl<K: Eq + Hash, T> Borrow<T> for KeyedReusable<'_, K, T> {
+    #[inline]
+    fn borrow(&self) -> &T {
+        &self.item
+    }
+}
+
+impl<K: Eq + Hash, T> BorrowMut<T> for KeyedReusable<'_, K, T> {
+    #[inline]
+    fn borrow_mut(&mut self) -> &mut T {
+        &mut self.item
+    }
+}
+
+impl<K: Eq + Hash, T> AsRef<T> for KeyedReusable<'_, K, T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        &self.item
+    }
+}
+
+impl<K: Eq + Hash, T> AsMut<T> for KeyedReusable<'_, K, T> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut T {
+        &mut self.item
+    }
+}
+
+impl<K: Eq + Hash, T> Deref for KeyedReusable<'_, K, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.item
+    }
+}
+
+impl<K: Eq + Hash, T> DerefMut for KeyedReusable<'_, K, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.item
+    }
+}
+
+impl<K: Eq + Hash, T> Drop for KeyedReusable<'_, K, T> {
+    #[inline]
+    fn drop(&mut self) {
+        let key = unsafe { ManuallyDrop::take(&mut self.key) };
+        let item = unsafe { ManuallyDrop::take(&mut self.item) };
+        self.pool.insert(key, item);
+    }
+}
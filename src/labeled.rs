@@ -0,0 +1,134 @@
+use core::borrow::{Borrow, BorrowMut};
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use crate::{ObjectPool, Reusable};
+
+/// The next identifier given to a labeled checkout.
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// A [`Reusable`] object carrying a checkout label and a unique checkout
+/// identifier, created with [`Reusable::labeled`]. It implements [`Display`],
+/// so including "which pooled object am I using" in structured logs is a `{}`
+/// away. The object is returned to the pool when dropped.
+///
+/// [`Display`]: fmt::Display
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::prelude::*;
+///
+/// #[derive(Default, ObjectPool)]
+/// struct Connection(u16);
+///
+/// fn main() {
+///     let conn = Connection::new().labeled("db-primary");
+///     let log = format!("using {conn}");
+///     assert!(log.starts_with("using db-primary#"));
+/// }
+/// ```
+pub struct Labeled<T: ObjectPool> {
+    /// The wrapped object.
+    item: Reusable<T>,
+    /// The label of the checkout.
+    label: &'static str,
+    /// The unique identifier of the checkout.
+    id: u64,
+}
+
+impl<T: ObjectPool> Labeled<T> {
+    /// Labels the checkout of the object, giving it a unique identifier.
+    #[inline]
+    pub(crate) fn new(item: Reusable<T>, label: &'static str) -> Self {
+        item.stamp.owner::<T>().checkouts.label(item.stamp, label);
+        Self {
+            item,
+            label,
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    /// Returns the label of the checkout.
+    #[inline]
+    pub const fn label(&self) -> &'static str {
+        self.label
+    }
+
+    /// Returns the unique identifier of the checkout.
+    #[inline]
+    pub const fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Removes the label, returning the [`Reusable`] object.
+    #[inline]
+    pub fn into_reusable(self) -> Reusable<T> {
+        self.item
+    }
+}
+
+impl<T: ObjectPool> fmt::Display for Labeled<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}#{} ({})",
+            self.label,
+            self.id,
+            core::any::type_name::<T>()
+        )
+    }
+}
+
+impl<T: ObjectPool> fmt::Debug for Labeled<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Labeled")
+            .field("label", &self.label)
+            .field("id", &self.id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: ObjectPool> Borrow<T> for Labeled<T> {
+    #[inline]
+    fn borrow(&self) -> &T {
+        &self.item
+    }
+}
+
+impl<T: ObjectPool> BorrowMut<T> for Labeled<T> {
+    #[inline]
+    fn borrow_mut(&mut self) -> &mut T {
+        &mut self.item
+    }
+}
+
+impl<T: ObjectPool> AsRef<T> for Labeled<T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        &self.item
+    }
+}
+
+impl<T: ObjectPool> AsMut<T> for Labeled<T> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut T {
+        &mut self.item
+    }
+}
+
+impl<T: ObjectPool> Deref for Labeled<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.item
+    }
+}
+
+impl<T: ObjectPool> DerefMut for Labeled<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.item
+    }
+}
@@ -7,20 +7,23 @@
 //!
 //! This crate has the following features compared to other object pool crates:
 //! - **Derivable**: The pool is simple to use and can be used with any type. Can
-//! be just derived using the [`#[derive(ObjectPool)]`](derive@ObjectPool)
-//! attribute macro.
+//!   be just derived using the [`#[derive(ObjectPool)]`](derive@ObjectPool)
+//!   attribute macro.
 //! - **Reusable**: The user can use the [`ObjectPool::new`] function to create
-//! objects from the pool, which will reuse objects from the pool if possible.
-//! This items are wrapped in a [`Reusable`] struct, which will be returned to
-//! the pool when dropped.
-//! - **Thread Safe**: The pool is thread-safe (through the use of a [`Mutex`])
-//! and can be used in a multi-threaded environment.
+//!   objects from the pool, which will reuse objects from the pool if possible.
+//!   This items are wrapped in a [`Reusable`] struct, which will be returned to
+//!   the pool when dropped.
+//! - **Thread Safe**: The pool is thread-safe (through the use of a
+//!   [`Mutex`](std::sync::Mutex), or an optional lock-free backend, see
+//!   [`Pool::with_lock_free`]) and can be used in a multi-threaded environment.
 //! - **Simple**: The user doesn't need to create a pool for each type manually
-//! and can use the [`ObjectPool::new`] function to create objects from the
-//! pool.
+//!   and can use the [`ObjectPool::new`] function to create objects from the
+//!   pool.
 //! - **Flexible**: The user can configure the pool to use a custom generator
-//! function (see attributes in [`#[derive(ObjectPool)]`](derive@ObjectPool)) or
-//! just use the [`Default`] trait to create new objects.
+//!   function (see attributes in [`#[derive(ObjectPool)]`](derive@ObjectPool)) or
+//!   just use the [`Default`] trait to create new objects.
+//! - **`no_std`**: Without the default `std` feature, the crate only depends
+//!   on `alloc` and uses the spin locks of the `spin` feature.
 //!
 //! # Example
 //!
@@ -41,12 +44,178 @@
 //!     assert_eq!(obj.0, 1);
 //! }
 //! ```
-use std::borrow::{Borrow, BorrowMut};
-use std::mem::{forget, ManuallyDrop};
-use std::ops::{Deref, DerefMut};
-use std::sync::{Mutex, MutexGuard};
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
-pub use derivable_object_pool_macros::ObjectPool;
+extern crate alloc;
+// Lets the derived code refer to the crate by its full path inside the crate
+extern crate self as derivable_object_pool;
+
+use alloc::vec::Vec;
+use core::borrow::{Borrow, BorrowMut};
+use core::cmp;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::FusedIterator;
+use core::mem::{forget, ManuallyDrop};
+use core::ops::{Deref, DerefMut};
+use core::pin::Pin;
+use core::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+#[cfg(feature = "std")]
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+pub use derivable_object_pool_macros::{global_pool, ObjectPool};
+
+/// Re-export of the [`pin_project!`](pin_project_lite::pin_project) macro from
+/// the `pin-project-lite` crate (requires the `pin-project` feature). It allows
+/// safely projecting the fields of pinned pooled objects, so pooled futures or
+/// state machines can be written without unsafe code.
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::pin_project;
+/// use derivable_object_pool::prelude::*;
+/// use std::future::Future;
+/// use std::pin::Pin;
+/// use std::task::{Context, Poll};
+///
+/// pin_project! {
+///     #[derive(ObjectPool)]
+///     #[generator(Countdown::start)]
+///     struct Countdown {
+///         #[pin]
+///         ready: std::future::Ready<()>,
+///         remaining: usize,
+///     }
+/// }
+///
+/// impl Countdown {
+///     fn start() -> Self {
+///         Self { ready: std::future::ready(()), remaining: 0 }
+///     }
+/// }
+///
+/// impl Future for Countdown {
+///     type Output = usize;
+///
+///     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<usize> {
+///         let this = self.project();
+///         *this.remaining += 1;
+///         this.ready.poll(cx).map(|()| *this.remaining)
+///     }
+/// }
+///
+/// fn main() {
+///     let mut countdown = Countdown::new();
+///     let waker = std::task::Waker::noop();
+///     let mut cx = Context::from_waker(&waker);
+///     let poll = Pin::new(&mut *countdown).poll(&mut cx);
+///     assert_eq!(poll, Poll::Ready(1));
+/// }
+/// ```
+#[cfg(feature = "pin-project")]
+pub use pin_project_lite::pin_project;
+
+#[cfg(feature = "tokio")]
+mod async_pool;
+mod batch;
+#[cfg(feature = "std")]
+mod blocking;
+mod buffer;
+mod checkouts;
+mod counters;
+mod debug;
+mod error;
+mod fallible;
+mod foreign;
+mod frozen;
+mod generic;
+mod guard;
+mod handle;
+mod hold;
+mod idle;
+mod inbox;
+#[cfg(feature = "introspection")]
+mod introspection;
+#[cfg(feature = "std")]
+mod io;
+#[cfg(feature = "std")]
+mod keyed;
+mod labeled;
+#[cfg(feature = "std")]
+mod maintenance;
+mod mapped;
+mod maybe;
+mod padded;
+mod recycle;
+pub mod registry;
+mod returns;
+#[cfg(feature = "std")]
+mod scoped;
+#[cfg(feature = "serde")]
+mod serialize;
+mod shared;
+mod size;
+mod slots;
+mod snapshot;
+mod staged;
+mod stamp;
+mod static_pool;
+mod sync;
+#[cfg(feature = "std")]
+mod testing;
+mod zero;
+
+#[cfg(feature = "tokio")]
+pub use async_pool::{AsyncObjectPool, AsyncPool, AsyncReusable};
+pub use batch::{acquire_three, acquire_two, AcquireAll};
+#[cfg(feature = "std")]
+use blocking::Gate;
+pub use buffer::{BufferPool, BufferReusable};
+use checkouts::Checkouts;
+#[cfg(feature = "leak-detection")]
+pub use checkouts::Overdue;
+#[cfg(feature = "counters")]
+pub use counters::Stats;
+use counters::{Counters, Event};
+pub use debug::{ItemDebugState, PoolDebugState};
+pub use error::{AcquireError, AcquireErrorKind};
+pub use fallible::TryObjectPool;
+pub use frozen::FrozenPool;
+pub use guard::PoolGuard;
+pub use handle::{PoolHandle, Pooled};
+pub use hold::HoldToken;
+use idle::{Idle, IdleGuard};
+use inbox::Inbox;
+#[cfg(feature = "introspection")]
+pub use introspection::{
+    introspection_table, IntrospectionEntry, IntrospectionTable,
+    DERIVABLE_OBJECT_POOL_INTROSPECTION, INTROSPECTION_MAGIC, MAX_INTROSPECTED_POOLS,
+};
+#[cfg(feature = "std")]
+pub use keyed::{KeyedPool, KeyedReusable};
+pub use labeled::Labeled;
+#[cfg(feature = "std")]
+pub use maintenance::MaintenanceGuard;
+#[cfg(feature = "tokio")]
+pub use maintenance::MaintenanceTask;
+pub use mapped::MappedReusable;
+pub use maybe::MaybePooled;
+use padded::CachePadded;
+pub use recycle::Recycle;
+pub use returns::ReturnBatch;
+pub use shared::{SharedReusable, WeakReusable};
+pub use size::HeapSize;
+use slots::Slots;
+pub use snapshot::PoolSnapshot;
+pub use staged::StagedReturn;
+use stamp::Stamp;
+pub use static_pool::{StaticObjectPool, StaticPool, StaticReusable};
+use sync::{Mutex, RwLock};
+#[cfg(feature = "std")]
+pub use testing::{AllReturnedGuard, PoolTestGuard};
+pub use zero::Zeroable;
 
 /// Allows for the creation of objects that can be reused. This is useful for
 /// objects that are expensive to create, but are used frequently. This trait
@@ -104,6 +273,38 @@ pub use derivable_object_pool_macros::ObjectPool;
 ///     let obj2 = Test::new(); // obj2 is the same object as obj
 /// }
 /// ```
+///
+/// Enums can be pooled as well, as long as they implement [`Default`] or have a
+/// generator:
+/// ```
+/// use derivable_object_pool::prelude::*;
+///
+/// #[derive(Debug, Default, PartialEq, ObjectPool)]
+/// enum State {
+///     #[default]
+///     Idle,
+///     Running(Vec<u8>),
+/// }
+///
+/// fn main() {
+///     let mut state = State::new();
+///     assert_eq!(*state, State::Idle);
+///     *state = State::Running(vec![1]);
+///     drop(state);
+///     assert_eq!(*State::new(), State::Running(vec![1]));
+/// }
+/// ```
+///
+/// Without either of them, the pool can't create new objects:
+/// ```compile_fail
+/// use derivable_object_pool::prelude::*;
+///
+/// #[derive(ObjectPool)]
+/// enum Event {
+///     Click,
+///     Key(char),
+/// }
+/// ```
 pub trait ObjectPool: Sized {
     /// Returns a reference to the pool for this type of object. This allows
     /// you to interact with the pool directly, if you need to.
@@ -147,104 +348,2721 @@ pub trait ObjectPool: Sized {
     ///     assert_eq!(obj.0, 1);
     /// }
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pool is empty and it can't create new objects (see
+    /// [`Pool::reuse_only`]), use [`ObjectPool::acquire`] to handle the error.
     #[must_use]
     #[inline]
     fn new() -> Reusable<Self> {
-        let mut pool = Self::pool().get_pool();
-        match pool.pop() {
-            Some(item) => Reusable::new(item),
-            None => Reusable::new((Self::pool().generator)()),
+        Reusable::checked_out(Self::pool().take_or_generate())
+    }
+
+    /// Creates a new object like [`ObjectPool::new`], but returns an error
+    /// instead of panicking if the object can't be acquired (for example if
+    /// the pool is empty and it can't create new objects).
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// #[derive(Default, ObjectPool)]
+    /// struct Test(i32);
+    ///
+    /// fn main() {
+    ///     let obj = Test::acquire().unwrap();
+    ///     assert_eq!(obj.0, 0);
+    /// }
+    /// ```
+    #[inline]
+    fn acquire() -> Result<Reusable<Self>, AcquireError> {
+        Self::pool()
+            .try_take_or_generate()
+            .map(Reusable::checked_out)
+    }
+
+    /// Acquires an object (like [`ObjectPool::new`]), calls the closure with it
+    /// and returns the object to the pool. The object is returned even if the
+    /// closure exits early or panics, so no guard has to be bound by the
+    /// caller.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    /// use std::fmt::Write;
+    ///
+    /// #[derive(Default, ObjectPool)]
+    /// struct Scratch(String);
+    ///
+    /// fn render(id: u32) -> Result<usize, std::fmt::Error> {
+    ///     Scratch::with(|scratch| {
+    ///         scratch.0.clear();
+    ///         write!(scratch.0, "item-{id}")?;
+    ///         Ok(scratch.0.len())
+    ///     })
+    /// }
+    ///
+    /// fn main() {
+    ///     assert_eq!(render(7).unwrap(), 6);
+    ///     assert_eq!(Scratch::pool().len(), 1);
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the object can't be acquired, like [`ObjectPool::new`].
+    #[inline]
+    fn with<R>(f: impl FnOnce(&mut Self) -> R) -> R {
+        f(&mut Self::new())
+    }
+
+    /// Creates `n` objects at once. The idle objects of the pool are taken
+    /// under a single lock, and the remaining ones are created with the
+    /// generator function, so acquiring large batches doesn't lock the pool once
+    /// per object.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// #[derive(Default, ObjectPool)]
+    /// struct Buffer(Vec<u8>);
+    ///
+    /// fn main() {
+    ///     drop(Buffer::new_n(2));
+    ///     assert_eq!(Buffer::pool().len(), 2);
+    ///
+    ///     let buffers = Buffer::new_n(1000);
+    ///     assert_eq!(buffers.len(), 1000);
+    ///     assert_eq!(Buffer::pool().len(), 0);
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pool doesn't have enough objects and it can't create new
+    /// ones, like [`ObjectPool::new`]. The objects acquired until then are
+    /// returned to the pool.
+    #[must_use]
+    #[inline]
+    fn new_n(n: usize) -> Vec<Reusable<Self>> {
+        Self::pool()
+            .take_or_generate_n(n)
+            .map(Reusable::checked_out)
+            .collect()
+    }
+
+    /// Returns a cloneable [`PoolHandle`] to the pool of this type. This is
+    /// useful to pass the pool around (for example to worker threads or to
+    /// structs) without depending on the static [`ObjectPool::pool`] accessor.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// #[derive(Default, ObjectPool)]
+    /// struct Test(i32);
+    ///
+    /// fn main() {
+    ///     let handle = Test::handle();
+    ///     let obj = handle.acquire();
+    ///     drop(obj);
+    ///     assert_eq!(Test::pool().len(), 1);
+    /// }
+    /// ```
+    #[inline]
+    fn handle() -> PoolHandle<Self>
+    where
+        Self: 'static,
+    {
+        PoolHandle::from_static(Self::pool())
+    }
+
+    /// Fills the pool of this type with its prefill objects now, instead of
+    /// the first time it is used (see [`Pool::with_prefill`] and
+    /// [`Pool::warm`]).
+    #[inline]
+    fn warm_pool() {
+        Self::pool().warm();
+    }
+}
+
+/// A pool of objects that can be reused. This is useful for objects that are
+/// expensive to create, but are used frequently. This struct can be created
+/// using the [`Pool::new`] function. However, it is highly recommended that
+/// you use the [`ObjectPool`] trait instead, as it is much easier to use.
+///
+///
+/// # Example
+///
+/// Example without deriving [`ObjectPool`]:
+///
+/// ```
+/// use derivable_object_pool::prelude::*;
+///
+/// #[derive(Default)]
+/// struct Test;
+///
+/// static POOL: Pool<Test> = Pool::new(Test::default);
+///
+/// impl ObjectPool for Test {
+///     fn pool<'a>() -> &'a Pool<Self> {
+///         &POOL
+///     }
+/// }
+///
+/// fn main() {
+///    let obj = Test::new();
+///    drop(obj); // obj is returned to the pool
+///    assert_eq!(POOL.len(), 1);
+/// }
+/// ```
+pub struct Pool<T> {
+    /// The pool of objects that can be reused. The pool uses a [`Mutex`] to
+    /// ensure that it is thread-safe, padded to a cache line to avoid false
+    /// sharing with neighboring data.
+    pool: CachePadded<Mutex<Idle<T>>>,
+    /// The number of objects in the pool, published whenever the pool is
+    /// unlocked so it can be read without locking.
+    len: AtomicUsize,
+    /// The generator function that is used to create new objects, if the pool
+    /// can create them.
+    generator: Option<fn() -> T>,
+    /// The function used to measure the heap size of returned objects, if the
+    /// pool limits the size of the objects it stores.
+    heap_size: Option<fn(&T) -> usize>,
+    /// The maximum heap size of the objects stored by the pool.
+    max_object_size: usize,
+    /// The maximum total heap size of the idle objects of the pool.
+    max_bytes: usize,
+    /// The number of returned objects that were dropped for exceeding the
+    /// maximum heap size.
+    oversized_rejections: AtomicUsize,
+    /// Whether the objects are forbidden from escaping the pool through
+    /// [`Reusable::into_inner_checked`].
+    escape_forbidden: bool,
+    /// The number of idle objects above which the soft limit callback is
+    /// triggered.
+    soft_limit: usize,
+    /// The callback triggered when the soft limit is crossed.
+    on_soft_limit: Option<fn(usize)>,
+    /// The ordered functions applied to every object handed out by the pool.
+    middlewares: RwLock<Vec<fn(&mut T)>>,
+    /// The lock-free inbox of returned objects (stamped when they were checked
+    /// out), used if enabled.
+    inbox: Inbox<(T, Stamp)>,
+    /// Whether the returned objects are pushed into the inbox instead of
+    /// locking the pool.
+    inbox_enabled: bool,
+    /// The current epoch of the pool, incremented on each invalidation.
+    epoch: AtomicU64,
+    /// The maximum number of idle objects stored by the pool.
+    capacity: AtomicUsize,
+    /// The number of idle objects the maintenance of the pool tops it up to.
+    min_idle: usize,
+    /// The number of objects created at once when an acquisition finds the
+    /// pool empty.
+    growth: usize,
+    /// The number of objects the pool is filled with the first time it is
+    /// used.
+    prefill: usize,
+    /// Whether the pool was already filled with its prefill objects.
+    prefilled: AtomicBool,
+    /// The time after which the maintenance of the pool evicts idle objects,
+    /// if any.
+    #[cfg(feature = "std")]
+    idle_timeout: Option<Duration>,
+    /// The function that resets the returned objects, if enabled.
+    recycle: Option<fn(&mut T)>,
+    /// The hook called with each newly created object.
+    on_create: Option<fn(&mut T)>,
+    /// The hook called with each object handed out again by the pool.
+    on_reuse: Option<fn(&mut T)>,
+    /// The hook called with each object returned to the pool.
+    on_return: Option<fn(&mut T)>,
+    /// The hook called with each object dropped by the pool.
+    on_discard: Option<fn(&mut T)>,
+    /// The predicate the idle objects must pass to be handed out, if any.
+    validator: Option<fn(&T) -> bool>,
+    /// The number of times an object can be handed out again before it is
+    /// dropped instead of being returned to the pool.
+    max_reuses: u32,
+    /// The number of objects whose return is staged.
+    staged: AtomicUsize,
+    /// The function that zeroes the objects before handing them out, if
+    /// enabled.
+    zero: Option<fn(&mut T)>,
+    /// The lock-free free-list of idle objects (stamped when they were checked
+    /// out), used before locking the pool if enabled.
+    slots: Slots<(T, Stamp)>,
+    /// The function that fills the pool with its initial objects, called the
+    /// first time the pool is locked.
+    initial_items: Option<fn(&mut Vec<T>)>,
+    /// Whether the initial objects were already added to the pool.
+    initialized: AtomicBool,
+    /// Whether the pool was added to the global registry.
+    registered: AtomicBool,
+    /// The human-assigned name of the pool, if any.
+    name: RwLock<Option<&'static str>>,
+    /// The counters of the pool, which are zero-sized unless enabled.
+    counters: Counters,
+    /// The live checkouts of the pool, which are zero-sized unless enabled.
+    checkouts: Checkouts,
+    /// The number of objects held in guards (like [`Reusable`]) that were not
+    /// returned to the pool yet.
+    outstanding: AtomicUsize,
+    /// The maximum number of objects held in guards for the blocking
+    /// acquisitions to proceed.
+    max_outstanding: AtomicUsize,
+    /// The gate where blocking acquisitions wait for objects to be returned.
+    #[cfg(feature = "std")]
+    gate: Gate,
+    /// The order in which the blocking acquisitions are served.
+    #[cfg(feature = "std")]
+    fairness: Fairness,
+    /// The entry of the pool in the introspection table, registered the first
+    /// time the pool hands out or stores an object.
+    #[cfg(feature = "introspection")]
+    introspection: std::sync::OnceLock<Option<&'static IntrospectionEntry>>,
+}
+
+impl<T> Pool<T> {
+    /// Creates a new pool of objects. The pool will use the specified generator
+    /// function to create new objects.
+    #[must_use]
+    #[inline]
+    pub const fn new(generator: fn() -> T) -> Self {
+        Self::with_generator(Some(generator))
+    }
+
+    /// Creates a new pool of objects without a generator function. The pool
+    /// can only hand out objects that were inserted into it, so acquiring an
+    /// object from an empty pool fails (see [`ObjectPool::acquire`]). This is
+    /// useful for strictly bounded resources (like licensed handles or
+    /// hardware contexts) where creating more objects is impossible.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    /// use derivable_object_pool::AcquireErrorKind;
+    ///
+    /// struct License(u32);
+    ///
+    /// static POOL: Pool<License> = Pool::reuse_only();
+    ///
+    /// impl ObjectPool for License {
+    ///     fn pool<'a>() -> &'a Pool<Self> {
+    ///         &POOL
+    ///     }
+    /// }
+    ///
+    /// fn main() {
+    ///     POOL.insert(License(1));
+    ///     let license = License::acquire().unwrap();
+    ///     assert!(License::acquire().is_err_and(|err| err == AcquireErrorKind::Exhausted));
+    ///     drop(license);
+    ///     assert!(License::acquire().is_ok());
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn reuse_only() -> Self {
+        Self::with_generator(None)
+    }
+
+    /// Creates a new pool of objects that starts with the specified objects
+    /// (see [`Pool::restore_with_order`]). The pool will use the specified
+    /// generator function to create new objects.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// fn main() {
+    ///     let pool = Pool::from_iter(String::new, ["a", "b"].map(String::from));
+    ///     assert_eq!(pool.len(), 2);
+    ///     assert_eq!(pool.remove().unwrap(), "b");
+    /// }
+    /// ```
+    pub fn from_iter<I>(generator: fn() -> T, items: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let pool = Self::new(generator);
+        pool.restore_with_order(items, RestoreOrder::ReuseFirst);
+        pool
+    }
+
+    /// Creates a new pool of objects with the optional generator function.
+    const fn with_generator(generator: Option<fn() -> T>) -> Self {
+        Self {
+            pool: CachePadded::new(Mutex::new(Idle::new())),
+            len: AtomicUsize::new(0),
+            generator,
+            heap_size: None,
+            max_object_size: usize::MAX,
+            max_bytes: usize::MAX,
+            oversized_rejections: AtomicUsize::new(0),
+            escape_forbidden: false,
+            soft_limit: usize::MAX,
+            on_soft_limit: None,
+            middlewares: RwLock::new(Vec::new()),
+            inbox: Inbox::new(),
+            inbox_enabled: false,
+            epoch: AtomicU64::new(0),
+            capacity: AtomicUsize::new(usize::MAX),
+            min_idle: 0,
+            growth: 1,
+            prefill: 0,
+            prefilled: AtomicBool::new(false),
+            #[cfg(feature = "std")]
+            idle_timeout: None,
+            recycle: None,
+            on_create: None,
+            on_reuse: None,
+            on_return: None,
+            on_discard: None,
+            validator: None,
+            max_reuses: u32::MAX,
+            staged: AtomicUsize::new(0),
+            zero: None,
+            slots: Slots::new(0),
+            initial_items: None,
+            initialized: AtomicBool::new(false),
+            registered: AtomicBool::new(false),
+            name: RwLock::new(None),
+            counters: Counters::new(),
+            checkouts: Checkouts::new(),
+            outstanding: AtomicUsize::new(0),
+            max_outstanding: AtomicUsize::new(usize::MAX),
+            #[cfg(feature = "std")]
+            gate: Gate::new(),
+            #[cfg(feature = "std")]
+            fairness: Fairness::Unfair,
+            #[cfg(feature = "introspection")]
+            introspection: std::sync::OnceLock::new(),
+        }
+    }
+
+    /// Sets the name of the pool, which identifies it in its statistics, logs
+    /// and the listings of the [`registry`] instead of the name of the type of
+    /// its objects. This is useful when several pools share an underlying type
+    /// (for example through newtypes) but have a different purpose.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    /// use derivable_object_pool::registry;
+    ///
+    /// static POOL: Pool<Vec<u8>> = Pool::new(Vec::new).with_name("http_request_buffers");
+    ///
+    /// fn main() {
+    ///     POOL.register("POOL");
+    ///     assert_eq!(POOL.name(), Some("http_request_buffers"));
+    ///     assert!(registry::iter().any(|pool| pool.name() == "http_request_buffers"));
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn with_name(mut self, name: &'static str) -> Self {
+        self.name = RwLock::new(Some(name));
+        self
+    }
+
+    /// Changes the name of the pool at runtime (see [`Pool::with_name`]).
+    #[inline]
+    pub fn set_name(&self, name: &'static str) {
+        *self.name.write() = Some(name);
+    }
+
+    /// Returns the name of the pool, if it was assigned one (see
+    /// [`Pool::with_name`]).
+    #[inline]
+    pub fn name(&self) -> Option<&'static str> {
+        *self.name.read()
+    }
+
+    /// Returns the name that identifies the pool in its diagnostics: its name
+    /// if it was assigned one, or the name of the type of its objects.
+    #[cfg(any(feature = "log", feature = "tracing"))]
+    #[inline]
+    fn diagnostic_name(&self) -> &'static str {
+        self.name().unwrap_or_else(core::any::type_name::<T>)
+    }
+
+    /// Enables zeroing the objects (setting all their bytes to zero) right
+    /// before handing them out, both reused and newly created ones. This is a
+    /// cheap blanket guarantee against stale data bugs for pools of plain old
+    /// data scratch structs, where a full [`Recycle`] implementation is
+    /// overkill. The zeroing happens before the acquire middlewares run.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// static POOL: Pool<[u8; 16]> = Pool::new(|| [1; 16]).zero_on_acquire();
+    ///
+    /// fn main() {
+    ///     let handle = PoolHandle::from_static(&POOL);
+    ///     assert_eq!(*handle.acquire(), [0; 16]);
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn zero_on_acquire(mut self) -> Self
+    where
+        T: Zeroable + Copy,
+    {
+        self.zero = Some(zero::zero::<T>);
+        self
+    }
+
+    /// Enables recycling the objects returned to the pool: [`Recycle::recycle`]
+    /// is called on every returned (or inserted) object before storing it, so
+    /// the objects are handed out with a clean state.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// static POOL: Pool<Vec<u8>> = Pool::new(Vec::new).with_recycle();
+    ///
+    /// fn main() {
+    ///     POOL.insert(vec![1, 2, 3]);
+    ///     assert!(POOL.remove().unwrap().is_empty());
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn with_recycle(mut self) -> Self
+    where
+        T: Recycle,
+    {
+        self.recycle = Some(T::recycle);
+        self
+    }
+
+    /// Sets a hook called with each object created by the generator function
+    /// (or by [`Pool::prefill_with`]), before it is handed out or stored.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// static CREATED: AtomicUsize = AtomicUsize::new(0);
+    /// static POOL: Pool<Vec<u8>> =
+    ///     Pool::new(Vec::new).on_create(|_| drop(CREATED.fetch_add(1, Ordering::Relaxed)));
+    ///
+    /// fn main() {
+    ///     POOL.prefill(2);
+    ///     assert_eq!(CREATED.load(Ordering::Relaxed), 2);
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn on_create(mut self, hook: fn(&mut T)) -> Self {
+        self.on_create = Some(hook);
+        self
+    }
+
+    /// Sets a hook called with each idle object right before it is handed out
+    /// again by the pool (objects taken with [`Pool::remove`] bypass it).
+    #[must_use]
+    #[inline]
+    pub const fn on_reuse(mut self, hook: fn(&mut T)) -> Self {
+        self.on_reuse = Some(hook);
+        self
+    }
+
+    /// Sets a hook called with each object returned to the pool, before the
+    /// pool decides whether to store it (for example to roll back the
+    /// transaction of a database connection).
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// #[derive(Default)]
+    /// struct Connection {
+    ///     in_transaction: bool,
+    /// }
+    ///
+    /// static POOL: Pool<Connection> =
+    ///     Pool::new(Connection::default).on_return(|conn| conn.in_transaction = false);
+    ///
+    /// fn main() {
+    ///     let mut conn = POOL.acquire();
+    ///     conn.in_transaction = true;
+    ///     drop(conn);
+    ///     assert!(!POOL.remove().unwrap().in_transaction);
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn on_return(mut self, hook: fn(&mut T)) -> Self {
+        self.on_return = Some(hook);
+        self
+    }
+
+    /// Sets a hook called with each object the pool drops instead of storing
+    /// or handing it out: returned objects rejected by the capacity or the
+    /// maximum object size of the pool, idle objects that fail its validator
+    /// (see [`Pool::with_validator`]), idle objects evicted after a timeout
+    /// (see [`Pool::evict_idle`]), trimmed (see [`Pool::trim`]) or purged
+    /// (see [`Pool::retain`]), and objects of a previous epoch (see
+    /// [`Pool::invalidate`]).
+    #[must_use]
+    #[inline]
+    pub const fn on_discard(mut self, hook: fn(&mut T)) -> Self {
+        self.on_discard = Some(hook);
+        self
+    }
+
+    /// Sets a predicate that idle objects must pass to be handed out again.
+    /// Objects that fail it are discarded (see [`Pool::on_discard`]) and the
+    /// next idle object is tried, or a new one is created if there are none
+    /// left. This avoids handing out broken objects, like pooled network
+    /// connections that went stale while idle.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// struct Connection {
+    ///     alive: bool,
+    /// }
+    ///
+    /// static POOL: Pool<Connection> =
+    ///     Pool::new(|| Connection { alive: true }).with_validator(|conn| conn.alive);
+    ///
+    /// fn main() {
+    ///     let mut conn = POOL.acquire();
+    ///     conn.alive = false; // the connection was closed by the server
+    ///     drop(conn);
+    ///
+    ///     let conn = POOL.acquire(); // the closed connection was discarded
+    ///     assert!(conn.alive);
+    ///     assert_eq!(POOL.len(), 0);
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn with_validator(mut self, validator: fn(&T) -> bool) -> Self {
+        self.validator = Some(validator);
+        self
+    }
+
+    /// Sets the maximum number of objects checked out from the pool at the same
+    /// time for the blocking acquisitions (see [`Pool::acquire_blocking`],
+    /// requires the `std` feature), which wait for an object to be returned
+    /// instead of creating a new one once the limit is reached. As new objects
+    /// are only created when the pool has no idle objects, this is a hard upper
+    /// bound on the number of live objects (like open connections) as long as
+    /// they are only acquired that way.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// static POOL: Pool<Vec<u8>> = Pool::new(Vec::new).with_max_outstanding(2);
+    ///
+    /// fn main() {
+    ///     let first = POOL.acquire_blocking();
+    ///     let second = POOL.acquire_blocking();
+    ///     let waiter = std::thread::spawn(|| POOL.acquire_blocking().len());
+    ///     drop(first); // wakes up the waiter, which reuses the object
+    ///     assert_eq!(waiter.join().unwrap(), 0);
+    ///     drop(second);
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    #[must_use]
+    #[inline]
+    pub const fn with_max_outstanding(mut self, max_outstanding: usize) -> Self {
+        self.max_outstanding = AtomicUsize::new(max_outstanding);
+        self
+    }
+
+    /// Changes the maximum number of objects checked out at the same time for
+    /// the blocking acquisitions at runtime (see
+    /// [`Pool::with_max_outstanding`]), for example from a configuration
+    /// watcher. The objects already checked out are kept, so lowering the
+    /// limit below them only makes the next blocking acquisitions wait for
+    /// more objects to be returned, while raising it wakes them up.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// static POOL: Pool<Vec<u8>> = Pool::new(Vec::new).with_max_outstanding(1);
+    ///
+    /// fn main() {
+    ///     let first = POOL.acquire_blocking();
+    ///     let waiter = std::thread::spawn(|| POOL.acquire_blocking().len());
+    ///     POOL.set_max_outstanding(2); // wakes up the waiter
+    ///     assert_eq!(waiter.join().unwrap(), 0);
+    ///     drop(first);
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn set_max_outstanding(&self, max_outstanding: usize) {
+        self.max_outstanding
+            .store(max_outstanding, Ordering::Relaxed);
+        self.gate.notify_all();
+    }
+
+    /// Sets the order in which the blocking acquisitions waiting for an object
+    /// (see [`Pool::acquire_blocking`]) are served. With [`Fairness::Fifo`]
+    /// they are served in arrival order, instead of letting the operating
+    /// system pick. The acquisitions that don't wait (like
+    /// [`ObjectPool::new`]) are not queued.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    /// use derivable_object_pool::Fairness;
+    ///
+    /// static POOL: Pool<Vec<u8>> = Pool::new(Vec::new)
+    ///     .with_max_outstanding(1)
+    ///     .with_fairness(Fairness::Fifo);
+    ///
+    /// fn main() {
+    ///     assert_eq!(POOL.fairness(), Fairness::Fifo);
+    ///     drop(POOL.acquire_blocking());
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    #[must_use]
+    #[inline]
+    pub const fn with_fairness(mut self, fairness: Fairness) -> Self {
+        self.fairness = fairness;
+        self
+    }
+
+    /// Returns the order in which the blocking acquisitions are served (see
+    /// [`Pool::with_fairness`]).
+    #[cfg(feature = "std")]
+    #[inline]
+    pub const fn fairness(&self) -> Fairness {
+        self.fairness
+    }
+
+    /// Returns the maximum number of objects checked out at the same time for
+    /// the blocking acquisitions (see [`Pool::with_max_outstanding`]).
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn max_outstanding(&self) -> usize {
+        self.max_outstanding.load(Ordering::Relaxed)
+    }
+
+    /// Sets the maximum number of times an object can be handed out again by
+    /// the pool. Once an object was reused that many times, it is discarded
+    /// (see [`Pool::on_discard`]) instead of being returned to the pool. This
+    /// bounds the slow degradation of long-lived objects, like fragmented
+    /// buffers or connections leaking server-side state.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// static POOL: Pool<Vec<u8>> = Pool::new(Vec::new).with_max_reuses(1);
+    ///
+    /// fn main() {
+    ///     drop(POOL.acquire()); // created and returned
+    ///     let buffer = POOL.acquire();
+    ///     assert_eq!(buffer.reuses(), 1);
+    ///     drop(buffer); // discarded, as it was already reused once
+    ///     assert_eq!(POOL.len(), 0);
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn with_max_reuses(mut self, max_reuses: u32) -> Self {
+        self.max_reuses = max_reuses;
+        self
+    }
+
+    /// Returns the maximum number of times an object can be handed out again
+    /// by the pool (see [`Pool::with_max_reuses`]).
+    #[inline]
+    pub const fn max_reuses(&self) -> u32 {
+        self.max_reuses
+    }
+
+    /// Drops an object discarded by the pool, calling the discard hook.
+    #[inline]
+    fn discard(&self, mut item: T) {
+        #[cfg(feature = "tracing")]
+        tracing::trace!(
+            pool = self.diagnostic_name(),
+            event = "discarded",
+            "pool event"
+        );
+        if let Some(on_discard) = self.on_discard {
+            on_discard(&mut item);
+        }
+    }
+
+    /// Limits the number of idle objects stored by the pool. Once the pool
+    /// holds `capacity` objects, the extra returned objects are dropped instead
+    /// of being pooled, so the pool never grows indefinitely (for example in
+    /// long-running servers after a burst of requests).
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// static POOL: Pool<Vec<u8>> = Pool::new(Vec::new).with_capacity(2);
+    ///
+    /// fn main() {
+    ///     for _ in 0..4 {
+    ///         POOL.insert(Vec::new());
+    ///     }
+    ///     assert_eq!(POOL.len(), 2);
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = AtomicUsize::new(capacity);
+        self
+    }
+
+    /// Changes the maximum number of idle objects stored by the pool (its
+    /// capacity, see [`Pool::with_capacity`]) at runtime, for example from a configuration
+    /// watcher. If the pool holds more idle objects than the new capacity, the
+    /// ones that would be handed out last are discarded (see
+    /// [`Pool::on_discard`]) after releasing the lock. Returns the number of
+    /// dropped objects.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// static POOL: Pool<Vec<u8>> = Pool::new(Vec::new).with_capacity(8);
+    ///
+    /// fn main() {
+    ///     POOL.prefill(8);
+    ///     assert_eq!(POOL.set_max_idle(2), 6);
+    ///     assert_eq!(POOL.len(), 2);
+    ///     assert_eq!(POOL.capacity(), 2);
+    /// }
+    /// ```
+    pub fn set_max_idle(&self, capacity: usize) -> usize {
+        let trimmed = {
+            let mut pool = self.get_pool();
+            // Stored while the lock is held, so no object is pooled over it
+            self.capacity.store(capacity, Ordering::Relaxed);
+            let excess = pool.len().saturating_sub(capacity);
+            pool.split_bottom(excess)
+        };
+        let count = trimmed.len();
+        for item in trimmed {
+            self.discard(item);
+        }
+        count
+    }
+
+    /// Returns the maximum number of idle objects stored by the pool (see
+    /// [`Pool::with_capacity`]). Unbounded pools return [`usize::MAX`].
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity.load(Ordering::Relaxed)
+    }
+
+    /// Sets a floor of idle objects for the pool. Each maintenance pass (see
+    /// [`Pool::maintain`]) tops the pool back up to the floor with the
+    /// generator function, so bursty workloads consistently hit the warm path
+    /// instead of paying the construction cost at the start of every burst.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// static POOL: Pool<Vec<u8>> = Pool::new(Vec::new).with_min_idle(4);
+    ///
+    /// fn main() {
+    ///     POOL.maintain(); // e.g. from a maintenance thread
+    ///     let burst: Vec<_> = (0..4).map(|_| POOL.remove().unwrap()).collect();
+    ///     assert!(POOL.is_empty());
+    ///
+    ///     POOL.maintain();
+    ///     assert_eq!(POOL.len(), 4);
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn with_min_idle(mut self, min_idle: usize) -> Self {
+        self.min_idle = min_idle;
+        self
+    }
+
+    /// Returns the floor of idle objects of the pool (see
+    /// [`Pool::with_min_idle`]).
+    #[inline]
+    pub const fn min_idle(&self) -> usize {
+        self.min_idle
+    }
+
+    /// Sets the number of objects created at once when an acquisition finds
+    /// the pool empty. One of them is handed out and the rest are stored in
+    /// the pool (see [`Pool::prefill`]), so cheap but frequently acquired
+    /// objects amortize the cost of the generator and of locking the pool
+    /// during warm-up spikes. A growth of zero is treated as one.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// static POOL: Pool<Vec<u8>> = Pool::new(Vec::new).with_growth(16);
+    ///
+    /// fn main() {
+    ///     let buffer = POOL.acquire();
+    ///     assert_eq!(POOL.len(), 15);
+    ///     drop(buffer);
+    ///     assert_eq!(POOL.len(), 16);
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn with_growth(mut self, growth: usize) -> Self {
+        self.growth = if growth == 0 { 1 } else { growth };
+        self
+    }
+
+    /// Returns the number of objects created at once when an acquisition finds
+    /// the pool empty (see [`Pool::with_growth`]).
+    #[inline]
+    pub const fn growth(&self) -> usize {
+        self.growth
+    }
+
+    /// Sets the number of objects the pool is filled with the first time it is
+    /// used (see [`Pool::prefill`]), so pooled types are warmed without
+    /// hand-written startup code. The objects are created lazily, without
+    /// holding the lock of the pool, or eagerly with [`Pool::warm`]. Does
+    /// nothing for reuse-only pools.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// static POOL: Pool<Vec<u8>> = Pool::new(Vec::new).with_prefill(8);
+    ///
+    /// fn main() {
+    ///     let buffer = POOL.acquire(); // fills the pool, then reuses an object
+    ///     assert_eq!(POOL.len(), 7);
+    ///     drop(buffer);
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn with_prefill(mut self, prefill: usize) -> Self {
+        self.prefill = prefill;
+        self
+    }
+
+    /// Fills the pool with its prefill objects (see [`Pool::with_prefill`]) if
+    /// it wasn't filled yet, so the cost of warming the pool can be paid at
+    /// startup instead of by the first acquisition.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// #[derive(Default, ObjectPool)]
+    /// #[pool(prefill = 4)]
+    /// struct Buffer(Vec<u8>);
+    ///
+    /// fn main() {
+    ///     Buffer::warm_pool();
+    ///     Buffer::warm_pool(); // already filled
+    ///     assert_eq!(Buffer::pool().len(), 4);
+    /// }
+    /// ```
+    #[inline]
+    pub fn warm(&self) {
+        if self.prefill > 0 && !self.prefilled.load(Ordering::Relaxed) {
+            self.warm_slow();
+        }
+    }
+
+    #[cold]
+    fn warm_slow(&self) {
+        if !self.prefilled.swap(true, Ordering::Relaxed) {
+            self.prefill(self.prefill);
+        }
+    }
+
+    /// Sets the time after which idle objects are evicted by the maintenance
+    /// of the pool (see [`Pool::maintain`]), so pools that grew during a burst
+    /// of traffic shrink back once it is over. The eviction never shrinks the
+    /// pool below its floor of idle objects (see [`Pool::with_min_idle`]).
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// static POOL: Pool<Vec<u8>> = Pool::new(Vec::new)
+    ///     .with_idle_timeout(Duration::from_millis(10))
+    ///     .with_min_idle(1);
+    ///
+    /// fn main() {
+    ///     let burst: Vec<_> = (0..4).map(|_| POOL.acquire()).collect();
+    ///     drop(burst);
+    ///     assert_eq!(POOL.len(), 4);
+    ///
+    ///     std::thread::sleep(Duration::from_millis(20));
+    ///     POOL.maintain();
+    ///     assert_eq!(POOL.len(), 1);
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    #[must_use]
+    #[inline]
+    pub const fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = Some(idle_timeout);
+        self
+    }
+
+    /// Returns the time after which idle objects are evicted by the
+    /// maintenance of the pool, if any (see [`Pool::with_idle_timeout`]).
+    #[cfg(feature = "std")]
+    #[inline]
+    pub const fn idle_timeout(&self) -> Option<Duration> {
+        self.idle_timeout
+    }
+
+    /// Drops the idle objects that entered the pool more than `older_than`
+    /// ago, calling the discard hook with each of them (see
+    /// [`Pool::on_discard`]). The objects are dropped after releasing the
+    /// lock. Returns the number of evicted objects.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let pool = Pool::new(String::new);
+    ///     pool.insert(String::from("old"));
+    ///     std::thread::sleep(Duration::from_millis(20));
+    ///     pool.insert(String::from("new"));
+    ///
+    ///     assert_eq!(pool.evict_idle(Duration::from_millis(10)), 1);
+    ///     assert_eq!(pool.remove().unwrap(), "new");
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn evict_idle(&self, older_than: Duration) -> usize {
+        self.evict_idle_above(older_than, 0)
+    }
+
+    /// Evicts the idle objects older than the specified time while the pool
+    /// has more than `keep` objects.
+    #[cfg(feature = "std")]
+    fn evict_idle_above(&self, older_than: Duration, keep: usize) -> usize {
+        // Nothing can be older than the start of the clock
+        let Some(deadline) = Instant::now().checked_sub(older_than) else {
+            return 0;
+        };
+        let evicted = self.get_pool().evict(deadline, keep);
+        let count = evicted.len();
+        for item in evicted {
+            self.discard(item);
+        }
+        count
+    }
+
+    /// Runs a maintenance pass on the pool: the return inbox is drained, the
+    /// idle objects are evicted after their timeout (see
+    /// [`Pool::with_idle_timeout`]), the idle objects that fail the validator
+    /// are discarded (see [`Pool::with_validator`]) and the pool is topped up
+    /// to its floor of idle objects (see [`Pool::with_min_idle`]). The new
+    /// objects are created before locking the pool. See
+    /// [`Pool::spawn_maintenance`] to run it periodically.
+    pub fn maintain(&self) {
+        #[cfg(feature = "std")]
+        if let Some(idle_timeout) = self.idle_timeout {
+            self.evict_idle_above(idle_timeout, self.min_idle);
+        }
+        if let Some(validator) = self.validator {
+            self.retain(validator);
+        }
+        let missing = self.min_idle.saturating_sub(self.len());
+        if missing > 0 {
+            self.prefill(missing);
+        }
+    }
+
+    /// Sets a soft limit on the number of idle objects of the pool. When a
+    /// returned object makes the pool cross the limit, the callback is called
+    /// with the new number of idle objects (after releasing the lock). The
+    /// returned objects are still accepted, so this gives operators an early
+    /// warning of leaks or workload shifts ahead of the hard limit set by
+    /// [`Pool::with_capacity`].
+    ///
+    /// The callback is triggered each time the limit is crossed, i.e. it will
+    /// only be triggered again once the pool shrinks to the limit and grows
+    /// past it again.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    /// use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+    ///
+    /// static WARNINGS: AtomicUsize = AtomicUsize::new(0);
+    ///
+    /// fn warn(idle: usize) {
+    ///     eprintln!("the pool has grown to {idle} idle objects");
+    ///     WARNINGS.fetch_add(1, Ordering::Relaxed);
+    /// }
+    ///
+    /// static POOL: Pool<String> = Pool::new(String::new).with_soft_limit(2, warn);
+    ///
+    /// fn main() {
+    ///     for _ in 0..4 {
+    ///         POOL.insert(String::new());
+    ///     }
+    ///     assert_eq!(POOL.len(), 4);
+    ///     assert_eq!(WARNINGS.load(Ordering::Relaxed), 1);
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn with_soft_limit(mut self, soft_limit: usize, callback: fn(usize)) -> Self {
+        self.soft_limit = soft_limit;
+        self.on_soft_limit = Some(callback);
+        self
+    }
+
+    /// Forbids the objects of the pool from escaping it through
+    /// [`Reusable::into_inner_checked`]. This allows enforcing data-handling
+    /// rules at the API level for audit-heavy environments (for example for
+    /// types that must be zeroized before being released).
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// #[derive(Default)]
+    /// struct Secret([u8; 32]);
+    ///
+    /// static POOL: Pool<Secret> = Pool::new(Secret::default).forbid_escape();
+    ///
+    /// impl ObjectPool for Secret {
+    ///     fn pool<'a>() -> &'a Pool<Self> {
+    ///         &POOL
+    ///     }
+    /// }
+    ///
+    /// fn main() {
+    ///     let secret = Secret::new();
+    ///     let Err(secret) = secret.into_inner_checked() else {
+    ///         panic!("the secret escaped the pool");
+    ///     };
+    ///     drop(secret); // returned to the pool
+    ///     assert_eq!(POOL.len(), 1);
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn forbid_escape(mut self) -> Self {
+        self.escape_forbidden = true;
+        self
+    }
+
+    /// Returns `true` if the objects of the pool are forbidden from escaping
+    /// it (see [`Pool::forbid_escape`]).
+    #[inline]
+    pub const fn escape_forbidden(&self) -> bool {
+        self.escape_forbidden
+    }
+
+    /// Limits the heap size (as reported by [`HeapSize`]) of the objects that
+    /// the pool stores. Returned objects that exceed the limit are dropped
+    /// instead of being pooled, so a single giant request can't fill the pool
+    /// with huge buffers. The number of rejected objects can be queried with
+    /// [`Pool::oversized_rejections`].
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// static POOL: Pool<Vec<u8>> = Pool::new(Vec::new).with_max_object_size(1024);
+    ///
+    /// fn main() {
+    ///     POOL.insert(Vec::with_capacity(16));
+    ///     POOL.insert(Vec::with_capacity(4096));
+    ///     assert_eq!(POOL.len(), 1);
+    ///     assert_eq!(POOL.oversized_rejections(), 1);
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn with_max_object_size(mut self, max_object_size: usize) -> Self
+    where
+        T: HeapSize,
+    {
+        self.heap_size = Some(T::heap_size);
+        self.max_object_size = max_object_size;
+        self
+    }
+
+    /// Sets a budget on the total heap size of the idle objects of the pool,
+    /// measured with [`HeapSize`]. Returned objects that would make the idle
+    /// objects exceed the budget are discarded (see [`Pool::on_discard`]).
+    /// This bounds the memory held by pools whose objects vary widely in
+    /// size, where limiting the number of objects is meaningless.
+    ///
+    /// The heap size of each object is measured when it enters the pool (and
+    /// after [`Pool::apply_idle`]).
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// static POOL: Pool<Vec<u8>> = Pool::new(Vec::new).with_max_bytes(1024);
+    ///
+    /// fn main() {
+    ///     POOL.insert(Vec::with_capacity(512));
+    ///     POOL.insert(Vec::with_capacity(1024)); // discarded, over the budget
+    ///     POOL.insert(Vec::with_capacity(256));
+    ///     assert_eq!(POOL.len(), 2);
+    ///     assert_eq!(POOL.idle_bytes(), 768);
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn with_max_bytes(mut self, max_bytes: usize) -> Self
+    where
+        T: HeapSize,
+    {
+        self.heap_size = Some(T::heap_size);
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Returns the total heap size of the idle objects of the pool, as
+    /// measured when they entered the pool. This is always zero unless the
+    /// pool measures its objects (see [`Pool::with_max_bytes`] and
+    /// [`Pool::with_max_object_size`]).
+    #[inline]
+    pub fn idle_bytes(&self) -> usize {
+        self.get_pool().bytes()
+    }
+
+    /// Enables the lock-free return inbox of the pool. Returned objects are
+    /// pushed into the inbox without taking the lock of the pool, and they are
+    /// moved into the pool by the next operation that locks it (like acquiring
+    /// an object or querying its length). This decouples the latency of the
+    /// returns from the contention of the lock in drop-heavy workloads.
+    ///
+    /// The soft limit (see [`Pool::with_soft_limit`]) is not checked for
+    /// objects returned through the inbox.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// static POOL: Pool<Vec<u8>> = Pool::new(Vec::new).with_return_inbox();
+    ///
+    /// fn main() {
+    ///     POOL.insert(Vec::new()); // doesn't lock the pool
+    ///     assert_eq!(POOL.len(), 1);
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn with_return_inbox(mut self) -> Self {
+        self.inbox_enabled = true;
+        self
+    }
+
+    /// Enables the lock-free backend of the pool with the specified number of
+    /// slots. Objects are returned into (and acquired from) a fixed set of
+    /// atomic slots without taking the lock of the pool, which only is locked
+    /// when all the slots are full (on return) or empty (on acquire). This
+    /// removes the lock from the hot path of multi-threaded pipelines that
+    /// acquire and return objects at a high rate.
+    ///
+    /// The objects held in the slots are moved into the pool by the next
+    /// operation that locks it (like querying its length), so up to `slots`
+    /// objects may be held on top of the capacity of the pool (see
+    /// [`Pool::with_capacity`]) until then. The soft limit (see
+    /// [`Pool::with_soft_limit`]) is not checked for objects returned into
+    /// the slots.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// static POOL: Pool<Vec<u8>> = Pool::new(Vec::new).with_lock_free(8);
+    ///
+    /// fn main() {
+    ///     let handle = PoolHandle::from_static(&POOL);
+    ///     let workers: Vec<_> = (0..4)
+    ///         .map(|_| {
+    ///             let handle = handle.clone();
+    ///             std::thread::spawn(move || {
+    ///                 for _ in 0..100 {
+    ///                     handle.acquire().push(1); // doesn't lock the pool
+    ///                 }
+    ///             })
+    ///         })
+    ///         .collect();
+    ///     for worker in workers {
+    ///         worker.join().unwrap();
+    ///     }
+    ///     assert!(POOL.len() <= 4);
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn with_lock_free(mut self, slots: usize) -> Self {
+        self.slots.set_capacity(slots);
+        self
+    }
+
+    /// Sets the function that fills the pool with its initial objects. The
+    /// function is called the first time the pool is locked (like when the
+    /// first object is acquired), so a static pool starts non-empty without
+    /// any runtime initialization at startup. The initial objects beyond the
+    /// capacity of the pool are dropped.
+    ///
+    /// With [`#[pool(const_items = [...])]`](derive@crate::ObjectPool), the
+    /// objects are evaluated at compile time and copied into the pool.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// fn initial_items(pool: &mut Vec<u32>) {
+    ///     pool.extend_from_slice(&[1, 2, 3]);
+    /// }
+    ///
+    /// static POOL: Pool<u32> = Pool::new(|| 0).with_initial_items(initial_items);
+    ///
+    /// fn main() {
+    ///     assert_eq!(POOL.len(), 3);
+    /// }
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn with_initial_items(mut self, initial_items: fn(&mut Vec<T>)) -> Self {
+        self.initial_items = Some(initial_items);
+        self
+    }
+
+    /// Moves the objects of the return inbox into the pool. This happens
+    /// automatically whenever the pool is locked, but it can be called to do
+    /// it eagerly (for example from a maintenance task).
+    #[inline]
+    pub fn drain_inbox(&self) {
+        drop(self.get_pool());
+    }
+
+    /// Returns a locked reference to the pool. This is used internally by the
+    /// rest of the library, but it can also be used to interact with the pool
+    /// directly.
+    #[inline]
+    fn get_pool(&self) -> IdleGuard<'_, T> {
+        // Filled before locking, as the generator may run arbitrary code
+        self.warm();
+        let mut pool = IdleGuard::new(self.lock_idle(), self);
+        if let Some(initial_items) = self.initial_items {
+            // The flag is only written while the lock is held
+            if !self.initialized.load(Ordering::Relaxed) {
+                self.initialized.store(true, Ordering::Relaxed);
+                let mut items = Vec::new();
+                initial_items(&mut items);
+                items.truncate(self.capacity().saturating_sub(pool.len()));
+                pool.extend(items, self.heap_size);
+                for item in pool.split_bottom_bytes(self.max_bytes) {
+                    self.discard(item);
+                }
+            }
+        }
+        if self.inbox_enabled && !self.inbox.is_empty() {
+            // The epoch can't change while the lock is held
+            let epoch = self.epoch();
+            self.inbox.drain(|(item, stamp)| {
+                let stored = match stamp.epoch == epoch {
+                    true => self.push_idle(&mut pool, item, stamp.reuses),
+                    false => Err(item),
+                };
+                if let Err(item) = stored {
+                    self.discard(item);
+                }
+            });
+        }
+        if self.slots.capacity() > 0 {
+            let epoch = self.epoch();
+            self.slots.drain(|(item, stamp)| {
+                let stored = match stamp.epoch == epoch {
+                    true => self.push_idle(&mut pool, item, stamp.reuses),
+                    false => Err(item),
+                };
+                if let Err(item) = stored {
+                    self.discard(item);
+                }
+            });
+        }
+        pool
+    }
+
+    /// Pushes an object into the locked pool, giving it back if it exceeds the
+    /// capacity or the byte budget of the pool.
+    #[inline]
+    fn push_idle(&self, pool: &mut Idle<T>, item: T, reuses: u32) -> Result<(), T> {
+        let bytes = self.heap_size.map_or(0, |heap_size| heap_size(&item));
+        if pool.len() >= self.capacity() || pool.bytes().saturating_add(bytes) > self.max_bytes {
+            return Err(item);
+        }
+        pool.push(item, reuses, bytes);
+        Ok(())
+    }
+
+    /// Pops an object and its reuse count from the lock-free slots, dropping
+    /// the ones that belong to a previous epoch.
+    #[inline]
+    fn pop_slot(&self) -> Option<(T, u32)> {
+        while let Some((item, stamp)) = self.slots.pop() {
+            if stamp.epoch == self.epoch() {
+                return Some((item, stamp.reuses));
+            }
+            self.discard(item);
+        }
+        None
+    }
+
+    /// Pops the next idle object to hand out, stamped as reused once more,
+    /// discarding the ones that fail the validator of the pool.
+    #[inline]
+    fn pop_idle(&self) -> Option<(T, Stamp)> {
+        loop {
+            let (item, reuses) = self.pop_slot().or_else(|| self.get_pool().pop())?;
+            match self.validator {
+                Some(validator) if !validator(&item) => self.invalidated(item),
+                _ => return Some((item, self.stamp(reuses.saturating_add(1)))),
+            }
+        }
+    }
+
+    /// Discards an idle object that failed the validator of the pool.
+    #[inline]
+    fn invalidated(&self, item: T) {
+        #[cfg(feature = "log")]
+        log::debug!(
+            "pool {} discarded an idle object that failed its validator",
+            self.diagnostic_name()
+        );
+        self.discard(item);
+    }
+
+    /// Stamps an object checked out now that was reused the specified number
+    /// of times.
+    #[inline]
+    fn stamp(&self, reuses: u32) -> Stamp {
+        Stamp::new(self.epoch(), reuses)
+    }
+
+    /// Removes an object from the pool or, if the pool is empty, creates a new
+    /// one using the generator function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pool is empty and it has no generator function.
+    #[inline]
+    pub(crate) fn take_or_generate(&self) -> (T, Stamp) {
+        self.try_take_or_generate()
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Removes an object from the pool or, if the pool is empty, creates a new
+    /// one using the generator function. Fails if the pool is empty and it has
+    /// no generator function.
+    pub(crate) fn try_take_or_generate(&self) -> Result<(T, Stamp), AcquireError> {
+        // The lock must be released before calling the generator
+        match self.try_take() {
+            Some(item) => Ok(item),
+            None => self.try_generate(),
+        }
+    }
+
+    /// Removes an idle object from the pool to check it out, if there is one.
+    /// The generator function is never called.
+    pub(crate) fn try_take(&self) -> Option<(T, Stamp)> {
+        let item = self.pop_idle();
+        self.record(match item {
+            Some(_) => Event::Hit,
+            None => Event::Miss,
+        });
+        item.map(|(item, stamp)| (self.checkout(self.reused(item)), stamp))
+    }
+
+    /// Creates a new object using the generator function, storing the extra
+    /// objects of the growth of the pool (see [`Pool::with_growth`]). Fails if
+    /// the pool has no generator function.
+    fn try_generate(&self) -> Result<(T, Stamp), AcquireError> {
+        let item = self.try_generate_one()?;
+        if let Some(generator) = self.generator.filter(|_| self.growth > 1) {
+            self.prefill_with(self.growth - 1, generator);
+        }
+        Ok(item)
+    }
+
+    /// Creates a single new object using the generator function. Fails if the
+    /// pool has no generator function.
+    fn try_generate_one(&self) -> Result<(T, Stamp), AcquireError> {
+        let generator = self
+            .generator
+            .ok_or_else(|| self.acquire_error(AcquireErrorKind::Exhausted))?;
+        #[cfg(feature = "log")]
+        log::debug!(
+            "pool {} ran empty with {} objects checked out, creating a new one",
+            self.diagnostic_name(),
+            self.outstanding()
+        );
+        self.record(Event::Created);
+        Ok((self.checkout(self.created(generator())), self.stamp(0)))
+    }
+
+    /// Removes up to `n` objects from the pool, locking it at most once.
+    /// The objects that fail the validator of the pool are discarded.
+    pub(crate) fn take_n(&self, n: usize) -> Vec<(T, Stamp)> {
+        let mut popped = Vec::with_capacity(n);
+        popped.extend(core::iter::from_fn(|| self.pop_slot()).take(n));
+        if popped.len() < n {
+            let mut pool = self.get_pool();
+            let missing = n - popped.len();
+            popped.extend(core::iter::from_fn(|| pool.pop()).take(missing));
+        }
+        // The lock must be released before calling the hooks
+        let mut items = Vec::with_capacity(popped.len());
+        for (item, reuses) in popped {
+            match self.validator {
+                Some(validator) if !validator(&item) => self.invalidated(item),
+                _ => {
+                    self.record(Event::Hit);
+                    let stamp = self.stamp(reuses.saturating_add(1));
+                    items.push((self.checkout(self.reused(item)), stamp));
+                }
+            }
+        }
+        items
+    }
+
+    /// Removes `n` objects from the pool, creating new ones with the generator
+    /// function if there aren't enough. The pool is locked at most once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pool doesn't have enough objects and it has no generator
+    /// function.
+    pub(crate) fn take_or_generate_n(&self, n: usize) -> impl Iterator<Item = (T, Stamp)> + '_ {
+        let items = self.take_n(n);
+        let missing = n - items.len();
+        items.into_iter().chain((0..missing).map(|_| {
+            self.record(Event::Miss);
+            // The batch already creates every missing object, so it doesn't grow
+            self.try_generate_one()
+                .unwrap_or_else(|err| panic!("{err}"))
+        }))
+    }
+
+    /// Removes an object from the pool or, if the pool is empty, creates a new
+    /// one using the specified fallible generator, propagating its error.
+    pub(crate) fn take_or_try_generate<E>(
+        &self,
+        generator: impl FnOnce() -> Result<T, E>,
+    ) -> Result<(T, Stamp), E> {
+        // The lock must be released before calling the generator
+        let item = self.pop_idle();
+        let (item, stamp) = match item {
+            Some((item, stamp)) => {
+                self.record(Event::Hit);
+                (self.reused(item), stamp)
+            }
+            None => {
+                self.record(Event::Miss);
+                let item = generator()?;
+                self.record(Event::Created);
+                (self.created(item), self.stamp(0))
+            }
+        };
+        Ok((self.checkout(item), stamp))
+    }
+
+    /// Calls the create hook with a newly created object.
+    #[inline]
+    fn created(&self, mut item: T) -> T {
+        if let Some(on_create) = self.on_create {
+            on_create(&mut item);
+        }
+        item
+    }
+
+    /// Calls the reuse hook with an object taken from the pool.
+    #[inline]
+    fn reused(&self, mut item: T) -> T {
+        if let Some(on_reuse) = self.on_reuse {
+            on_reuse(&mut item);
+        }
+        item
+    }
+
+    /// Creates an acquisition error with a snapshot of the pool.
+    #[cold]
+    fn acquire_error(&self, kind: AcquireErrorKind) -> AcquireError {
+        AcquireError::new(
+            kind,
+            self.len(),
+            self.outstanding(),
+            #[cfg(feature = "counters")]
+            self.stats(),
+        )
+    }
+
+    /// Returns `true` if the pool has no generator function, so it can only
+    /// hand out objects that were inserted into it (see [`Pool::reuse_only`]).
+    #[inline]
+    pub const fn is_reuse_only(&self) -> bool {
+        self.generator.is_none()
+    }
+
+    /// Prepares an object to be handed out by the pool, applying the acquire
+    /// middlewares.
+    #[inline]
+    pub(crate) fn checkout(&self, mut item: T) -> T {
+        if let Some(zero) = self.zero {
+            zero(&mut item);
+        }
+        let middlewares = self.middlewares.read();
+        for middleware in middlewares.iter() {
+            middleware(&mut item);
+        }
+        item
+    }
+
+    /// Appends a middleware to the acquire pipeline of the pool. The
+    /// middlewares are applied in order to every object handed out by the pool
+    /// (both reused and newly created ones), right before handing it out. This
+    /// allows implementing cross-cutting concerns (like tagging pooled contexts
+    /// with request IDs) without wrapper types. Objects taken with
+    /// [`Pool::remove`] bypass the pipeline.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// #[derive(Default, ObjectPool)]
+    /// struct Context {
+    ///     checkouts: usize,
+    /// }
+    ///
+    /// fn main() {
+    ///     Context::pool().add_acquire_middleware(|ctx| ctx.checkouts += 1);
+    ///     drop(Context::new());
+    ///     let ctx = Context::new();
+    ///     assert_eq!(ctx.checkouts, 2);
+    ///
+    ///     Context::pool().clear_acquire_middlewares();
+    /// }
+    /// ```
+    pub fn add_acquire_middleware(&self, middleware: fn(&mut T)) {
+        self.middlewares.write().push(middleware);
+    }
+
+    /// Removes all the middlewares of the acquire pipeline of the pool.
+    pub fn clear_acquire_middlewares(&self) {
+        self.middlewares.write().clear();
+    }
+
+    /// Returns the number of objects in the pool. The number is read without
+    /// locking the pool (so it is cheap to call from hot logging or metrics
+    /// code), unless there are objects waiting in the return inbox or initial
+    /// objects to be added, which are moved into the pool first.
+    #[inline]
+    pub fn len(&self) -> usize {
+        if self.has_pending() {
+            drop(self.get_pool());
+        }
+        self.idle_len()
+    }
+
+    /// Returns `true` if the pool is empty. Like [`Pool::len`], this doesn't
+    /// lock the pool.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the pool replacing this one on the current thread (see
+    /// `Pool::scoped`), or this pool otherwise. This is used by the pools
+    /// derived with [`#[derive(ObjectPool)]`](derive@ObjectPool).
+    #[doc(hidden)]
+    #[inline]
+    pub fn current(&'static self) -> &'static Self {
+        #[cfg(feature = "std")]
+        if let Some(pool) = self.scoped_override() {
+            return pool;
+        }
+        self
+    }
+
+    /// Returns the number of objects in the pool and its lock-free slots, as
+    /// published the last time the pool was unlocked.
+    #[inline]
+    fn idle_len(&self) -> usize {
+        self.len.load(Ordering::Relaxed) + self.slots.len()
+    }
+
+    /// Counts objects checked out of the pool, keeping the peak.
+    #[inline]
+    pub(crate) fn count_checkouts(&self, n: usize) {
+        let outstanding = self.outstanding.fetch_add(n, Ordering::Relaxed) + n;
+        self.counters.observe_outstanding(outstanding);
+    }
+
+    /// Returns `true` if there are objects waiting to be moved into the pool
+    /// the next time it is locked.
+    #[inline]
+    fn has_pending(&self) -> bool {
+        (self.inbox_enabled && !self.inbox.is_empty())
+            || (self.initial_items.is_some() && !self.initialized.load(Ordering::Relaxed))
+            || (self.prefill > 0 && !self.prefilled.load(Ordering::Relaxed))
+    }
+
+    /// Inserts an object into the pool while taking ownership of it. If the
+    /// object exceeds the maximum object size of the pool, it is dropped
+    /// instead.
+    #[inline]
+    pub fn insert(&self, item: T) {
+        self.release(item);
+    }
+
+    /// Returns the number of objects whose return to the pool is staged (see
+    /// [`Reusable::stage_return`]).
+    #[inline]
+    pub fn staged(&self) -> usize {
+        self.staged.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of objects currently checked out of the pool that
+    /// were not returned to it yet. The count is incremented when an object
+    /// is acquired and decremented when it is returned (or discarded by the
+    /// pool instead). Objects taken out of their guards (like with
+    /// [`Reusable::into_inner`]) are still live, so they stay counted, unless
+    /// they are detached from the pool (see [`Reusable::detach`]). Along with
+    /// [`Pool::len`], this tells how many live objects of the pool exist.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// #[derive(Default, ObjectPool)]
+    /// struct Connection(u32);
+    ///
+    /// fn main() {
+    ///     let first = Connection::new();
+    ///     let second = Connection::new();
+    ///     assert_eq!(Connection::pool().outstanding(), 2);
+    ///
+    ///     drop(first);
+    ///     assert_eq!(Connection::pool().outstanding(), 1);
+    ///     assert_eq!(Connection::pool().len(), 1);
+    ///     drop(second);
+    /// }
+    /// ```
+    #[inline]
+    pub fn outstanding(&self) -> usize {
+        self.outstanding.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of objects that were dropped instead of being pooled
+    /// for exceeding the maximum object size of the pool.
+    #[inline]
+    pub fn oversized_rejections(&self) -> usize {
+        self.oversized_rejections.load(Ordering::Relaxed)
+    }
+
+    /// Returns a structured snapshot of the state of the pool: its
+    /// configuration, counters and the metadata of each idle object.
+    pub fn debug_state(&self) -> PoolDebugState {
+        let pool = self.get_pool();
+        let items = pool
+            .iter()
+            .map(|(item, reuses)| ItemDebugState {
+                heap_size: self.heap_size.map(|heap_size| heap_size(item)),
+                reuses,
+            })
+            .collect::<Vec<_>>();
+        let idle_bytes = pool.bytes();
+        drop(pool);
+        PoolDebugState {
+            name: self.name(),
+            type_name: core::any::type_name::<T>(),
+            idle: items.len(),
+            max_object_size: (self.max_object_size != usize::MAX).then_some(self.max_object_size),
+            max_bytes: (self.max_bytes != usize::MAX).then_some(self.max_bytes),
+            idle_bytes,
+            reuse_only: self.is_reuse_only(),
+            escape_forbidden: self.escape_forbidden,
+            soft_limit: self.on_soft_limit.map(|_| self.soft_limit),
+            capacity: Some(self.capacity()).filter(|&capacity| capacity != usize::MAX),
+            min_idle: self.min_idle,
+            growth: self.growth,
+            prefill: self.prefill,
+            #[cfg(feature = "std")]
+            idle_timeout: self.idle_timeout,
+            #[cfg(not(feature = "std"))]
+            idle_timeout: None,
+            max_reuses: (self.max_reuses != u32::MAX).then_some(self.max_reuses),
+            max_outstanding: Some(self.max_outstanding.load(Ordering::Relaxed))
+                .filter(|&max_outstanding| max_outstanding != usize::MAX),
+            recycle: self.recycle.is_some(),
+            zero_on_acquire: self.zero.is_some(),
+            lock_free_slots: (self.slots.capacity() > 0).then_some(self.slots.capacity()),
+            staged: self.staged(),
+            oversized_rejections: self.oversized_rejections(),
+            items,
+        }
+    }
+
+    /// Returns the current epoch of the pool, which starts at zero and is
+    /// incremented by each [`Pool::invalidate`].
+    #[inline]
+    pub fn epoch(&self) -> u64 {
+        self.epoch.load(Ordering::Acquire)
+    }
+
+    /// Invalidates all the objects of the pool: the idle objects are discarded
+    /// (see [`Pool::on_discard`]) and the epoch of the pool is incremented, so the objects that are
+    /// currently checked out (stamped with the previous epoch) are dropped
+    /// instead of being returned to the pool. This guarantees that a
+    /// configuration change fully propagates without racing with returns.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// #[derive(Default, ObjectPool)]
+    /// struct Connection(String);
+    ///
+    /// fn main() {
+    ///     let old = Connection::new();
+    ///     Connection::pool().invalidate(); // e.g. the server address changed
+    ///     let new = Connection::new();
+    ///     assert_eq!(new.epoch(), old.epoch() + 1);
+    ///
+    ///     drop(old); // dropped, as it belongs to a previous epoch
+    ///     drop(new);
+    ///     assert_eq!(Connection::pool().len(), 1);
+    /// }
+    /// ```
+    pub fn invalidate(&self) {
+        let stale = {
+            let mut pool = self.get_pool();
+            self.epoch.fetch_add(1, Ordering::AcqRel);
+            pool.take()
+        };
+        for item in stale {
+            self.discard(item);
+        }
+    }
+
+    /// Returns an object checked out with the specified stamp to the pool.
+    /// The object is dropped if the pool was invalidated since then or if it
+    /// reached the maximum number of reuses of the pool.
+    #[inline]
+    pub(crate) fn release_stamped(&self, item: T, stamp: Stamp) -> bool {
+        self.outstanding.fetch_sub(1, Ordering::Relaxed);
+        self.checkouts.untrack(stamp);
+        self.release_at(item, Some(stamp))
+    }
+
+    /// Stops counting an object checked out with the specified stamp as
+    /// checked out, as it was detached from the pool and won't be returned.
+    #[inline]
+    pub(crate) fn detach_stamped(&self, stamp: Stamp) {
+        self.outstanding.fetch_sub(1, Ordering::Relaxed);
+        self.checkouts.untrack(stamp);
+        self.notify_available();
+    }
+
+    /// Returns an object to the pool, applying the storage policies of the
+    /// pool. Returns `true` if the object was pooled or `false` if it was
+    /// dropped.
+    #[inline]
+    pub(crate) fn release(&self, item: T) -> bool {
+        self.release_at(item, None)
+    }
+
+    /// Returns an object to the pool, with the stamp of its checkout if any.
+    #[inline]
+    fn release_at(&self, mut item: T, stamp: Option<Stamp>) -> bool {
+        if let Some(on_return) = self.on_return {
+            on_return(&mut item);
+        }
+        let pooled = match self.store(item, stamp) {
+            Ok(()) => true,
+            Err(item) => {
+                self.discard(item);
+                false
+            }
+        };
+        self.record(match pooled {
+            true => Event::Returned,
+            false => Event::Rejected,
+        });
+        self.notify_available();
+        pooled
+    }
+
+    /// Wakes up a blocking acquisition waiting for an object, if the pool can
+    /// make them wait.
+    #[inline]
+    fn notify_available(&self) {
+        #[cfg(feature = "std")]
+        if self.max_outstanding() != usize::MAX || self.generator.is_none() {
+            self.wake_waiters();
+        }
+    }
+
+    /// Stores a returned object in the pool, giving it back if it is
+    /// rejected. If a stamp is specified, the object is rejected unless it
+    /// matches the current epoch of the pool, which is checked while holding
+    /// the lock so it can't race with an invalidation.
+    fn store(&self, item: T, stamp: Option<Stamp>) -> Result<(), T> {
+        let mut item = self.recycled(item, stamp)?;
+        if self.slots.capacity() > 0 {
+            let stamp = stamp.unwrap_or_else(|| self.stamp(0));
+            if stamp.epoch != self.epoch() {
+                return Err(item);
+            }
+            item = match self.slots.push((item, stamp)) {
+                Ok(()) => {
+                    self.counters.observe_idle(self.idle_len());
+                    return Ok(());
+                }
+                Err((item, _)) => item,
+            };
+        }
+        if self.inbox_enabled {
+            let stamp = stamp.unwrap_or_else(|| self.stamp(0));
+            self.inbox.push((item, stamp));
+            return Ok(());
+        }
+        let len = {
+            let mut pool = self.get_pool();
+            if stamp.is_some_and(|stamp| stamp.epoch != self.epoch()) {
+                return Err(item);
+            }
+            self.push_idle(&mut pool, item, stamp.map_or(0, |stamp| stamp.reuses))?;
+            pool.len()
+        };
+        if len == self.soft_limit.wrapping_add(1) {
+            self.exceeded_soft_limit(len);
+        }
+        Ok(())
+    }
+
+    /// Reports that the number of idle objects of the pool went over its soft
+    /// limit.
+    #[inline]
+    fn exceeded_soft_limit(&self, len: usize) {
+        if let Some(callback) = self.on_soft_limit {
+            #[cfg(feature = "log")]
+            log::warn!(
+                "pool {} exceeded its soft limit of {} idle objects ({len} idle)",
+                self.diagnostic_name(),
+                self.soft_limit
+            );
+            callback(len);
+        }
+    }
+
+    /// Recycles a returned object, giving it back if it is rejected by the
+    /// maximum object size or the maximum number of reuses of the pool.
+    #[inline]
+    fn recycled(&self, mut item: T, stamp: Option<Stamp>) -> Result<T, T> {
+        if !self.admits(&item) || stamp.is_some_and(|stamp| stamp.reuses >= self.max_reuses) {
+            return Err(item);
+        }
+        if let Some(recycle) = self.recycle {
+            recycle(&mut item);
+        }
+        Ok(item)
+    }
+
+    /// Returns a batch of objects checked out with the specified stamps to the
+    /// pool under one lock, applying the same policies as
+    /// [`Pool::release_stamped`]. Returns the number of pooled objects.
+    pub(crate) fn release_batch(&self, items: Vec<(T, Stamp)>) -> usize {
+        self.outstanding.fetch_sub(items.len(), Ordering::Relaxed);
+        let mut rejected = Vec::new();
+        let mut recycled = Vec::with_capacity(items.len());
+        for (mut item, stamp) in items {
+            self.checkouts.untrack(stamp);
+            if let Some(on_return) = self.on_return {
+                on_return(&mut item);
+            }
+            match self.recycled(item, Some(stamp)) {
+                Ok(item) => recycled.push((item, stamp)),
+                Err(item) => rejected.push(item),
+            }
+        }
+        let (before, after) = {
+            let mut pool = self.get_pool();
+            let before = pool.len();
+            for (item, stamp) in recycled {
+                let pushed = match stamp.epoch == self.epoch() {
+                    true => self.push_idle(&mut pool, item, stamp.reuses),
+                    false => Err(item),
+                };
+                if let Err(item) = pushed {
+                    rejected.push(item);
+                }
+            }
+            (before, pool.len())
+        };
+        if before <= self.soft_limit && after > self.soft_limit {
+            self.exceeded_soft_limit(after);
+        }
+        let pooled = after - before;
+        (0..pooled).for_each(|_| self.record(Event::Returned));
+        for item in rejected {
+            self.discard(item);
+            self.record(Event::Rejected);
+        }
+        #[cfg(feature = "std")]
+        if self.max_outstanding() != usize::MAX || self.generator.is_none() {
+            self.wake_waiters();
+        }
+        pooled
+    }
+
+    /// Records an event in the enabled counters of the pool. This compiles to
+    /// nothing if no counters are enabled.
+    #[inline(always)]
+    fn record(&self, event: Event) {
+        self.counters.record(event);
+        #[cfg(feature = "introspection")]
+        if let Some(entry) = self.introspection() {
+            entry.record(event);
+        }
+        #[cfg(feature = "tracing")]
+        tracing::trace!(
+            pool = self.diagnostic_name(),
+            event = event.name(),
+            "pool event"
+        );
+    }
+
+    /// Locks the idle objects of the pool, reporting the contention on the
+    /// lock with the `tracing` feature.
+    #[inline]
+    fn lock_idle(&self) -> sync::MutexGuard<'_, Idle<T>> {
+        #[cfg(feature = "tracing")]
+        if let Some(pool) = self.pool.try_lock() {
+            return pool;
+        } else {
+            tracing::debug!(pool = self.diagnostic_name(), "pool lock contended");
+        }
+        self.pool.lock()
+    }
+
+    /// Returns a snapshot of the statistics of the pool (see [`Stats`]).
+    #[cfg(feature = "counters")]
+    #[inline]
+    pub fn stats(&self) -> Stats {
+        self.counters.snapshot(self.name())
+    }
+
+    /// Returns the objects checked out of the pool for longer than the
+    /// threshold, the oldest first, with their type and the label of their
+    /// checkout (see [`Reusable::labeled`]). Pooled objects that never come
+    /// back are effectively leaks, so calling this periodically (or at
+    /// shutdown) finds them. Objects taken out of their guards (like with
+    /// [`Reusable::into_inner`]) are no longer tracked. With the
+    /// `debug-tracking` feature, each object also tells where it was acquired
+    /// (see [`Overdue`]).
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// #[derive(Default, ObjectPool)]
+    /// struct Connection(u16);
+    ///
+    /// fn main() {
+    ///     let forgotten = Connection::new().labeled("handshake");
+    ///     std::thread::sleep(Duration::from_millis(5));
+    ///     let fresh = Connection::new();
+    ///
+    ///     let overdue = Connection::pool().report_overdue(Duration::from_millis(1));
+    ///     assert_eq!(overdue.len(), 1);
+    ///     assert_eq!(overdue[0].label, Some("handshake"));
+    ///     drop((forgotten, fresh));
+    ///     assert!(Connection::pool().report_overdue(Duration::ZERO).is_empty());
+    /// }
+    /// ```
+    #[cfg(feature = "leak-detection")]
+    pub fn report_overdue(&self, threshold: Duration) -> Vec<Overdue> {
+        self.checkouts
+            .overdue(threshold, core::any::type_name::<T>())
+    }
+
+    /// Returns the entry of the pool in the introspection table, registering
+    /// the pool (at its current address) if it is not registered yet.
+    #[cfg(feature = "introspection")]
+    fn introspection(&self) -> Option<&'static IntrospectionEntry> {
+        *self.introspection.get_or_init(|| {
+            introspection_table()
+                .register((self as *const Self).cast(), core::any::type_name::<T>())
+        })
+    }
+
+    /// Returns `true` if the object can be stored in the pool according to its
+    /// maximum object size, counting the rejection otherwise.
+    fn admits(&self, item: &T) -> bool {
+        if let Some(heap_size) = self.heap_size {
+            let size = heap_size(item);
+            if size > self.max_object_size {
+                #[cfg(feature = "log")]
+                log::debug!(
+                    "pool {} rejected a returned object of {size} bytes, over its maximum object size of {} bytes",
+                    self.diagnostic_name(),
+                    self.max_object_size
+                );
+                self.oversized_rejections.fetch_add(1, Ordering::Relaxed);
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Freezes the pool, returning a read-only view of its idle objects that
+    /// can be shared between threads (for example to checksum or serialize
+    /// the contents of the pool). See [`FrozenPool`] for more details.
+    #[inline]
+    pub fn freeze(&self) -> FrozenPool<'_, T> {
+        FrozenPool::new(self.get_pool())
+    }
+
+    /// Locks the pool, returning a guard that allows performing several
+    /// operations on the idle objects atomically. See [`PoolGuard`] for more
+    /// details.
+    #[inline]
+    pub fn lock(&self) -> PoolGuard<'_, T> {
+        PoolGuard::new(self, self.get_pool())
+    }
+
+    /// Calls the closure with each idle object of the pool, in insertion
+    /// order, while holding the lock. This is useful for diagnostics and
+    /// metrics over the pooled objects. Acquiring or returning objects of the
+    /// same pool from the closure deadlocks, see [`Pool::freeze`] for a view
+    /// that can be shared with other threads.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// fn main() {
+    ///     let pool = Pool::new(Vec::<u8>::new);
+    ///     pool.insert(Vec::with_capacity(16));
+    ///     pool.insert(Vec::with_capacity(32));
+    ///
+    ///     let mut capacity = 0;
+    ///     pool.for_each_idle(|buffer| capacity += buffer.capacity());
+    ///     assert!(capacity >= 48);
+    /// }
+    /// ```
+    #[inline]
+    pub fn for_each_idle(&self, f: impl FnMut(&T)) {
+        self.get_pool().items().iter().for_each(f);
+    }
+
+    /// Calls the closure with each idle object of the pool mutably, in
+    /// insertion order, while holding the lock. This allows updating the
+    /// pooled objects in place without taking them out of circulation (for
+    /// example to release the memory of idle buffers under memory pressure).
+    /// Like [`Pool::for_each_idle`], using the same pool from the closure
+    /// deadlocks.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// fn main() {
+    ///     let pool = Pool::new(Vec::<u8>::new);
+    ///     pool.insert(Vec::with_capacity(1024));
+    ///     pool.apply_idle(Vec::shrink_to_fit);
+    ///     assert_eq!(pool.remove().unwrap().capacity(), 0);
+    /// }
+    /// ```
+    #[inline]
+    pub fn apply_idle(&self, f: impl FnMut(&mut T)) {
+        self.get_pool().apply(f, self.heap_size);
+    }
+
+    /// Removes all objects from the pool. The objects are dropped in the order
+    /// they were inserted (see [`DropOrder::Fifo`]).
+    #[inline]
+    pub fn clear(&self) {
+        self.get_pool().clear();
+    }
+
+    /// Retains only the idle objects for which the predicate returns `true`,
+    /// keeping their order. The other objects are discarded (see
+    /// [`Pool::on_discard`]) after releasing the lock. Returns the number of
+    /// discarded objects.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// fn main() {
+    ///     let pool = Pool::new(Vec::<u8>::new);
+    ///     pool.insert(Vec::with_capacity(16));
+    ///     pool.insert(Vec::with_capacity(1 << 20));
+    ///
+    ///     // Purge the buffers that grew too big
+    ///     assert_eq!(pool.retain(|buffer| buffer.capacity() <= 1024), 1);
+    ///     assert_eq!(pool.len(), 1);
+    /// }
+    /// ```
+    pub fn retain(&self, mut f: impl FnMut(&T) -> bool) -> usize {
+        let removed = self.get_pool().remove_where(|item| !f(item));
+        let count = removed.len();
+        for item in removed {
+            self.discard(item);
+        }
+        count
+    }
+
+    /// Removes all objects from the pool and drops them in the specified
+    /// order. This matters when the pooled objects hold dependent external
+    /// resources that must be torn down in sequence. The objects are dropped
+    /// after releasing the lock.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    /// use derivable_object_pool::DropOrder;
+    ///
+    /// fn main() {
+    ///     let pool = Pool::new(Vec::<u8>::new);
+    ///     pool.insert(vec![1]);
+    ///     pool.insert(vec![2]);
+    ///     pool.clear_ordered(DropOrder::Lifo); // drops [2] and then [1]
+    ///     assert!(pool.is_empty());
+    /// }
+    /// ```
+    pub fn clear_ordered(&self, order: DropOrder) {
+        order.drop_all(self.take_all());
+    }
+
+    /// Removes all objects from the pool and returns them while taking
+    /// ownership of them. The objects are returned in the order they were
+    /// inserted, so the last one is the next object the pool would have
+    /// handed out.
+    #[inline]
+    pub fn take_all(&self) -> Vec<T> {
+        self.get_pool().take()
+    }
+
+    /// Removes all objects from the pool under one lock and returns an owning
+    /// iterator over them, in the order of [`Pool::take_all`]. The lock is
+    /// released before returning, so the objects can be torn down (or moved
+    /// to another pool) while the pool keeps being used.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// fn main() {
+    ///     let pool = Pool::new(String::new);
+    ///     pool.insert(String::from("a"));
+    ///     pool.insert(String::from("b"));
+    ///
+    ///     let closed: Vec<_> = pool.drain().map(|conn| conn + " closed").collect();
+    ///     assert_eq!(closed, ["a closed", "b closed"]);
+    ///     assert!(pool.is_empty());
+    /// }
+    /// ```
+    #[inline]
+    pub fn drain(&self) -> alloc::vec::IntoIter<T> {
+        self.take_all().into_iter()
+    }
+
+    /// Removes all objects from the pool and returns them as a parallel
+    /// iterator (requires the `rayon` feature). This allows parallelizing
+    /// expensive teardown of the objects (flushing, closing, checksumming)
+    /// during shutdown. The lock is released before returning.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    /// use rayon::prelude::*;
+    ///
+    /// fn main() {
+    ///     let pool = Pool::new(Vec::<u8>::new);
+    ///     for i in 0..10 {
+    ///         pool.insert(vec![i; 100]);
+    ///     }
+    ///     let checksum: usize = pool
+    ///         .par_drain()
+    ///         .map(|buf| buf.iter().map(|&b| b as usize).sum::<usize>())
+    ///         .sum();
+    ///     assert_eq!(checksum, 4500);
+    ///     assert!(pool.is_empty());
+    /// }
+    /// ```
+    #[cfg(feature = "rayon")]
+    #[inline]
+    pub fn par_drain(&self) -> rayon::vec::IntoIter<T>
+    where
+        T: Send,
+    {
+        use rayon::iter::IntoParallelIterator;
+
+        self.take_all().into_par_iter()
+    }
+
+    /// Inserts a batch of objects (for example restored from a persisted
+    /// snapshot) into the pool under one lock. The order specifies whether the
+    /// restored objects will be handed out before or after the objects already
+    /// stored in the pool, so warm-restart scenarios can control which objects
+    /// get used first. Objects exceeding the maximum object size of the pool
+    /// are discarded (see [`Pool::on_discard`]), as are the objects that would
+    /// be handed out last if the capacity of the pool is exceeded.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    /// use derivable_object_pool::RestoreOrder;
+    ///
+    /// fn main() {
+    ///     let pool = Pool::new(String::new);
+    ///     pool.insert(String::from("existing"));
+    ///     pool.restore_with_order([String::from("restored")], RestoreOrder::ReuseLast);
+    ///     assert_eq!(pool.remove().unwrap(), "existing");
+    ///     assert_eq!(pool.remove().unwrap(), "restored");
+    /// }
+    /// ```
+    pub fn restore_with_order<I>(&self, items: I, order: RestoreOrder)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        // Collected before locking, as the iterator may run arbitrary code
+        let mut admitted = Vec::new();
+        for item in items {
+            match self.admits(&item) {
+                true => admitted.push(item),
+                false => self.discard(item),
+            }
+        }
+        let items = admitted;
+        let mut pool = self.get_pool();
+        match order {
+            RestoreOrder::ReuseFirst => pool.extend(items, self.heap_size),
+            RestoreOrder::ReuseLast => pool.extend_below(items, self.heap_size),
+        }
+        // The objects that would be handed out last are dropped over capacity
+        let excess = pool.len().saturating_sub(self.capacity());
+        let mut dropped = pool.split_bottom(excess);
+        dropped.append(&mut pool.split_bottom_bytes(self.max_bytes));
+        drop(pool);
+        for item in dropped {
+            self.discard(item);
+        }
+    }
+
+    /// Eagerly creates `n` objects with the generator function of the pool and
+    /// stores them, so latency-sensitive services can warm their pools during
+    /// boot instead of paying the construction cost on the first requests. The
+    /// objects are created before locking the pool, and the ones exceeding the
+    /// capacity of the pool are dropped. Does nothing for reuse-only pools.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// #[derive(Default, ObjectPool)]
+    /// struct Buffer(Vec<u8>);
+    ///
+    /// fn main() {
+    ///     Buffer::pool().prefill(8);
+    ///     assert_eq!(Buffer::pool().len(), 8);
+    /// }
+    /// ```
+    #[inline]
+    pub fn prefill(&self, n: usize) {
+        if let Some(generator) = self.generator {
+            self.prefill_with(n, generator);
+        }
+    }
+
+    /// Eagerly creates `n` objects with the specified function and stores
+    /// them (see [`Pool::prefill`]).
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// fn main() {
+    ///     let pool = Pool::new(Vec::<u8>::new);
+    ///     pool.prefill_with(4, || Vec::with_capacity(1024));
+    ///     assert!(pool.remove().unwrap().capacity() >= 1024);
+    /// }
+    /// ```
+    #[inline]
+    pub fn prefill_with(&self, n: usize, mut f: impl FnMut() -> T) {
+        let items = core::iter::repeat_with(|| {
+            self.record(Event::Prefilled);
+            self.created(f())
+        })
+        .take(n);
+        self.restore_with_order(items, RestoreOrder::ReuseFirst);
+    }
+
+    /// Moves up to `n` idle objects to another pool, converting them with
+    /// [`From`]. This allows reusing allocations across the stages of a
+    /// pipeline (e.g. decoded frames to raw buffers) when rebalancing pools.
+    /// The objects that would be handed out next are moved first, and they are
+    /// inserted into the other pool under one lock (see
+    /// [`Pool::restore_with_order`]). Returns the number of moved objects.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// struct Frame(Vec<u8>);
+    ///
+    /// impl From<Frame> for Vec<u8> {
+    ///     fn from(frame: Frame) -> Self {
+    ///         frame.0
+    ///     }
+    /// }
+    ///
+    /// fn main() {
+    ///     let frames = Pool::new(|| Frame(Vec::new()));
+    ///     let buffers = Pool::new(Vec::<u8>::new);
+    ///     for _ in 0..3 {
+    ///         frames.insert(Frame(Vec::with_capacity(1024)));
+    ///     }
+    ///     assert_eq!(frames.transfer_to(&buffers, 2), 2);
+    ///     assert_eq!(frames.len(), 1);
+    ///     assert_eq!(buffers.len(), 2);
+    /// }
+    /// ```
+    pub fn transfer_to<U: From<T>>(&self, other: &Pool<U>, n: usize) -> usize {
+        let items = self.get_pool().split_top(n);
+        let moved = items.len();
+        // Converted outside of the locks, keeping the reuse order
+        other.restore_with_order(items.into_iter().map(U::from), RestoreOrder::ReuseFirst);
+        moved
+    }
+
+    /// Removes an object from the pool and returns the object while taking
+    /// ownership of it.
+    #[inline]
+    pub fn remove(&self) -> Option<T> {
+        self.get_pool().pop().map(|(item, _)| item)
+    }
+
+    /// Removes an object from the pool to check it out, stamped as reused
+    /// once more.
+    #[inline]
+    pub(crate) fn remove_stamped(&self) -> Option<(T, Stamp)> {
+        let (item, reuses) = self.get_pool().pop()?;
+        Some((self.checkout(item), self.stamp(reuses.saturating_add(1))))
+    }
+
+    /// Drops the idle objects of the pool above its floor of idle objects
+    /// (see [`Pool::with_min_idle`]), starting with the ones that would be
+    /// handed out last. The objects are discarded (see [`Pool::on_discard`])
+    /// after releasing the lock. Returns the number of dropped objects.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// fn main() {
+    ///     let pool = Pool::new(Vec::<u8>::new).with_min_idle(2);
+    ///     pool.prefill(5);
+    ///     assert_eq!(pool.trim(), 3);
+    ///     assert_eq!(pool.len(), 2);
+    /// }
+    /// ```
+    pub fn trim(&self) -> usize {
+        let trimmed = {
+            let mut pool = self.get_pool();
+            let excess = pool.len().saturating_sub(self.min_idle);
+            pool.split_bottom(excess)
+        };
+        let count = trimmed.len();
+        for item in trimmed {
+            self.discard(item);
+        }
+        count
+    }
+
+    /// Discards (see [`Pool::on_discard`]) up to `max_items` objects from the
+    /// pool, starting with the ones that would be handed out last like
+    /// [`Pool::trim`], and stopping early once `max_duration` has elapsed.
+    /// Returns the number of objects that were dropped.
+    ///
+    /// Unlike [`Pool::clear`], the lock is only held to remove each object and
+    /// never while dropping it, so acquiring objects from other threads is not
+    /// blocked during the trimming. This allows amortizing the cleanup of large
+    /// pools over several calls without latency spikes.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// fn main() {
+    ///     let pool = Pool::new(Vec::<u8>::new);
+    ///     for _ in 0..10 {
+    ///         pool.insert(Vec::new());
+    ///     }
+    ///     assert_eq!(pool.trim_some(4, Duration::from_secs(1)), 4);
+    ///     assert_eq!(pool.len(), 6);
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn trim_some(&self, max_items: usize, max_duration: Duration) -> usize {
+        let start = Instant::now();
+        let mut trimmed = 0;
+        while trimmed < max_items && start.elapsed() < max_duration {
+            // The guard is a temporary, so the lock is released before dropping
+            let Some(item) = self.get_pool().split_bottom(1).pop() else {
+                break;
+            };
+            self.discard(item);
+            trimmed += 1;
+        }
+        trimmed
+    }
+
+    /// Runs a startup self-check of the pool configuration. A new object is
+    /// created with the generator function and round-tripped through the pool
+    /// (returned through its recycling and storage policies, and acquired
+    /// again through its validator), panicking with a descriptive message if
+    /// any step misbehaves. This catches misconfigured pools at startup rather
+    /// than in the middle of the traffic. The object is left in the pool.
+    ///
+    /// The check only runs when debug assertions are enabled, in release
+    /// builds this function does nothing.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// #[derive(Default, ObjectPool)]
+    /// struct Test(i32);
+    ///
+    /// fn main() {
+    ///     Test::pool().debug_self_check();
+    ///     # #[cfg(debug_assertions)]
+    ///     assert_eq!(Test::pool().len(), 1);
+    /// }
+    /// ```
+    #[inline]
+    pub fn debug_self_check(&self) {
+        if cfg!(debug_assertions) {
+            self.self_check();
+        }
+    }
+
+    /// Performs the checks of [`Pool::debug_self_check`].
+    fn self_check(&self) {
+        // Reuse-only pools can only check the objects they already have
+        let Some(item) = self
+            .generator
+            .map(|generator| generator())
+            .or_else(|| self.remove())
+        else {
+            return;
+        };
+        // The object goes through the policies applied to returned objects
+        let idle = self.len();
+        assert!(
+            self.release(item),
+            "pool self-check: returned object was dropped instead of being pooled"
+        );
+        // and is acquired again through the validator of the pool
+        let (item, _) = self
+            .try_take()
+            .expect("pool self-check: returned object could not be acquired again");
+        assert_eq!(
+            self.len(),
+            idle,
+            "pool self-check: returned object failed the validator of the pool"
+        );
+        self.insert(item);
+    }
+
+    /// Round-trips an object through the pool for the unit test generated by
+    /// `#[pool(test_roundtrip)]`: an object is acquired, dirtied through its
+    /// guard with `mutate`, returned and acquired again, panicking unless the
+    /// same object is handed out again, passes the validator of the pool and
+    /// is reset (according to `is_reset`) to a new object. With the `std`
+    /// feature, the test runs on a scoped pool so it doesn't race with other
+    /// tests.
+    #[doc(hidden)]
+    pub fn roundtrip_test(&'static self, mutate: Option<fn(&mut T)>, is_reset: fn(&T, &T) -> bool)
+    where
+        T: ObjectPool,
+    {
+        #[cfg(feature = "std")]
+        self.scoped(|| T::pool().roundtrip(mutate, is_reset));
+        #[cfg(not(feature = "std"))]
+        self.roundtrip(mutate, is_reset);
+    }
+
+    /// Performs the round-trip of [`Pool::roundtrip_test`].
+    fn roundtrip(&self, mutate: Option<fn(&mut T)>, is_reset: fn(&T, &T) -> bool)
+    where
+        T: ObjectPool,
+    {
+        self.debug_self_check();
+        let mut item = T::new();
+        let reuses = item.reuses();
+        if let Some(mutate) = mutate {
+            mutate(&mut item);
+        }
+        drop(item);
+
+        let item = T::new();
+        assert_eq!(
+            item.reuses(),
+            reuses + 1,
+            "pool round-trip: returned object was not handed out again"
+        );
+        if let Some(validator) = self.validator {
+            assert!(
+                validator(&item),
+                "pool round-trip: returned object fails the validator"
+            );
+        }
+        if let (Some(_), Some(generator)) = (mutate, self.generator) {
+            assert!(
+                is_reset(&item, &generator()),
+                "pool round-trip: returned object was not reset like a new object"
+            );
+        }
+    }
+}
+
+/// The order in which the blocking acquisitions waiting on a pool are served
+/// (see [`Pool::with_fairness`]).
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Fairness {
+    /// The waiting acquisitions race for the returned objects, so the
+    /// operating system picks which one is served. This has the highest
+    /// throughput.
+    #[default]
+    Unfair,
+    /// The waiting acquisitions are served in arrival order (first in, first
+    /// out), which keeps the tail latencies predictable when the pool is
+    /// saturated.
+    Fifo,
+}
+
+/// The order in which objects are destroyed by bulk drops like
+/// [`Pool::clear_ordered`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DropOrder {
+    /// The objects are dropped in the order they were inserted in the pool
+    /// (first in, first out).
+    #[default]
+    Fifo,
+    /// The objects are dropped in the reverse order they were inserted in the
+    /// pool (last in, first out).
+    Lifo,
+}
+
+/// The position in the reuse order of a batch of objects inserted with
+/// [`Pool::restore_with_order`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RestoreOrder {
+    /// The restored objects are handed out before the objects already stored
+    /// in the pool.
+    #[default]
+    ReuseFirst,
+    /// The restored objects are handed out after the objects already stored in
+    /// the pool.
+    ReuseLast,
+}
+
+impl DropOrder {
+    /// Drops the objects in this order. The objects must be sorted in
+    /// insertion order.
+    pub fn drop_all<T>(self, mut items: Vec<T>) {
+        match self {
+            Self::Fifo => drop(items),
+            Self::Lifo => while items.pop().is_some() {},
         }
     }
 }
 
-/// A pool of objects that can be reused. This is useful for objects that are
-/// expensive to create, but are used frequently. This struct can be created
-/// using the [`Pool::new`] function. However, it is highly recommended that
-/// you use the [`ObjectPool`] trait instead, as it is much easier to use.
-///
+impl<T> Extend<T> for Pool<T> {
+    /// Inserts the objects into the pool under one lock (see
+    /// [`Pool::restore_with_order`]).
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        (&*self).extend(items);
+    }
+}
+
+/// Allows bulk-loading pools that are only accessible through a shared
+/// reference, like the ones stored in statics.
 ///
 /// # Example
-///
-/// Example without deriving [`ObjectPool`]:
-///
 /// ```
 /// use derivable_object_pool::prelude::*;
 ///
-/// #[derive(Default)]
-/// struct Test;
-///
-/// static POOL: Pool<Test> = Pool::new(Test::default);
-///
-/// impl ObjectPool for Test {
-///     fn pool<'a>() -> &'a Pool<Self> {
-///         &POOL
-///     }
-/// }
+/// static POOL: Pool<Vec<u8>> = Pool::new(Vec::new);
 ///
 /// fn main() {
-///    let obj = Test::new();
-///    drop(obj); // obj is returned to the pool
-///    assert_eq!(POOL.len(), 1);
+///     (&POOL).extend((0..4).map(|_| Vec::with_capacity(1024)));
+///     assert_eq!(POOL.len(), 4);
 /// }
 /// ```
-pub struct Pool<T> {
-    /// The pool of objects that can be reused. The pool uses a [`Mutex`] to
-    /// ensure that it is thread-safe.
-    pool: Mutex<Vec<T>>,
-    /// The generator function that is used to create new objects.
-    generator: fn() -> T,
-}
-
-impl<T> Pool<T> {
-    /// Creates a new pool of objects. The pool will use the specified generator
-    /// function to create new objects.
-    #[must_use]
-    #[inline]
-    pub const fn new(generator: fn() -> T) -> Self {
-        Self {
-            pool: Mutex::new(Vec::new()),
-            generator,
-        }
-    }
-
-    /// Returns a locked reference to the pool. This is used internally by the
-    /// rest of the library, but it can also be used to interact with the pool
-    /// directly.
-    #[inline]
-    fn get_pool(&self) -> MutexGuard<'_, Vec<T>> {
-        self.pool.lock().unwrap()
-    }
-
-    /// Returns the number of objects in the pool.
-    #[inline]
-    pub fn len(&self) -> usize {
-        self.get_pool().len()
-    }
-
-    /// Returns `true` if the pool is empty.
-    #[inline]
-    pub fn is_empty(&self) -> bool {
-        self.get_pool().is_empty()
-    }
-
-    /// Inserts an object into the pool while taking ownership of it.
+impl<T> Extend<T> for &Pool<T> {
     #[inline]
-    pub fn insert(&self, item: T) {
-        self.get_pool().push(item);
+    fn extend<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        self.restore_with_order(items, RestoreOrder::ReuseFirst);
     }
+}
 
-    /// Removes all objects from the pool.
+/// Collects the objects into a new pool that uses [`Default`] to create new
+/// objects.
+impl<T: Default> FromIterator<T> for Pool<T> {
     #[inline]
-    pub fn clear(&self) {
-        self.get_pool().clear();
+    fn from_iter<I: IntoIterator<Item = T>>(items: I) -> Self {
+        Self::from_iter(T::default, items)
     }
+}
 
-    /// Removes an object from the pool and returns the object while taking
-    /// ownership of it.
-    #[inline]
-    pub fn remove(&self) -> Option<T> {
-        self.get_pool().pop()
+#[cfg(feature = "introspection")]
+impl<T> Drop for Pool<T> {
+    fn drop(&mut self) {
+        if let Some(Some(entry)) = self.introspection.get() {
+            entry.unregister();
+        }
     }
 }
 
@@ -253,7 +3071,33 @@ impl<T: ObjectPool> Pool<T> {
     /// which will return the object to the pool when it is dropped.
     #[inline]
     pub fn remove_reusable(&self) -> Option<Reusable<T>> {
-        self.remove().map(Reusable::new)
+        self.remove_stamped().map(Reusable::checked_out)
+    }
+
+    /// Acquires an idle object from the pool like [`ObjectPool::new`], but
+    /// returns `None` instead of creating a new object if the pool is empty.
+    /// The generator function of the pool is never called, so callers can take
+    /// a cheaper path when there is no object to reuse.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// #[derive(Default, ObjectPool)]
+    /// struct Buffer(Vec<u8>);
+    ///
+    /// fn main() {
+    ///     assert!(Buffer::pool().try_acquire().is_none());
+    ///
+    ///     drop(Buffer::new());
+    ///     let buffer = Buffer::pool().try_acquire().unwrap();
+    ///     assert_eq!(Buffer::pool().len(), 0);
+    ///     drop(buffer);
+    /// }
+    /// ```
+    #[inline]
+    pub fn try_acquire(&self) -> Option<Reusable<T>> {
+        self.try_take().map(Reusable::checked_out)
     }
 }
 
@@ -289,29 +3133,284 @@ impl<T: ObjectPool> Pool<T> {
 ///    assert_eq!(obj.0, 1);
 /// }
 /// ```
-#[repr(transparent)]
 pub struct Reusable<T: ObjectPool> {
     /// The wrapped object. This is a `ManuallyDrop` to ensure that the object
     /// is not dropped when the wrapper is dropped.
     item: ManuallyDrop<T>,
+    /// The bookkeeping of the checkout of the object.
+    stamp: Stamp,
 }
 
 impl<T: ObjectPool> Reusable<T> {
-    /// Creates a new reusable wrapper for the specified object.
+    /// Creates a new reusable wrapper for the specified object, stamped as a
+    /// new object of the current epoch of the pool.
     #[inline]
-    const fn new(item: T) -> Self {
+    fn new(item: T) -> Self {
+        Self::checked_out((item, T::pool().stamp(0)))
+    }
+
+    /// Creates a new reusable wrapper for an object checked out from the pool
+    /// with the specified stamp. The wrapper returns the object to the pool
+    /// it was checked out from, even if that pool is replaced meanwhile.
+    #[inline]
+    pub(crate) fn checked_out((item, mut stamp): (T, Stamp)) -> Self {
+        let pool = T::pool();
+        pool.count_checkouts(1);
+        pool.checkouts.track(&mut stamp);
+        stamp.bind(pool);
+        Self::stamped(item, stamp)
+    }
+
+    /// Creates a new reusable wrapper for an object checked out from the pool
+    /// of the objects (as returned by [`ObjectPool::pool`]) with the specified
+    /// stamp, which is already counted as outstanding.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub(crate) fn reserved(pool: &Pool<T>, (item, mut stamp): (T, Stamp)) -> Self {
+        pool.checkouts.track(&mut stamp);
+        stamp.bind(pool);
+        Self::stamped(item, stamp)
+    }
+
+    /// Creates a new reusable wrapper for the specified object, with the
+    /// specified stamp.
+    #[inline]
+    const fn stamped(item: T, stamp: Stamp) -> Self {
         Self {
             item: ManuallyDrop::new(item),
+            stamp,
         }
     }
 
+    /// Returns the epoch of the pool when the object was checked out (see
+    /// [`Pool::invalidate`]).
+    #[inline]
+    pub const fn epoch(&self) -> u64 {
+        self.stamp.epoch
+    }
+
+    /// Returns the number of times the object was handed out again by the
+    /// pool before this checkout (see [`Pool::with_max_reuses`]).
+    #[inline]
+    pub const fn reuses(&self) -> u32 {
+        self.stamp.reuses
+    }
+
     /// Returns the owned object inside the wrapper. This will return the object
     /// without returning it to the pool. This is useful if you want to take
     /// ownership of the object.
-    pub fn into_inner(mut self) -> T {
-        let ret = unsafe { ManuallyDrop::take(&mut self.item) };
+    pub fn into_inner(self) -> T {
+        let (item, stamp) = self.into_parts();
+        stamp.owner::<T>().checkouts.untrack(stamp);
+        item
+    }
+
+    /// Takes the object and the stamp of its checkout out of the wrapper,
+    /// without returning the object to the pool.
+    #[inline]
+    pub(crate) fn into_parts(mut self) -> (T, Stamp) {
+        let item = unsafe { ManuallyDrop::take(&mut self.item) };
+        let stamp = self.stamp;
         forget(self);
-        ret
+        (item, stamp)
+    }
+
+    /// Takes the object out of the wrapper, replacing it with a new object
+    /// acquired from the pool (reusing an idle one if possible, like
+    /// [`ObjectPool::new`]). The wrapper stays usable and returns the new
+    /// object to the pool when dropped, while the old one is handed back
+    /// without being returned to the pool.
+    ///
+    /// This is an associated function that needs to be used as
+    /// `Reusable::take(...)`, so it doesn't shadow a method of the object.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pool has no idle objects and can't create new ones (see
+    /// [`ObjectPool::new`]).
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// #[derive(Default, ObjectPool)]
+    /// struct Buffer(Vec<u8>);
+    ///
+    /// fn main() {
+    ///     let mut buffer = Buffer::new();
+    ///     let mut results = Vec::new();
+    ///     for chunk in [b"ab", b"cd"] {
+    ///         buffer.0.extend_from_slice(chunk);
+    ///         results.push(Reusable::take(&mut buffer));
+    ///     }
+    ///     assert_eq!(results[0].0, b"ab");
+    ///     assert_eq!(results[1].0, b"cd");
+    ///     assert!(buffer.0.is_empty());
+    /// }
+    /// ```
+    #[inline]
+    pub fn take(this: &mut Self) -> T {
+        let (item, stamp) = this.stamp.owner::<T>().take_or_generate();
+        this.stamp.renew(stamp);
+        core::mem::replace(&mut this.item, item)
+    }
+
+    /// Replaces the object inside the wrapper with the specified one, handing
+    /// back the old object without returning it to the pool. The wrapper
+    /// returns the new object to the pool when dropped.
+    ///
+    /// This is an associated function that needs to be used as
+    /// `Reusable::replace(...)`, so it doesn't shadow a method of the object.
+    #[inline]
+    pub fn replace(this: &mut Self, item: T) -> T {
+        this.stamp.renew(this.stamp.owner::<T>().stamp(0));
+        core::mem::replace(&mut this.item, item)
+    }
+
+    /// Returns a pinned mutable reference to the object, so it can be used with
+    /// `Pin`-based APIs (like polling a pooled future).
+    ///
+    /// The object is stored inline in the wrapper, so it is moved along with
+    /// the wrapper and when it is returned to the pool, hence the `Unpin`
+    /// bound. Objects that need a stable address (like self-referential or
+    /// intrusive structures) can keep the pinned part in a `Pin<Box<_>>` field:
+    /// the boxed part is never moved, even while it is stored in the pool.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    /// use std::future::Future;
+    /// use std::pin::Pin;
+    /// use std::task::{Context, Poll, Waker};
+    ///
+    /// #[derive(ObjectPool)]
+    /// #[generator(Task::idle)]
+    /// struct Task(Pin<Box<dyn Future<Output = u32> + Send>>);
+    ///
+    /// impl Task {
+    ///     fn idle() -> Self {
+    ///         Self(Box::pin(std::future::ready(0)))
+    ///     }
+    /// }
+    ///
+    /// impl Future for Task {
+    ///     type Output = u32;
+    ///
+    ///     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<u32> {
+    ///         self.0.as_mut().poll(cx)
+    ///     }
+    /// }
+    ///
+    /// fn main() {
+    ///     let mut task = Task::new();
+    ///     let mut cx = Context::from_waker(Waker::noop());
+    ///     assert_eq!(task.as_pin_mut().poll(&mut cx), Poll::Ready(0));
+    /// }
+    /// ```
+    #[inline]
+    pub fn as_pin_mut(&mut self) -> Pin<&mut T>
+    where
+        T: Unpin,
+    {
+        Pin::new(&mut self.item)
+    }
+
+    /// Returns the object to the pool immediately, like dropping the wrapper
+    /// but explicit at the call site. The object goes through the same return
+    /// pipeline as on drop (return hook, admission checks, recycling...), so
+    /// this returns `true` if the object was pooled or `false` if it was
+    /// discarded.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// #[derive(Default, ObjectPool)]
+    /// #[pool(max_reuses = 1)]
+    /// struct Buffer(Vec<u8>);
+    ///
+    /// fn main() {
+    ///     assert!(Buffer::new().release());
+    ///     // The object was already reused once, so it is discarded
+    ///     assert!(!Buffer::new().release());
+    ///     assert!(Buffer::pool().is_empty());
+    /// }
+    /// ```
+    #[inline]
+    pub fn release(self) -> bool {
+        let (item, stamp) = self.into_parts();
+        stamp.owner::<T>().release_stamped(item, stamp)
+    }
+
+    /// Detaches the object from the pool: the return to the pool on drop is
+    /// cancelled, and the object can keep being used by value through the
+    /// returned [`MaybePooled::Owned`] (for example once it grew too big to be
+    /// worth pooling). Unlike [`Reusable::into_inner`], the object stops being
+    /// counted as checked out (see [`Pool::outstanding`]). The object can be
+    /// attached to the pool again with [`MaybePooled::attach`].
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// #[derive(Default, ObjectPool)]
+    /// struct Buffer(Vec<u8>);
+    ///
+    /// fn main() {
+    ///     let mut buffer = Buffer::new();
+    ///     buffer.0.resize(1 << 20, 0);
+    ///     let buffer = buffer.detach();
+    ///     assert_eq!(buffer.0.len(), 1 << 20);
+    ///     drop(buffer); // dropped instead of being returned to the pool
+    ///     assert_eq!(Buffer::pool().len(), 0);
+    /// }
+    /// ```
+    #[inline]
+    pub fn detach(self) -> MaybePooled<T> {
+        let (item, stamp) = self.into_parts();
+        stamp.owner::<T>().detach_stamped(stamp);
+        MaybePooled::Owned(item)
+    }
+
+    /// Returns the owned object inside the wrapper, like
+    /// [`Reusable::into_inner`], unless the pool forbids its objects from
+    /// escaping it (see [`Pool::forbid_escape`]). In that case the wrapper is
+    /// returned back as the error.
+    #[inline]
+    pub fn into_inner_checked(self) -> Result<T, Self> {
+        if self.stamp.owner::<T>().escape_forbidden() {
+            Err(self)
+        } else {
+            Ok(self.into_inner())
+        }
+    }
+
+    /// Stages the return of the object to the pool. The object is parked in a
+    /// "cleaning" state until the return is committed with
+    /// [`StagedReturn::commit`], which allows running asynchronous cleanup
+    /// before the object can be reused.
+    #[inline]
+    pub fn stage_return(self) -> StagedReturn<T> {
+        let (item, stamp) = self.into_parts();
+        StagedReturn::new(item, stamp)
+    }
+
+    /// Labels the checkout of the object, giving it a unique identifier. The
+    /// returned [`Labeled`] wrapper implements [`Display`](std::fmt::Display)
+    /// to include the label and identifier in logs.
+    #[inline]
+    pub fn labeled(self, label: &'static str) -> Labeled<T> {
+        Labeled::new(self, label)
+    }
+
+    /// Hands the object to external code (e.g. a C callback or a GPU queue)
+    /// deferring its return to the pool until the returned [`HoldToken`] is
+    /// redeemed. This prevents the object from being reused while it is
+    /// still referenced externally.
+    #[inline]
+    pub fn hold(self) -> HoldToken<T> {
+        let (item, stamp) = self.into_parts();
+        HoldToken::new(item, stamp)
     }
 }
 
@@ -362,7 +3461,8 @@ impl<T: ObjectPool> DerefMut for Reusable<T> {
 impl<T: ObjectPool> Drop for Reusable<T> {
     #[inline]
     fn drop(&mut self) {
-        T::pool().insert(unsafe { ManuallyDrop::take(&mut self.item) });
+        let item = unsafe { ManuallyDrop::take(&mut self.item) };
+        self.stamp.owner::<T>().release_stamped(item, self.stamp);
     }
 }
 
@@ -373,11 +3473,131 @@ impl<T: ObjectPool> From<T> for Reusable<T> {
     }
 }
 
+impl<T: ObjectPool + fmt::Debug> fmt::Debug for Reusable<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ObjectPool + fmt::Display> fmt::Display for Reusable<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+impl<T: ObjectPool + PartialEq> PartialEq for Reusable<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: ObjectPool + Eq> Eq for Reusable<T> {}
+
+impl<T: ObjectPool + PartialOrd> PartialOrd for Reusable<T> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+impl<T: ObjectPool + Ord> Ord for Reusable<T> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        (**self).cmp(&**other)
+    }
+}
+
+impl<T: ObjectPool + Hash> Hash for Reusable<T> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state);
+    }
+}
+
+/// Iterates the pooled object, so pooled iterators and cursors can be passed
+/// to adapters and `for` loops directly. The object is returned to the pool
+/// once the iteration is over and the wrapper is dropped.
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::prelude::*;
+///
+/// #[derive(Default, ObjectPool)]
+/// struct Countdown(u32);
+///
+/// impl Iterator for Countdown {
+///     type Item = u32;
+///
+///     fn next(&mut self) -> Option<u32> {
+///         self.0 = self.0.checked_sub(1)?;
+///         Some(self.0)
+///     }
+/// }
+///
+/// fn main() {
+///     let mut countdown = Countdown::new();
+///     countdown.0 = 3;
+///     assert_eq!(countdown.collect::<Vec<_>>(), [2, 1, 0]);
+///     assert_eq!(Countdown::pool().len(), 1);
+/// }
+/// ```
+impl<T: ObjectPool + Iterator> Iterator for Reusable<T> {
+    type Item = T::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        (**self).next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (**self).size_hint()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        (**self).nth(n)
+    }
+}
+
+impl<T: ObjectPool + DoubleEndedIterator> DoubleEndedIterator for Reusable<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        (**self).next_back()
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        (**self).nth_back(n)
+    }
+}
+
+impl<T: ObjectPool + ExactSizeIterator> ExactSizeIterator for Reusable<T> {
+    #[inline]
+    fn len(&self) -> usize {
+        (**self).len()
+    }
+}
+
+impl<T: ObjectPool + FusedIterator> FusedIterator for Reusable<T> {}
+
 /// This is the prelude for the `derivable-object-pool` crate. It contains the
 /// main traits and structs that you will need to use the crate. It is
 /// recommended that you import this prelude at the top of your file.
 pub mod prelude {
-    pub use crate::{ObjectPool, Pool, Reusable};
+    pub use crate::{
+        global_pool, pool, AcquireAll, HeapSize, HoldToken, Labeled, MaybePooled, ObjectPool, Pool,
+        PoolHandle, Pooled, Recycle, Reusable, StaticObjectPool, StaticPool, TryObjectPool,
+        Zeroable,
+    };
+
+    #[cfg(feature = "std")]
+    pub use crate::KeyedPool;
+    #[cfg(feature = "tokio")]
+    pub use crate::{AsyncObjectPool, AsyncPool};
 }
 
 #[cfg(test)]
@@ -445,4 +3665,55 @@ mod tests {
 
         assert_eq!(2, Test2::pool().len());
     }
+
+    #[test]
+    fn clear_ordered() {
+        static DROPPED: std::sync::Mutex<Vec<u32>> = std::sync::Mutex::new(Vec::new());
+
+        struct Tracked(u32);
+
+        impl Drop for Tracked {
+            fn drop(&mut self) {
+                DROPPED.lock().unwrap().push(self.0);
+            }
+        }
+
+        let pool = Pool::new(|| Tracked(0));
+        for order in [DropOrder::Fifo, DropOrder::Lifo] {
+            for i in 1..=3 {
+                pool.insert(Tracked(i));
+            }
+            pool.clear_ordered(order);
+        }
+        assert_eq!(*DROPPED.lock().unwrap(), [1, 2, 3, 3, 2, 1]);
+    }
+
+    #[test]
+    fn return_inbox() {
+        static POOL: Pool<usize> = Pool::new(|| 0).with_return_inbox();
+
+        std::thread::scope(|scope| {
+            for thread in 0..4 {
+                scope.spawn(move || {
+                    for i in 0..100 {
+                        POOL.insert(thread * 100 + i);
+                    }
+                });
+            }
+        });
+        let mut items = POOL.take_all();
+        assert_eq!(items.len(), 400);
+        items.sort_unstable();
+        assert!(items.into_iter().eq(0..400));
+    }
+
+    #[test]
+    #[cfg(not(feature = "counters"))]
+    fn disabled_counters_are_free() {
+        // Without the feature, the counters add no state to the pool and
+        // recording an event is a no-op
+        assert_eq!(core::mem::size_of::<Counters>(), 0);
+        let pool = Pool::new(|| 0u8);
+        pool.record(Event::Hit);
+    }
 }
@@ -0,0 +1,126 @@
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use crate::Pool;
+
+impl<T: Send + 'static> Pool<T> {
+    /// Spawns a thread that runs a maintenance pass on the pool (see
+    /// [`Pool::maintain`]) every `interval`, so the idle timeout, validator and
+    /// floor of idle objects of the pool are applied without wiring a timer to
+    /// every pool. The thread is stopped when the returned guard is dropped.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// static POOL: Pool<Vec<u8>> = Pool::new(Vec::new).with_min_idle(4);
+    ///
+    /// fn main() {
+    ///     let maintenance = POOL.spawn_maintenance(Duration::from_millis(1));
+    ///     while POOL.len() < 4 {
+    ///         std::thread::yield_now();
+    ///     }
+    ///     drop(maintenance); // stops the thread
+    /// }
+    /// ```
+    #[must_use = "dropping the guard stops the maintenance"]
+    pub fn spawn_maintenance(&'static self, interval: Duration) -> MaintenanceGuard {
+        let stop = Arc::new((Mutex::new(false), Condvar::new()));
+        let signal = Arc::clone(&stop);
+        let worker = std::thread::spawn(move || {
+            let (stopped, wake) = &*signal;
+            let mut stopped = stopped.lock().unwrap_or_else(|err| err.into_inner());
+            while !*stopped {
+                (stopped, _) = wake
+                    .wait_timeout(stopped, interval)
+                    .unwrap_or_else(|err| err.into_inner());
+                if !*stopped {
+                    self.maintain();
+                }
+            }
+        });
+        MaintenanceGuard {
+            stop,
+            worker: Some(worker),
+        }
+    }
+
+    /// Spawns a task on the current [`tokio`] runtime that runs a maintenance
+    /// pass on the pool every `interval`, like [`Pool::spawn_maintenance`]
+    /// (requires the `tokio` feature). The task is aborted when the returned
+    /// guard is dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a tokio runtime.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// static POOL: Pool<Vec<u8>> = Pool::new(Vec::new).with_min_idle(4);
+    ///
+    /// #[tokio::main(flavor = "current_thread")]
+    /// async fn main() {
+    ///     let maintenance = POOL.spawn_maintenance_task(Duration::from_millis(1));
+    ///     while POOL.len() < 4 {
+    ///         tokio::task::yield_now().await;
+    ///     }
+    ///     drop(maintenance); // aborts the task
+    /// }
+    /// ```
+    #[cfg(feature = "tokio")]
+    #[must_use = "dropping the guard stops the maintenance"]
+    pub fn spawn_maintenance_task(&'static self, interval: Duration) -> MaintenanceTask {
+        let task = tokio::spawn(async move {
+            let mut ticks = tokio::time::interval(interval);
+            // The first tick completes immediately
+            ticks.tick().await;
+            loop {
+                ticks.tick().await;
+                self.maintain();
+            }
+        });
+        MaintenanceTask { task }
+    }
+}
+
+/// A guard of the maintenance thread of a pool, created by
+/// [`Pool::spawn_maintenance`]. Dropping the guard stops the thread, waiting
+/// for the maintenance pass in progress (if any) to finish.
+pub struct MaintenanceGuard {
+    /// The flag that stops the thread and the condition variable that wakes it.
+    stop: Arc<(Mutex<bool>, Condvar)>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl Drop for MaintenanceGuard {
+    fn drop(&mut self) {
+        let (stopped, wake) = &*self.stop;
+        *stopped.lock().unwrap_or_else(|err| err.into_inner()) = true;
+        wake.notify_one();
+        if let Some(worker) = self.worker.take() {
+            // A panic of the maintenance was already reported by the thread
+            let _ = worker.join();
+        }
+    }
+}
+
+/// A guard of the maintenance task of a pool, created by
+/// [`Pool::spawn_maintenance_task`] (requires the `tokio` feature). Dropping
+/// the guard aborts the task.
+#[cfg(feature = "tokio")]
+pub struct MaintenanceTask {
+    task: tokio::task::JoinHandle<()>,
+}
+
+#[cfg(feature = "tokio")]
+impl Drop for MaintenanceTask {
+    #[inline]
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
@@ -0,0 +1,188 @@
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem::size_of;
+use core::ops::{Deref, DerefMut};
+use core::ptr::NonNull;
+
+use crate::{ObjectPool, Reusable};
+
+/// Where the projected value of a [`MappedReusable`] lives.
+enum Target<U> {
+    /// Inside the pooled object, at the specified byte offset. The offset is
+    /// used as the object moves along with the guard.
+    Inline(usize),
+    /// Outside of the pooled object (e.g. in a heap buffer it owns), which
+    /// doesn't move along with the guard.
+    Outside(NonNull<U>),
+}
+
+impl<U> Target<U> {
+    /// Locates a borrow of the object, before the object is moved.
+    #[inline]
+    fn locate<T>(item: &T, target: NonNull<U>) -> Self {
+        let base = item as *const T as usize;
+        let addr = target.as_ptr() as usize;
+        match addr.checked_sub(base) {
+            Some(offset) if offset + size_of::<U>() <= size_of::<T>() => Self::Inline(offset),
+            _ => Self::Outside(target),
+        }
+    }
+
+    /// Returns a pointer to the projected value, derived from the specified
+    /// pointer to the object.
+    #[inline]
+    fn project<T>(&self, base: *const T) -> *const U {
+        match *self {
+            // SAFETY: the offset was measured inside the object
+            Self::Inline(offset) => unsafe { base.cast::<u8>().add(offset).cast() },
+            Self::Outside(target) => target.as_ptr(),
+        }
+    }
+}
+
+/// A [`Reusable`] object projected to a part of it with [`Reusable::map`]. The
+/// guard only gives access to the projected value, but the whole object is
+/// still returned to the pool when it is dropped.
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::prelude::*;
+///
+/// #[derive(Default, ObjectPool)]
+/// struct Request {
+///     headers: Vec<String>,
+///     body: Vec<u8>,
+/// }
+///
+/// fn write_body(body: &mut Vec<u8>) {
+///     body.extend_from_slice(b"hello");
+/// }
+///
+/// fn main() {
+///     let mut body = Reusable::map(Request::new(), |request| &mut request.body);
+///     write_body(&mut body);
+///     assert_eq!(*body, b"hello");
+///     drop(body); // the whole request is returned to the pool
+///     assert_eq!(Request::pool().len(), 1);
+/// }
+/// ```
+pub struct MappedReusable<T: ObjectPool, U> {
+    /// The object, which is returned to the pool when dropped.
+    item: Reusable<T>,
+    target: Target<U>,
+    /// The guard behaves as a mutable borrow of the projected value.
+    marker: PhantomData<*mut U>,
+}
+
+// SAFETY: the guard owns the object and exclusively borrows the projected value
+unsafe impl<T: ObjectPool + Send, U: Send> Send for MappedReusable<T, U> {}
+// SAFETY: the guard only hands out shared references from a shared reference
+unsafe impl<T: ObjectPool + Sync, U: Sync> Sync for MappedReusable<T, U> {}
+
+impl<T: ObjectPool, U> MappedReusable<T, U> {
+    /// Projects the object with the specified borrow of it.
+    #[inline]
+    fn new(item: Reusable<T>, target: Target<U>) -> Self {
+        Self {
+            item,
+            target,
+            marker: PhantomData,
+        }
+    }
+
+    /// Projects the value further, like [`Reusable::map`].
+    #[inline]
+    pub fn map<V, F>(mut this: Self, f: F) -> MappedReusable<T, V>
+    where
+        F: FnOnce(&mut U) -> &mut V,
+    {
+        let target = NonNull::from(f(&mut this));
+        let target = Target::locate(&*this.item, target);
+        MappedReusable::new(this.item, target)
+    }
+
+    /// Returns the [`Reusable`] object, giving access to the whole object
+    /// again.
+    #[inline]
+    pub fn into_reusable(this: Self) -> Reusable<T> {
+        this.item
+    }
+}
+
+impl<T: ObjectPool> Reusable<T> {
+    /// Projects the object to a part of it (like a field), in the style of
+    /// [`MutexGuard::map`]. The returned guard derefs to the projected value,
+    /// while still returning the whole object to the pool when dropped.
+    ///
+    /// This is an associated function that needs to be used as
+    /// `Reusable::map(...)`, so it doesn't shadow a method of the object.
+    ///
+    /// [`MutexGuard::map`]: https://doc.rust-lang.org/std/sync/struct.MutexGuard.html#method.map
+    #[inline]
+    pub fn map<U, F>(mut this: Self, f: F) -> MappedReusable<T, U>
+    where
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        let target = NonNull::from(f(&mut this));
+        let target = Target::locate(&*this, target);
+        MappedReusable::new(this, target)
+    }
+
+    /// Tries to project the object to a part of it, like [`Reusable::map`].
+    /// If the closure returns `None`, the object is given back.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// #[derive(Default, ObjectPool)]
+    /// struct Slot(Option<String>);
+    ///
+    /// fn main() {
+    ///     let slot = Reusable::try_map(Slot::new(), |slot| slot.0.as_mut());
+    ///     let mut slot = slot.unwrap_err();
+    ///     slot.0 = Some(String::from("filled"));
+    ///
+    ///     let name = Reusable::try_map(slot, |slot| slot.0.as_mut()).ok().unwrap();
+    ///     assert_eq!(*name, "filled");
+    /// }
+    /// ```
+    #[inline]
+    pub fn try_map<U, F>(mut this: Self, f: F) -> Result<MappedReusable<T, U>, Self>
+    where
+        F: FnOnce(&mut T) -> Option<&mut U>,
+    {
+        match f(&mut this).map(NonNull::from) {
+            Some(target) => {
+                let target = Target::locate(&*this, target);
+                Ok(MappedReusable::new(this, target))
+            }
+            None => Err(this),
+        }
+    }
+}
+
+impl<T: ObjectPool, U> Deref for MappedReusable<T, U> {
+    type Target = U;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: the projected value is exclusively borrowed by the guard
+        unsafe { &*self.target.project::<T>(&*self.item) }
+    }
+}
+
+impl<T: ObjectPool, U> DerefMut for MappedReusable<T, U> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        let base: *mut T = &mut *self.item;
+        // SAFETY: the projected value is exclusively borrowed by the guard
+        unsafe { &mut *self.target.project(base).cast_mut() }
+    }
+}
+
+impl<T: ObjectPool, U: fmt::Debug> fmt::Debug for MappedReusable<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
@@ -0,0 +1,172 @@
+use core::borrow::{Borrow, BorrowMut};
+use core::ops::{Deref, DerefMut};
+
+use crate::{ObjectPool, Reusable};
+
+/// A value that is either borrowed from the pool (wrapped in a [`Reusable`])
+/// or owned outside of the pool, in the spirit of [`Cow`]. This allows APIs
+/// to accept both pooled and non-pooled values without being generic over
+/// them, as both variants can be accessed uniformly through [`Deref`] and
+/// [`DerefMut`].
+///
+/// When dropped, a pooled value is returned to the pool while an owned value
+/// is dropped normally.
+///
+/// [`Cow`]: std::borrow::Cow
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::prelude::*;
+///
+/// #[derive(Default, ObjectPool)]
+/// struct Message(String);
+///
+/// fn send(message: MaybePooled<Message>) -> usize {
+///     message.0.len()
+/// }
+///
+/// fn main() {
+///     assert_eq!(send(Message::new().into()), 0);
+///     assert_eq!(send(Message(String::from("owned")).into()), 5);
+///     assert_eq!(Message::pool().len(), 1);
+/// }
+/// ```
+pub enum MaybePooled<T: ObjectPool> {
+    /// A value that will be returned to the pool when dropped.
+    Pooled(Reusable<T>),
+    /// A value that is not managed by the pool.
+    Owned(T),
+}
+
+impl<T: ObjectPool> MaybePooled<T> {
+    /// Returns `true` if the value will be returned to the pool when dropped.
+    #[inline]
+    pub const fn is_pooled(&self) -> bool {
+        matches!(self, Self::Pooled(_))
+    }
+
+    /// Returns `true` if the value is not managed by the pool.
+    #[inline]
+    pub const fn is_owned(&self) -> bool {
+        matches!(self, Self::Owned(_))
+    }
+
+    /// Returns the owned value. A pooled value is taken out of the pool
+    /// without returning it (see [`Reusable::into_inner`]).
+    #[inline]
+    pub fn into_inner(self) -> T {
+        match self {
+            Self::Pooled(item) => item.into_inner(),
+            Self::Owned(item) => item,
+        }
+    }
+
+    /// Cancels the return of a pooled value to the pool, converting it into an
+    /// owned value that is dropped normally (see [`Reusable::detach`]). An
+    /// owned value is returned unchanged.
+    #[inline]
+    pub fn detach(self) -> Self {
+        match self {
+            Self::Pooled(item) => item.detach(),
+            Self::Owned(item) => Self::Owned(item),
+        }
+    }
+
+    /// Attaches an owned value to the pool, so it will be returned to the pool
+    /// when dropped. A pooled value is returned unchanged.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// #[derive(Default, ObjectPool)]
+    /// struct Buffer(Vec<u8>);
+    ///
+    /// fn main() {
+    ///     let buffer = Buffer::new().detach();
+    ///     assert!(buffer.is_owned());
+    ///     let buffer = buffer.attach();
+    ///     assert!(buffer.is_pooled());
+    ///     drop(buffer);
+    ///     assert_eq!(Buffer::pool().len(), 1);
+    /// }
+    /// ```
+    #[inline]
+    pub fn attach(self) -> Self {
+        Self::Pooled(self.into_reusable())
+    }
+
+    /// Returns the value wrapped in a [`Reusable`]. An owned value will be
+    /// returned to the pool once the wrapper is dropped.
+    #[inline]
+    pub fn into_reusable(self) -> Reusable<T> {
+        match self {
+            Self::Pooled(item) => item,
+            Self::Owned(item) => Reusable::new(item),
+        }
+    }
+}
+
+impl<T: ObjectPool> From<Reusable<T>> for MaybePooled<T> {
+    #[inline]
+    fn from(item: Reusable<T>) -> Self {
+        Self::Pooled(item)
+    }
+}
+
+impl<T: ObjectPool> From<T> for MaybePooled<T> {
+    #[inline]
+    fn from(item: T) -> Self {
+        Self::Owned(item)
+    }
+}
+
+impl<T: ObjectPool> Borrow<T> for MaybePooled<T> {
+    #[inline]
+    fn borrow(&self) -> &T {
+        self
+    }
+}
+
+impl<T: ObjectPool> BorrowMut<T> for MaybePooled<T> {
+    #[inline]
+    fn borrow_mut(&mut self) -> &mut T {
+        self
+    }
+}
+
+impl<T: ObjectPool> AsRef<T> for MaybePooled<T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+impl<T: ObjectPool> AsMut<T> for MaybePooled<T> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut T {
+        self
+    }
+}
+
+impl<T: ObjectPool> Deref for MaybePooled<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Pooled(item) => item,
+            Self::Owned(item) => item,
+        }
+    }
+}
+
+impl<T: ObjectPool> DerefMut for MaybePooled<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            Self::Pooled(item) => item,
+            Self::Owned(item) => item,
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use core::ops::{Deref, DerefMut};
+
+/// Pads and aligns a value to the length of a cache line. This prevents false
+/// sharing between the hot internals of a pool (which are modified by every
+/// acquisition and return) and neighboring data, like other static pools.
+///
+/// Modern x86_64 and aarch64 CPUs prefetch cache lines in pairs, so the
+/// alignment is 128 bytes on those architectures and 64 bytes elsewhere.
+#[cfg_attr(any(target_arch = "x86_64", target_arch = "aarch64"), repr(align(128)))]
+#[cfg_attr(
+    not(any(target_arch = "x86_64", target_arch = "aarch64")),
+    repr(align(64))
+)]
+#[derive(Default)]
+pub(crate) struct CachePadded<T> {
+    value: T,
+}
+
+impl<T> CachePadded<T> {
+    /// Pads and aligns the specified value to the length of a cache line.
+    #[inline]
+    pub(crate) const fn new(value: T) -> Self {
+        Self { value }
+    }
+}
+
+impl<T> Deref for CachePadded<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for CachePadded<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
@@ -0,0 +1,76 @@
+use alloc::collections::{BTreeMap, BTreeSet, BinaryHeap, VecDeque};
+use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+
+/// Resets the state of an object when it is returned to the pool, so the next
+/// user gets a clean object without every caller having to remember to reset
+/// it manually (e.g. clearing a [`Vec`] or resetting counters). The pool calls
+/// [`Recycle::recycle`] on every returned object if it is enabled with
+/// [`Pool::with_recycle`] (or the `#[pool(recycle)]` derive attribute).
+///
+/// [`Pool::with_recycle`]: crate::Pool::with_recycle
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::prelude::*;
+///
+/// #[derive(Default, ObjectPool)]
+/// #[pool(recycle)]
+/// struct Request {
+///     path: String,
+///     retries: u32,
+/// }
+///
+/// impl Recycle for Request {
+///     fn recycle(&mut self) {
+///         self.path.recycle();
+///         self.retries = 0;
+///     }
+/// }
+///
+/// fn main() {
+///     let mut request = Request::new();
+///     request.path.push_str("/index.html");
+///     request.retries = 3;
+///     drop(request);
+///
+///     let request = Request::new();
+///     assert!(request.path.is_empty());
+///     assert!(request.path.capacity() >= 11); // the allocation is reused
+///     assert_eq!(request.retries, 0);
+/// }
+/// ```
+pub trait Recycle {
+    /// Resets the state of the object before it is stored in the pool.
+    fn recycle(&mut self);
+}
+
+macro_rules! impl_recycle_clear {
+    ($($ty:ty => [$($generics:tt)*]),+ $(,)?) => {
+        $(
+            impl<$($generics)*> Recycle for $ty {
+                #[inline]
+                fn recycle(&mut self) {
+                    self.clear();
+                }
+            }
+        )+
+    };
+}
+
+impl_recycle_clear!(
+    String => [],
+    Vec<T> => [T],
+    VecDeque<T> => [T],
+    BinaryHeap<T> => [T],
+    BTreeSet<T> => [T],
+    BTreeMap<K, V> => [K, V],
+);
+
+#[cfg(feature = "std")]
+impl_recycle_clear!(
+    HashSet<T, S> => [T, S],
+    HashMap<K, V, S> => [K, V, S],
+);
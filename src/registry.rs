@@ -0,0 +1,271 @@
+//! A process-global registry of pools, which allows listing the pools of the
+//! process and running operations on all of them (like flushing every pool on
+//! a configuration reload).
+//!
+//! The pools generated by [`#[derive(ObjectPool)]`](derive@crate::ObjectPool)
+//! are registered the first time they are used, other pools can be registered
+//! with [`Pool::register`].
+//!
+//! # Example
+//! ```
+//! use derivable_object_pool::prelude::*;
+//! use derivable_object_pool::registry;
+//!
+//! #[derive(Default, ObjectPool)]
+//! struct Request(Vec<u8>);
+//!
+//! fn main() {
+//!     drop(Request::new());
+//!
+//!     let pool = registry::iter()
+//!         .find(|pool| pool.type_name() == std::any::type_name::<Request>())
+//!         .unwrap();
+//!     assert_eq!(pool.name(), "REQUEST_OBJECT_POOL");
+//!     assert_eq!(pool.len(), 1);
+//!
+//!     registry::clear_all();
+//!     assert!(Request::pool().is_empty());
+//! }
+//! ```
+use alloc::vec::Vec;
+use core::fmt;
+use core::sync::atomic::Ordering;
+
+use crate::sync::RwLock;
+#[cfg(feature = "counters")]
+use crate::Stats;
+use crate::{Pool, PoolDebugState};
+
+mod prometheus;
+
+pub use prometheus::render_prometheus;
+
+/// The registered pools, in registration order.
+static REGISTRY: RwLock<Vec<RegisteredPool>> = RwLock::new(Vec::new());
+
+/// The operations of a pool available through the registry, independently of
+/// the type of its objects.
+trait ErasedPool: Sync {
+    fn name(&self) -> Option<&'static str>;
+    fn type_name(&self) -> &'static str;
+    fn len(&self) -> usize;
+    fn outstanding(&self) -> usize;
+    fn clear(&self);
+    fn trim(&self) -> usize;
+    fn debug_state(&self) -> PoolDebugState;
+    #[cfg(feature = "counters")]
+    fn stats(&self) -> Stats;
+}
+
+impl<T: Send> ErasedPool for Pool<T> {
+    fn name(&self) -> Option<&'static str> {
+        Pool::name(self)
+    }
+
+    fn type_name(&self) -> &'static str {
+        core::any::type_name::<T>()
+    }
+
+    fn len(&self) -> usize {
+        Pool::len(self)
+    }
+
+    fn outstanding(&self) -> usize {
+        Pool::outstanding(self)
+    }
+
+    fn clear(&self) {
+        Pool::clear(self);
+    }
+
+    fn trim(&self) -> usize {
+        Pool::trim(self)
+    }
+
+    fn debug_state(&self) -> PoolDebugState {
+        Pool::debug_state(self)
+    }
+
+    #[cfg(feature = "counters")]
+    fn stats(&self) -> Stats {
+        Pool::stats(self)
+    }
+}
+
+/// A pool of the registry, see the [module documentation](self).
+#[derive(Clone, Copy)]
+pub struct RegisteredPool {
+    name: &'static str,
+    pool: &'static dyn ErasedPool,
+}
+
+impl RegisteredPool {
+    /// Returns the name of the pool (see [`Pool::with_name`]), or the name it
+    /// was registered with if it wasn't assigned one.
+    #[inline]
+    pub fn name(&self) -> &'static str {
+        self.pool.name().unwrap_or(self.name)
+    }
+
+    /// Returns the name of the type of the pooled objects.
+    #[inline]
+    pub fn type_name(&self) -> &'static str {
+        self.pool.type_name()
+    }
+
+    /// Returns the number of idle objects of the pool.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.pool.len()
+    }
+
+    /// Returns `true` if the pool has no idle objects.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of objects checked out of the pool (see
+    /// [`Pool::outstanding`]).
+    #[inline]
+    pub fn outstanding(&self) -> usize {
+        self.pool.outstanding()
+    }
+
+    /// Removes all the idle objects of the pool (see [`Pool::clear`]).
+    #[inline]
+    pub fn clear(&self) {
+        self.pool.clear();
+    }
+
+    /// Drops the idle objects of the pool above its floor of idle objects
+    /// (see [`Pool::trim`]).
+    #[inline]
+    pub fn trim(&self) -> usize {
+        self.pool.trim()
+    }
+
+    /// Returns a snapshot of the state of the pool (see
+    /// [`Pool::debug_state`]).
+    #[inline]
+    pub fn debug_state(&self) -> PoolDebugState {
+        self.pool.debug_state()
+    }
+
+    /// Returns a snapshot of the statistics of the pool (requires the
+    /// `counters` feature, see [`Pool::stats`]).
+    #[cfg(feature = "counters")]
+    #[inline]
+    pub fn stats(&self) -> Stats {
+        self.pool.stats()
+    }
+
+    /// Publishes the state of the pool through the [`metrics`] facade, labeled
+    /// with the name of the pool (requires the `metrics` feature):
+    /// - `object_pool_idle`: gauge with the number of idle objects.
+    /// - `object_pool_outstanding`: gauge with the number of checked out
+    ///   objects (see [`Pool::outstanding`]).
+    /// - `object_pool_created_total`: counter with the number of objects
+    ///   created by the generator of the pool.
+    /// - `object_pool_reuse_ratio`: gauge with the fraction of acquisitions
+    ///   that reused an idle object (see [`Stats::hit_rate`]).
+    #[cfg(feature = "metrics")]
+    pub fn publish_metrics(&self) {
+        let stats = self.stats();
+        let labels = [("pool", self.name())];
+        metrics::gauge!("object_pool_idle", &labels).set(self.len() as f64);
+        metrics::gauge!("object_pool_outstanding", &labels).set(self.outstanding() as f64);
+        metrics::counter!("object_pool_created_total", &labels).absolute(stats.created);
+        metrics::gauge!("object_pool_reuse_ratio", &labels).set(stats.hit_rate());
+    }
+}
+
+impl fmt::Debug for RegisteredPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RegisteredPool")
+            .field("name", &self.name())
+            .field("type_name", &self.type_name())
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl<T: Send> Pool<T> {
+    /// Registers the pool in the global [`registry`](self) with the specified
+    /// name, if it is not registered yet. The pools generated by
+    /// [`#[derive(ObjectPool)]`](derive@crate::ObjectPool) are registered
+    /// automatically, with the name of their static.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    /// use derivable_object_pool::registry;
+    ///
+    /// static BUFFERS: Pool<Vec<u8>> = Pool::new(Vec::new);
+    ///
+    /// fn main() {
+    ///     BUFFERS.register("BUFFERS");
+    ///     BUFFERS.register("ignored, as it is already registered");
+    ///     assert_eq!(registry::iter().filter(|pool| pool.name() == "BUFFERS").count(), 1);
+    /// }
+    /// ```
+    #[inline]
+    pub fn register(&'static self, name: &'static str) {
+        if !self.registered.load(Ordering::Acquire) {
+            self.register_slow(name);
+        }
+    }
+
+    #[cold]
+    fn register_slow(&'static self, name: &'static str) {
+        let mut registry = REGISTRY.write();
+        // The flag is only written while the lock is held
+        if !self.registered.load(Ordering::Relaxed) {
+            registry.push(RegisteredPool { name, pool: self });
+            self.registered.store(true, Ordering::Release);
+        }
+    }
+}
+
+/// Returns an iterator over a snapshot of the registered pools, in
+/// registration order.
+pub fn iter() -> impl Iterator<Item = RegisteredPool> {
+    // Copied so the lock isn't held while iterating
+    REGISTRY.read().clone().into_iter()
+}
+
+/// Removes all the idle objects of every registered pool (see
+/// [`Pool::clear`]).
+pub fn clear_all() {
+    iter().for_each(|pool| pool.clear());
+}
+
+/// Publishes the state of every registered pool through the [`metrics`]
+/// facade (requires the `metrics` feature, see
+/// [`RegisteredPool::publish_metrics`]). Calling this periodically, or before
+/// the metrics are scraped, keeps the dashboards up to date.
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::prelude::*;
+/// use derivable_object_pool::registry;
+///
+/// #[derive(Default, ObjectPool)]
+/// struct Payload(Vec<u8>);
+///
+/// fn main() {
+///     drop(Payload::new());
+///     // Sets `object_pool_idle{pool="PAYLOAD_OBJECT_POOL"}` to 1, and so on
+///     registry::publish_metrics();
+/// }
+/// ```
+#[cfg(feature = "metrics")]
+pub fn publish_metrics() {
+    iter().for_each(|pool| pool.publish_metrics());
+}
+
+/// Drops the idle objects of every registered pool above its floor of idle
+/// objects (see [`Pool::trim`]). Returns the total number of dropped objects.
+pub fn trim_all() -> usize {
+    iter().map(|pool| pool.trim()).sum()
+}
@@ -0,0 +1,148 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+
+use super::{iter, RegisteredPool};
+
+/// A metric of the exposition, read from each registered pool.
+struct Metric {
+    name: &'static str,
+    kind: &'static str,
+    help: &'static str,
+    value: fn(&RegisteredPool) -> Value,
+}
+
+/// The value of a metric of a pool.
+enum Value {
+    Integer(u64),
+    #[cfg(feature = "counters")]
+    Float(f64),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Integer(value) => write!(f, "{value}"),
+            #[cfg(feature = "counters")]
+            Self::Float(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+/// The metrics of the exposition, in the order they are rendered.
+const METRICS: &[Metric] = &[
+    Metric {
+        name: "object_pool_idle",
+        kind: "gauge",
+        help: "The number of idle objects of the pool.",
+        value: |pool| Value::Integer(pool.len() as u64),
+    },
+    Metric {
+        name: "object_pool_outstanding",
+        kind: "gauge",
+        help: "The number of objects checked out of the pool.",
+        value: |pool| Value::Integer(pool.outstanding() as u64),
+    },
+    #[cfg(feature = "counters")]
+    Metric {
+        name: "object_pool_created_total",
+        kind: "counter",
+        help: "The number of objects created by the generator of the pool.",
+        value: |pool| Value::Integer(pool.stats().created),
+    },
+    #[cfg(feature = "counters")]
+    Metric {
+        name: "object_pool_hits_total",
+        kind: "counter",
+        help: "The number of acquisitions that reused an idle object.",
+        value: |pool| Value::Integer(pool.stats().hits),
+    },
+    #[cfg(feature = "counters")]
+    Metric {
+        name: "object_pool_misses_total",
+        kind: "counter",
+        help: "The number of acquisitions that found no idle objects.",
+        value: |pool| Value::Integer(pool.stats().misses),
+    },
+    #[cfg(feature = "counters")]
+    Metric {
+        name: "object_pool_returned_total",
+        kind: "counter",
+        help: "The number of returned objects stored in the pool.",
+        value: |pool| Value::Integer(pool.stats().returned),
+    },
+    #[cfg(feature = "counters")]
+    Metric {
+        name: "object_pool_rejected_total",
+        kind: "counter",
+        help: "The number of returned objects dropped by the policies of the pool.",
+        value: |pool| Value::Integer(pool.stats().rejected),
+    },
+    #[cfg(feature = "counters")]
+    Metric {
+        name: "object_pool_reuse_ratio",
+        kind: "gauge",
+        help: "The fraction of acquisitions that reused an idle object.",
+        value: |pool| Value::Float(pool.stats().hit_rate()),
+    },
+];
+
+/// Renders the state of every registered pool in the Prometheus text
+/// exposition format, so an existing `/metrics` handler can include the
+/// health of the pools with one call. Each sample is labeled with the name
+/// of the pool and the name of the type of its objects.
+///
+/// The idle (`object_pool_idle`) and checked out (`object_pool_outstanding`)
+/// objects are always rendered. With the `counters` feature, the statistics
+/// of the pools (see [`Stats`](crate::Stats)) are rendered too.
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::prelude::*;
+/// use derivable_object_pool::registry;
+///
+/// #[derive(Default, ObjectPool)]
+/// struct Message(Vec<u8>);
+///
+/// fn main() {
+///     drop(Message::new());
+///
+///     let exposition = registry::render_prometheus();
+///     assert!(exposition.contains("# TYPE object_pool_idle gauge\n"));
+///     assert!(exposition.contains("object_pool_idle{pool=\"MESSAGE_OBJECT_POOL\","));
+/// }
+/// ```
+pub fn render_prometheus() -> String {
+    let pools: Vec<_> = iter().collect();
+    let mut exposition = String::new();
+    for metric in METRICS {
+        render_metric(&mut exposition, metric, &pools).expect("writing to a string doesn't fail");
+    }
+    exposition
+}
+
+/// Renders the samples of a metric for each of the pools.
+fn render_metric(out: &mut String, metric: &Metric, pools: &[RegisteredPool]) -> fmt::Result {
+    writeln!(out, "# HELP {} {}", metric.name, metric.help)?;
+    writeln!(out, "# TYPE {} {}", metric.name, metric.kind)?;
+    for pool in pools {
+        write!(out, "{}{{pool=\"", metric.name)?;
+        escape_label(out, pool.name());
+        out.push_str("\",type=\"");
+        escape_label(out, pool.type_name());
+        writeln!(out, "\"}} {}", (metric.value)(pool))?;
+    }
+    Ok(())
+}
+
+/// Writes a label value, escaping the characters the format reserves.
+fn escape_label(out: &mut String, value: &str) {
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            c => out.push(c),
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use alloc::vec::Vec;
+use core::fmt;
+use core::ptr;
+
+use crate::stamp::Stamp;
+use crate::{ObjectPool, Reusable};
+
+/// A local buffer of [`Reusable`] objects that are returned to their pool
+/// together, locking the pool once per flush instead of once per object. This
+/// amortizes the cost of returning objects in tight loops that churn many
+/// objects. The objects are returned when the batch is flushed with
+/// [`ReturnBatch::flush`] or dropped.
+///
+/// The objects go through the same return pipeline as when they are dropped,
+/// but they are stored directly in the pool (bypassing the lock-free slots and
+/// the inbox of the pool, if enabled).
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::prelude::*;
+/// use derivable_object_pool::ReturnBatch;
+///
+/// #[derive(Default, ObjectPool)]
+/// struct Particle(f32);
+///
+/// fn main() {
+///     let mut batch = ReturnBatch::new();
+///     for _frame in 0..3 {
+///         for _ in 0..1000 {
+///             let particle = Particle::new();
+///             batch.push(particle); // instead of dropping it
+///         }
+///         assert_eq!(batch.flush(), 1000);
+///     }
+///     assert_eq!(Particle::pool().len(), 1000);
+/// }
+/// ```
+pub struct ReturnBatch<T: ObjectPool> {
+    /// The buffered objects, with the stamps of their checkouts.
+    items: Vec<(T, Stamp)>,
+}
+
+impl<T: ObjectPool> ReturnBatch<T> {
+    /// Creates a new, empty batch.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { items: Vec::new() }
+    }
+
+    /// Creates a new, empty batch with space for at least `capacity` objects
+    /// before reallocating.
+    #[must_use]
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            items: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Buffers the object, which will be returned to the pool with the rest of
+    /// the batch.
+    #[inline]
+    pub fn push(&mut self, item: Reusable<T>) {
+        self.items.push(item.into_parts());
+    }
+
+    /// Returns the number of buffered objects.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if there are no buffered objects.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns the buffered objects to the pool under one lock. Returns the
+    /// number of objects that were pooled, the rest were discarded by the
+    /// policies of the pool. The batch can keep being used afterwards.
+    pub fn flush(&mut self) -> usize {
+        if self.items.is_empty() {
+            return 0;
+        }
+        let mut items = core::mem::take(&mut self.items);
+        let mut pooled = 0;
+        // Each object goes back to the pool it was checked out from
+        loop {
+            let owner = items[0].1.owner::<T>();
+            if items.iter().all(|(_, stamp)| ptr::eq(stamp.owner(), owner)) {
+                return pooled + owner.release_batch(items);
+            }
+            let (owned, rest) = items
+                .into_iter()
+                .partition(|(_, stamp)| ptr::eq(stamp.owner(), owner));
+            pooled += owner.release_batch(owned);
+            items = rest;
+        }
+    }
+}
+
+impl<T: ObjectPool> Default for ReturnBatch<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ObjectPool> Extend<Reusable<T>> for ReturnBatch<T> {
+    fn extend<I: IntoIterator<Item = Reusable<T>>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|item| self.push(item));
+    }
+}
+
+impl<T: ObjectPool> fmt::Debug for ReturnBatch<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReturnBatch")
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: ObjectPool> Drop for ReturnBatch<T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
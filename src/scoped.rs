@@ -0,0 +1,195 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::sync::RwLock;
+use crate::Pool;
+
+/// The number of scoped pools alive in any thread, so the pools of the threads
+/// without scoped pools don't look them up.
+static SCOPED: AtomicUsize = AtomicUsize::new(0);
+
+std::thread_local! {
+    /// The scoped pools of the current thread, as the addresses of the
+    /// replaced pools and of their replacements, the innermost last.
+    static OVERRIDES: RefCell<Vec<(usize, usize)>> = const { RefCell::new(Vec::new()) };
+
+    /// The replacements leaked by the scopes of the current thread, as the
+    /// addresses of the replaced pools and of their replacements, so the next
+    /// scopes reuse them instead of leaking new ones.
+    static REPLACEMENTS: RefCell<Vec<(usize, usize)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Removes the innermost scoped pool of the current thread and invalidates its
+/// replacement when the scope ends (or panics).
+struct Override<T: 'static> {
+    /// The pool replacing the original pool within the scope.
+    fresh: &'static Pool<T>,
+}
+
+impl<T> Drop for Override<T> {
+    fn drop(&mut self) {
+        OVERRIDES.with(|overrides| overrides.borrow_mut().pop());
+        SCOPED.fetch_sub(1, Ordering::Relaxed);
+        self.fresh.invalidate();
+    }
+}
+
+impl<T> Pool<T> {
+    /// Calls the closure with the pool replaced by a fresh, empty pool with the
+    /// same configuration on the current thread, restoring it afterwards. Pools
+    /// derived with [`#[derive(ObjectPool)]`](derive@crate::ObjectPool) are
+    /// global, so this isolates the tests that check the state of a pool from
+    /// the other tests running in parallel.
+    ///
+    /// Only the current thread sees the replacement, through
+    /// [`ObjectPool::pool`](crate::ObjectPool::pool). The objects checked out
+    /// within the scope are returned to the replacement even if they outlive
+    /// the scope. When the scope ends (or the closure panics), the replacement
+    /// discards its idle objects and the objects returned to it afterwards
+    /// (see [`Pool::invalidate`]). The later scopes of the thread reuse the
+    /// replacement once none of its objects are checked out.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// #[derive(Default, ObjectPool)]
+    /// struct Buffer(Vec<u8>);
+    ///
+    /// fn main() {
+    ///     drop(Buffer::new());
+    ///     Buffer::pool().scoped(|| {
+    ///         assert_eq!(Buffer::pool().len(), 0);
+    ///         drop(Buffer::new());
+    ///         assert_eq!(Buffer::pool().len(), 1);
+    ///     });
+    ///     assert_eq!(Buffer::pool().len(), 1);
+    /// }
+    /// ```
+    pub fn scoped<R>(&'static self, f: impl FnOnce() -> R) -> R {
+        let fresh = self.replacement();
+        OVERRIDES.with(|overrides| {
+            overrides
+                .borrow_mut()
+                .push((self as *const Self as usize, fresh as *const Self as usize));
+        });
+        SCOPED.fetch_add(1, Ordering::Relaxed);
+        let _scope = Override { fresh };
+        f()
+    }
+
+    /// Returns a fresh replacement of this pool for a new scope of the current
+    /// thread. The replacements are leaked, as references to them can outlive
+    /// the scope, so the ones left by previous scopes are reset and reused once
+    /// none of their objects are checked out.
+    fn replacement(&'static self) -> &'static Self {
+        let address = self as *const Self as usize;
+        let reused = REPLACEMENTS.with(|replacements| {
+            OVERRIDES.with(|overrides| {
+                let overrides = overrides.borrow();
+                replacements
+                    .borrow()
+                    .iter()
+                    .filter(|&&(pool, fresh)| {
+                        pool == address && overrides.iter().all(|&(_, active)| active != fresh)
+                    })
+                    // SAFETY: The replacements are leaked, and they have the
+                    // type of the pool they replace.
+                    .map(|&(_, fresh)| unsafe { &*(fresh as *const Self) })
+                    .find(|fresh| fresh.outstanding() == 0)
+            })
+        });
+        if let Some(fresh) = reused {
+            fresh.reset_like(self);
+            return fresh;
+        }
+        let fresh: &'static Self = Box::leak(Box::new(self.fresh()));
+        REPLACEMENTS.with(|replacements| {
+            replacements
+                .borrow_mut()
+                .push((address, fresh as *const Self as usize));
+        });
+        fresh
+    }
+
+    /// Returns the innermost pool replacing this one on the current thread, if
+    /// any.
+    #[inline]
+    pub(crate) fn scoped_override(&'static self) -> Option<&'static Self> {
+        if SCOPED.load(Ordering::Relaxed) == 0 {
+            return None;
+        }
+        let address = self as *const Self as usize;
+        // The nested scopes can replace this pool or any of its replacements
+        let fresh = OVERRIDES.with(|overrides| {
+            overrides
+                .borrow()
+                .iter()
+                .fold(address, |current, &(pool, fresh)| {
+                    match pool == address || pool == current {
+                        true => fresh,
+                        false => current,
+                    }
+                })
+        });
+        if fresh == address {
+            return None;
+        }
+        // SAFETY: The replacements are leaked, and they have the type of the
+        // pool they replace, as the addresses of distinct pools are distinct.
+        Some(unsafe { &*(fresh as *const Self) })
+    }
+
+    /// Creates a new empty pool with the same configuration.
+    fn fresh(&self) -> Self {
+        let mut pool = Self::with_generator(self.generator)
+            .with_capacity(self.capacity())
+            .with_min_idle(self.min_idle)
+            .with_growth(self.growth)
+            .with_prefill(self.prefill)
+            .with_max_reuses(self.max_reuses)
+            .with_max_outstanding(self.max_outstanding())
+            .with_fairness(self.fairness)
+            .with_lock_free(self.slots.capacity());
+        // The options whose builders are optional or bound the type of objects
+        pool.name = RwLock::new(self.name());
+        pool.heap_size = self.heap_size;
+        pool.max_object_size = self.max_object_size;
+        pool.max_bytes = self.max_bytes;
+        pool.escape_forbidden = self.escape_forbidden;
+        pool.soft_limit = self.soft_limit;
+        pool.on_soft_limit = self.on_soft_limit;
+        pool.middlewares = RwLock::new(self.middlewares.read().clone());
+        pool.inbox_enabled = self.inbox_enabled;
+        pool.idle_timeout = self.idle_timeout;
+        pool.recycle = self.recycle;
+        pool.on_create = self.on_create;
+        pool.on_reuse = self.on_reuse;
+        pool.on_return = self.on_return;
+        pool.on_discard = self.on_discard;
+        pool.validator = self.validator;
+        pool.zero = self.zero;
+        pool.initial_items = self.initial_items;
+        pool
+    }
+
+    /// Resets a replacement left by a previous scope, which was invalidated
+    /// when the scope ended and has no objects checked out, to the state of a
+    /// fresh replacement of the specified pool.
+    fn reset_like(&self, pool: &Self) {
+        // The objects returned since the scope ended are of a previous epoch
+        self.set_max_idle(pool.capacity());
+        self.max_outstanding
+            .store(pool.max_outstanding(), Ordering::Relaxed);
+        *self.name.write() = pool.name();
+        *self.middlewares.write() = pool.middlewares.read().clone();
+        self.oversized_rejections.store(0, Ordering::Relaxed);
+        self.counters.reset();
+        // Filled again the first time the replacement is used
+        let _idle = self.lock_idle();
+        self.initialized.store(false, Ordering::Relaxed);
+        self.prefilled.store(false, Ordering::Relaxed);
+    }
+}
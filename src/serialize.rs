@@ -0,0 +1,46 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{ObjectPool, Reusable};
+
+/// Serializes the object transparently, as if it wasn't pooled (requires the
+/// `serde` feature).
+impl<T: ObjectPool + Serialize> Serialize for Reusable<T> {
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (**self).serialize(serializer)
+    }
+}
+
+/// Deserializes into an object acquired from the pool of `T` (requires the
+/// `serde` feature). The object is filled in place, so types that support it
+/// (like vectors and strings) reuse the allocations of the pooled object
+/// instead of allocating new ones. Derived implementations only fill structs in
+/// place with the `deserialize_in_place` feature of `serde_derive`, otherwise
+/// the pooled object is overwritten.
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::prelude::*;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Default, ObjectPool, Serialize, Deserialize)]
+/// struct Message {
+///     id: u32,
+///     body: String,
+/// }
+///
+/// fn main() {
+///     let message: Reusable<Message> = serde_json::from_str(r#"{"id":1,"body":"hi"}"#).unwrap();
+///     assert_eq!(message.body, "hi");
+///     assert_eq!(serde_json::to_string(&message).unwrap(), r#"{"id":1,"body":"hi"}"#);
+/// }
+/// ```
+impl<'de, T: ObjectPool + Deserialize<'de>> Deserialize<'de> for Reusable<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut item = T::new();
+        // If it fails, the object is returned to the pool (and recycled, if
+        // the pool recycles its objects) by the drop of the wrapper
+        T::deserialize_in_place(deserializer, &mut item)?;
+        Ok(item)
+    }
+}
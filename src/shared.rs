@@ -0,0 +1,216 @@
+use alloc::sync::{Arc, Weak};
+use core::borrow::Borrow;
+use core::fmt;
+use core::ops::Deref;
+
+use crate::{ObjectPool, Reusable};
+
+/// A reference-counted [`Reusable`] object, created with
+/// [`Reusable::into_shared`]. Like an [`Arc`], the clones of the wrapper share
+/// the same object immutably, and the object is returned to the pool only when
+/// the last clone is dropped.
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::prelude::*;
+///
+/// #[derive(Default, ObjectPool)]
+/// struct Table(Vec<u32>);
+///
+/// fn main() {
+///     let mut table = Table::new();
+///     table.0.extend([1, 2, 3]);
+///     let table = table.into_shared();
+///
+///     let workers: Vec<_> = (0..4)
+///         .map(|_| {
+///             let table = table.clone();
+///             std::thread::spawn(move || table.0.iter().sum::<u32>())
+///         })
+///         .collect();
+///     drop(table);
+///     for worker in workers {
+///         assert_eq!(worker.join().unwrap(), 6);
+///     }
+///     // The last clone returned the table to the pool
+///     assert_eq!(Table::pool().len(), 1);
+/// }
+/// ```
+pub struct SharedReusable<T: ObjectPool> {
+    item: Arc<Reusable<T>>,
+}
+
+impl<T: ObjectPool> SharedReusable<T> {
+    /// Returns the number of clones sharing the object.
+    #[inline]
+    pub fn strong_count(this: &Self) -> usize {
+        Arc::strong_count(&this.item)
+    }
+
+    /// Returns `true` if both wrappers share the same object.
+    #[inline]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Arc::ptr_eq(&this.item, &other.item)
+    }
+
+    /// Creates a [`WeakReusable`] handle to the object, which doesn't keep it
+    /// checked out.
+    #[inline]
+    pub fn downgrade(this: &Self) -> WeakReusable<T> {
+        WeakReusable {
+            item: Arc::downgrade(&this.item),
+        }
+    }
+
+    /// Returns the [`Reusable`] object if this is the only clone sharing it,
+    /// giving mutable access to it again. Otherwise, the wrapper is returned
+    /// back as the error.
+    #[inline]
+    pub fn try_unwrap(this: Self) -> Result<Reusable<T>, Self> {
+        Arc::try_unwrap(this.item).map_err(|item| Self { item })
+    }
+}
+
+impl<T: ObjectPool> Reusable<T> {
+    /// Shares the object immutably between several owners, returning it to the
+    /// pool when the last one drops it (see [`SharedReusable`]).
+    #[inline]
+    pub fn into_shared(self) -> SharedReusable<T> {
+        SharedReusable::from(self)
+    }
+}
+
+impl<T: ObjectPool> From<Reusable<T>> for SharedReusable<T> {
+    #[inline]
+    fn from(item: Reusable<T>) -> Self {
+        Self {
+            item: Arc::new(item),
+        }
+    }
+}
+
+impl<T: ObjectPool> Clone for SharedReusable<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            item: Arc::clone(&self.item),
+        }
+    }
+}
+
+impl<T: ObjectPool> Borrow<T> for SharedReusable<T> {
+    #[inline]
+    fn borrow(&self) -> &T {
+        &self.item
+    }
+}
+
+impl<T: ObjectPool> AsRef<T> for SharedReusable<T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        &self.item
+    }
+}
+
+impl<T: ObjectPool> Deref for SharedReusable<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.item
+    }
+}
+
+impl<T: ObjectPool + fmt::Debug> fmt::Debug for SharedReusable<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ObjectPool + fmt::Display> fmt::Display for SharedReusable<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+/// A weak handle to a [`SharedReusable`] object, created with
+/// [`SharedReusable::downgrade`]. The handle can observe the object without
+/// keeping it checked out: once the last [`SharedReusable`] clone is dropped
+/// the object is returned to the pool as usual, and the handle can't be
+/// upgraded anymore. This is useful for caches and debugging tools that peek
+/// at live pooled objects.
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::prelude::*;
+/// use derivable_object_pool::SharedReusable;
+///
+/// #[derive(Default, ObjectPool)]
+/// struct Session(u64);
+///
+/// fn main() {
+///     let session = Session::new().into_shared();
+///     let weak = SharedReusable::downgrade(&session);
+///     assert_eq!(weak.upgrade().unwrap().0, 0);
+///
+///     drop(session); // the session is returned to the pool
+///     assert!(weak.upgrade().is_none());
+///     assert_eq!(Session::pool().len(), 1);
+/// }
+/// ```
+pub struct WeakReusable<T: ObjectPool> {
+    item: Weak<Reusable<T>>,
+}
+
+impl<T: ObjectPool> WeakReusable<T> {
+    /// Creates a handle that never upgrades, as it observes no object.
+    #[must_use]
+    #[inline]
+    pub const fn new() -> Self {
+        Self { item: Weak::new() }
+    }
+
+    /// Returns a [`SharedReusable`] clone of the object if it is still
+    /// checked out, or `None` if it was already returned to the pool.
+    #[inline]
+    pub fn upgrade(&self) -> Option<SharedReusable<T>> {
+        self.item.upgrade().map(|item| SharedReusable { item })
+    }
+
+    /// Returns the number of [`SharedReusable`] clones sharing the object.
+    #[inline]
+    pub fn strong_count(&self) -> usize {
+        self.item.strong_count()
+    }
+
+    /// Returns `true` if both handles observe the same object.
+    #[inline]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        self.item.ptr_eq(&other.item)
+    }
+}
+
+impl<T: ObjectPool> Default for WeakReusable<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ObjectPool> Clone for WeakReusable<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            item: Weak::clone(&self.item),
+        }
+    }
+}
+
+impl<T: ObjectPool> fmt::Debug for WeakReusable<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("(WeakReusable)")
+    }
+}
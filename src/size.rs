@@ -0,0 +1,63 @@
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::mem::size_of;
+
+/// Reports the number of bytes an object owns on the heap. This is used by the
+/// pool to guard against storing objects that grew too much (see
+/// [`Pool::with_max_object_size`]), so a single giant request can't fill the
+/// pool with huge buffers, and to enforce a budget on the total heap size of
+/// the idle objects (see [`Pool::with_max_bytes`]).
+///
+/// [`Pool::with_max_object_size`]: crate::Pool::with_max_object_size
+/// [`Pool::with_max_bytes`]: crate::Pool::with_max_bytes
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::prelude::*;
+///
+/// #[derive(Default, ObjectPool)]
+/// struct Message {
+///     header: String,
+///     body: Vec<u8>,
+/// }
+///
+/// impl HeapSize for Message {
+///     fn heap_size(&self) -> usize {
+///         self.header.heap_size() + self.body.heap_size()
+///     }
+/// }
+/// ```
+pub trait HeapSize {
+    /// Returns the number of bytes owned by the object on the heap.
+    fn heap_size(&self) -> usize;
+}
+
+impl<T> HeapSize for Vec<T> {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<T>()
+    }
+}
+
+impl<T> HeapSize for VecDeque<T> {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<T>()
+    }
+}
+
+impl HeapSize for String {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
+}
+
+impl<T> HeapSize for Box<[T]> {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.len() * size_of::<T>()
+    }
+}
@@ -0,0 +1,236 @@
+use alloc::boxed::Box;
+#[cfg(feature = "std")]
+use core::cell::Cell;
+use core::cell::UnsafeCell;
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+use core::ptr::{null_mut, slice_from_raw_parts_mut};
+use core::slice;
+use core::sync::atomic::{AtomicPtr, AtomicU8, AtomicUsize, Ordering};
+
+/// The slot doesn't hold an object.
+const EMPTY: u8 = 0;
+/// The slot is being written or read by a thread.
+const BUSY: u8 = 1;
+/// The slot holds an object.
+const FULL: u8 = 2;
+
+/// A slot of the [`Slots`] free-list.
+struct Slot<T> {
+    state: AtomicU8,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+impl<T> Slot<T> {
+    /// Moves the object into the slot if it is empty, counting it in the
+    /// occupied slots before the slot is published as full.
+    #[inline]
+    fn try_push(&self, item: T, occupied: &AtomicUsize) -> Result<(), T> {
+        if self.state.load(Ordering::Relaxed) != EMPTY
+            || self
+                .state
+                .compare_exchange(EMPTY, BUSY, Ordering::Acquire, Ordering::Relaxed)
+                .is_err()
+        {
+            return Err(item);
+        }
+        // SAFETY: The slot is exclusively owned while it is busy.
+        unsafe { (*self.value.get()).write(item) };
+        // Counted before the slot can be popped, so the count can't wrap
+        occupied.fetch_add(1, Ordering::Relaxed);
+        self.state.store(FULL, Ordering::Release);
+        Ok(())
+    }
+
+    /// Moves the object out of the slot if it is full, uncounting it from the
+    /// occupied slots before the slot is published as empty.
+    #[inline]
+    fn try_pop(&self, occupied: &AtomicUsize) -> Option<T> {
+        if self.state.load(Ordering::Relaxed) != FULL
+            || self
+                .state
+                .compare_exchange(FULL, BUSY, Ordering::Acquire, Ordering::Relaxed)
+                .is_err()
+        {
+            return None;
+        }
+        // SAFETY: The slot is exclusively owned while it is busy, and it was
+        // initialized as it was full.
+        let item = unsafe { (*self.value.get()).assume_init_read() };
+        // Uncounted before the slot can be pushed to again, so the count
+        // never exceeds the number of slots
+        occupied.fetch_sub(1, Ordering::Relaxed);
+        self.state.store(EMPTY, Ordering::Release);
+        Some(item)
+    }
+}
+
+/// A fixed-size lock-free free-list of objects. Each slot is claimed with a
+/// single compare-and-swap, so neither pushing nor popping ever blocks: a
+/// thread that finds a slot claimed by another thread just moves on to the
+/// next one. Unlike popping single nodes from a Treiber stack, this can't
+/// suffer from the ABA problem and doesn't need to reclaim memory, as the
+/// slots are allocated once (on first use) and never freed until the
+/// free-list is dropped.
+pub(crate) struct Slots<T> {
+    /// The first slot of the allocated slots, or null if not allocated yet.
+    slots: AtomicPtr<Slot<T>>,
+    len: usize,
+    /// The number of slots holding an object.
+    occupied: AtomicUsize,
+    /// The free-list owns the allocated slots.
+    _marker: PhantomData<Box<[Slot<T>]>>,
+}
+
+// SAFETY: The objects are only moved between threads through the slots, never
+// shared, so the free-list is thread-safe as long as the objects can be sent.
+unsafe impl<T: Send> Send for Slots<T> {}
+// SAFETY: See above.
+unsafe impl<T: Send> Sync for Slots<T> {}
+
+/// Returns the slot where the current thread starts looking for a slot, so
+/// threads spread over the free-list and tend to get back the objects they
+/// returned.
+#[cfg(feature = "std")]
+#[inline]
+fn start_hint() -> usize {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    std::thread_local! {
+        static HINT: Cell<usize> = Cell::new(NEXT.fetch_add(1, Ordering::Relaxed));
+    }
+    HINT.with(Cell::get)
+}
+
+/// Without threads to tell apart, all the searches start at the first slot.
+#[cfg(not(feature = "std"))]
+#[inline]
+fn start_hint() -> usize {
+    0
+}
+
+impl<T> Slots<T> {
+    /// Creates a new free-list with the specified number of slots.
+    #[inline]
+    pub(crate) const fn new(len: usize) -> Self {
+        Self {
+            slots: AtomicPtr::new(null_mut()),
+            len,
+            occupied: AtomicUsize::new(0),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Sets the number of slots of the free-list, which must not have been
+    /// used yet.
+    #[inline]
+    pub(crate) const fn set_capacity(&mut self, len: usize) {
+        self.len = len;
+    }
+
+    /// Returns the number of slots of the free-list.
+    #[inline]
+    pub(crate) const fn capacity(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the number of objects in the free-list, without blocking.
+    #[inline]
+    pub(crate) fn len(&self) -> usize {
+        self.occupied.load(Ordering::Relaxed)
+    }
+
+    /// Returns the slots, if they are allocated.
+    #[inline]
+    fn get(&self) -> Option<&[Slot<T>]> {
+        let ptr = self.slots.load(Ordering::Acquire);
+        // SAFETY: A non-null pointer points to `len` allocated slots, which
+        // live as long as the free-list.
+        (!ptr.is_null()).then(|| unsafe { slice::from_raw_parts(ptr, self.len) })
+    }
+
+    /// Returns the slots, allocating them if needed.
+    fn get_or_init(&self) -> &[Slot<T>] {
+        if let Some(slots) = self.get() {
+            return slots;
+        }
+        let slots: Box<[Slot<T>]> = (0..self.len)
+            .map(|_| Slot {
+                state: AtomicU8::new(EMPTY),
+                value: UnsafeCell::new(MaybeUninit::uninit()),
+            })
+            .collect();
+        let ptr = Box::into_raw(slots).cast::<Slot<T>>();
+        if let Err(current) =
+            self.slots
+                .compare_exchange(null_mut(), ptr, Ordering::AcqRel, Ordering::Acquire)
+        {
+            // Another thread allocated the slots first
+            // SAFETY: The slots were allocated above and never shared.
+            drop(unsafe { Box::from_raw(slice_from_raw_parts_mut(ptr, self.len)) });
+            // SAFETY: See `Slots::get`.
+            return unsafe { slice::from_raw_parts(current, self.len) };
+        }
+        // SAFETY: See `Slots::get`.
+        unsafe { slice::from_raw_parts(ptr, self.len) }
+    }
+
+    /// Calls the function with each slot, starting from the hint of the
+    /// current thread, until it returns `Some`.
+    #[inline]
+    fn find<R>(slots: &[Slot<T>], f: impl FnMut(&Slot<T>) -> Option<R>) -> Option<R> {
+        let start = start_hint() % slots.len();
+        let (tail, head) = slots.split_at(start);
+        head.iter().chain(tail).find_map(f)
+    }
+
+    /// Pushes an object into a free slot without blocking, giving it back if
+    /// all the slots are taken.
+    pub(crate) fn push(&self, item: T) -> Result<(), T> {
+        if self.len == 0 {
+            return Err(item);
+        }
+        let slots = self.get_or_init();
+        let mut item = Some(item);
+        let pushed = Self::find(slots, |slot| {
+            match slot.try_push(item.take().unwrap(), &self.occupied) {
+                Ok(()) => Some(()),
+                Err(rejected) => {
+                    item = Some(rejected);
+                    None
+                }
+            }
+        });
+        match pushed {
+            Some(()) => Ok(()),
+            None => Err(item.unwrap()),
+        }
+    }
+
+    /// Pops an object from a full slot without blocking.
+    pub(crate) fn pop(&self) -> Option<T> {
+        let slots = self.get()?;
+        Self::find(slots, |slot| slot.try_pop(&self.occupied))
+    }
+
+    /// Takes all the objects of the free-list, calling the function with each
+    /// of them. Objects concurrently pushed may be missed.
+    pub(crate) fn drain(&self, f: impl FnMut(T)) {
+        if let Some(slots) = self.get() {
+            slots
+                .iter()
+                .filter_map(|slot| slot.try_pop(&self.occupied))
+                .for_each(f);
+        }
+    }
+}
+
+impl<T> Drop for Slots<T> {
+    fn drop(&mut self) {
+        self.drain(drop);
+        let ptr = *self.slots.get_mut();
+        if !ptr.is_null() {
+            // SAFETY: The slots were allocated by `Slots::get_or_init`.
+            drop(unsafe { Box::from_raw(slice_from_raw_parts_mut(ptr, self.len)) });
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::Pool;
+
+/// The idle objects of a [`Pool`] captured by [`Pool::snapshot`] (or
+/// [`Pool::snapshot_cloned`]), which can be put back with [`Pool::restore`].
+/// This resets the state of the pool between runs that must be deterministic
+/// (like simulations), as the restored pool hands out the same objects in the
+/// same order.
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::prelude::*;
+///
+/// fn main() {
+///     let pool = Pool::new(Vec::<u8>::new);
+///     pool.insert(vec![1]);
+///     pool.insert(vec![2]);
+///     let snapshot = pool.snapshot_cloned();
+///
+///     for _run in 0..2 {
+///         pool.restore(snapshot.clone());
+///         assert_eq!(pool.remove().unwrap(), [2]);
+///         pool.insert(vec![3]);
+///     }
+///     assert_eq!(snapshot.len(), 2);
+/// }
+/// ```
+pub struct PoolSnapshot<T> {
+    /// The objects with their reuse counts, the last one being the next to be
+    /// handed out.
+    items: Vec<(T, u32)>,
+}
+
+impl<T> PoolSnapshot<T> {
+    /// Returns the number of captured objects.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if no objects were captured.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns an iterator over the captured objects, the last one being the
+    /// next to be handed out once restored.
+    #[inline]
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &T> {
+        self.items.iter().map(|(item, _)| item)
+    }
+}
+
+impl<T: Clone> Clone for PoolSnapshot<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            items: self.items.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for PoolSnapshot<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> Pool<T> {
+    /// Captures the idle objects of the pool by draining it (see
+    /// [`PoolSnapshot`]). The pool is left empty.
+    #[inline]
+    pub fn snapshot(&self) -> PoolSnapshot<T> {
+        PoolSnapshot {
+            items: self.get_pool().take_with_reuses(),
+        }
+    }
+
+    /// Captures clones of the idle objects of the pool (see [`PoolSnapshot`]),
+    /// leaving the pool untouched.
+    pub fn snapshot_cloned(&self) -> PoolSnapshot<T>
+    where
+        T: Clone,
+    {
+        PoolSnapshot {
+            items: self
+                .get_pool()
+                .iter()
+                .map(|(item, reuses)| (item.clone(), reuses))
+                .collect(),
+        }
+    }
+
+    /// Replaces the idle objects of the pool with the ones of the snapshot,
+    /// which will be handed out in the same order as when they were captured.
+    /// The objects beyond the capacity or the byte budget of the pool are
+    /// discarded, as are the replaced objects (after releasing the lock).
+    pub fn restore(&self, snapshot: PoolSnapshot<T>) {
+        let mut pool = self.get_pool();
+        let mut discarded = pool.take();
+        for (item, reuses) in snapshot.items {
+            if let Err(item) = self.push_idle(&mut pool, item, reuses) {
+                discarded.push(item);
+            }
+        }
+        drop(pool);
+        for item in discarded {
+            self.discard(item);
+        }
+    }
+}
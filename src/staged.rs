@@ -0,0 +1,175 @@
+use core::future::Future;
+use core::marker::PhantomData;
+use core::mem::{forget, ManuallyDrop};
+use core::ops::{Deref, DerefMut};
+use core::sync::atomic::Ordering;
+
+use crate::stamp::Stamp;
+use crate::{ObjectPool, Reusable};
+
+/// An object whose return to the pool has been staged with
+/// [`Reusable::stage_return`]. The object is parked in a "cleaning" state: it
+/// is no longer in use, but it isn't stored in the pool either until the
+/// return is committed with [`StagedReturn::commit`] (or
+/// [`StagedReturn::commit_after`] once an async cleanup resolves). This is
+/// useful for objects that need asynchronous cleanup before being reused, like
+/// flushing to disk or awaiting a socket shutdown.
+///
+/// If the staged return is dropped without being committed, the object is
+/// dropped instead of being returned to the pool, as its cleanup may not have
+/// finished.
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::prelude::*;
+/// use std::future::Future;
+/// use std::pin::pin;
+/// use std::task::{Context, Poll, Waker};
+///
+/// #[derive(Default, ObjectPool)]
+/// struct Writer {
+///     pending: Vec<u8>,
+/// }
+///
+/// async fn flush(mut writer: Writer) -> Writer {
+///     writer.pending.clear(); // e.g. write the pending bytes to disk
+///     writer
+/// }
+///
+/// fn main() {
+///     let mut writer = Writer::new();
+///     writer.pending.extend_from_slice(b"data");
+///
+///     let staged = writer.stage_return();
+///     assert_eq!(Writer::pool().staged(), 1);
+///     assert_eq!(Writer::pool().len(), 0);
+///
+///     // Usually awaited in an async runtime
+///     let mut commit = pin!(staged.commit_after(flush));
+///     let mut cx = Context::from_waker(Waker::noop());
+///     assert_eq!(commit.as_mut().poll(&mut cx), Poll::Ready(true));
+///     assert_eq!(Writer::pool().staged(), 0);
+///     assert_eq!(Writer::pool().len(), 1);
+/// }
+/// ```
+#[must_use = "dropping a staged return drops the object, use `commit` to return it"]
+pub struct StagedReturn<T: ObjectPool> {
+    /// The staged object.
+    item: ManuallyDrop<T>,
+    /// The bookkeeping of the checkout of the object.
+    stamp: Stamp,
+}
+
+impl<T: ObjectPool> StagedReturn<T> {
+    /// Stages the return of the object checked out with the specified stamp.
+    #[inline]
+    pub(crate) fn new(item: T, stamp: Stamp) -> Self {
+        stamp.owner::<T>().staged.fetch_add(1, Ordering::Relaxed);
+        Self {
+            item: ManuallyDrop::new(item),
+            stamp,
+        }
+    }
+
+    /// Takes the object out of the staged state.
+    #[inline]
+    fn take(mut self) -> T {
+        self.stamp
+            .owner::<T>()
+            .staged
+            .fetch_sub(1, Ordering::Relaxed);
+        let item = unsafe { ManuallyDrop::take(&mut self.item) };
+        forget(self);
+        item
+    }
+
+    /// Commits the return, storing the object in the pool. Returns `true` if
+    /// the object was pooled or `false` if it was dropped (for example because
+    /// the pool is full or it was invalidated since the object was acquired).
+    #[inline]
+    pub fn commit(self) -> bool {
+        let stamp = self.stamp;
+        stamp.owner::<T>().release_stamped(self.take(), stamp)
+    }
+
+    /// Runs the asynchronous cleanup on the object and commits the return once
+    /// it resolves. The cleanup takes the ownership of the object and must
+    /// hand it back. See [`StagedReturn::commit`] for the returned value. If
+    /// the future is dropped or the cleanup panics, the checkout ends without
+    /// returning the object.
+    pub async fn commit_after<F, Fut>(self, cleanup: F) -> bool
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = T>,
+    {
+        let stamp = self.stamp;
+        let item = self.take();
+        // Ends the checkout if the cleanup panics or the future is dropped
+        let checkout = Checkout::<T>::new(stamp);
+        let item = cleanup(item).await;
+        forget(checkout);
+        stamp.owner::<T>().release_stamped(item, stamp)
+    }
+
+    /// Cancels the staged return, handing the object back inside a
+    /// [`Reusable`] wrapper.
+    #[inline]
+    pub fn cancel(self) -> Reusable<T> {
+        let stamp = self.stamp;
+        Reusable::stamped(self.take(), stamp)
+    }
+}
+
+impl<T: ObjectPool> Deref for StagedReturn<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.item
+    }
+}
+
+impl<T: ObjectPool> DerefMut for StagedReturn<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.item
+    }
+}
+
+impl<T: ObjectPool> Drop for StagedReturn<T> {
+    #[inline]
+    fn drop(&mut self) {
+        let pool = self.stamp.owner::<T>();
+        pool.staged.fetch_sub(1, Ordering::Relaxed);
+        pool.detach_stamped(self.stamp);
+        unsafe { ManuallyDrop::drop(&mut self.item) };
+    }
+}
+
+/// The checkout of an object taken out of the staged state, which is ended
+/// when the guard is dropped unless it is forgotten once the object is
+/// returned to the pool.
+struct Checkout<T: ObjectPool> {
+    /// The bookkeeping of the checkout of the object.
+    stamp: Stamp,
+    /// The checkout belongs to the pool of the objects.
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: ObjectPool> Checkout<T> {
+    /// Guards the checkout with the specified stamp.
+    #[inline]
+    const fn new(stamp: Stamp) -> Self {
+        Self {
+            stamp,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: ObjectPool> Drop for Checkout<T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.stamp.owner::<T>().detach_stamped(self.stamp);
+    }
+}
@@ -0,0 +1,67 @@
+use crate::{ObjectPool, Pool};
+
+/// The bookkeeping of a checked-out object, which its guard hands back to the
+/// pool along with the object when it is returned.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Stamp {
+    /// The epoch of the pool when the object was checked out.
+    pub(crate) epoch: u64,
+    /// The number of times the object was handed out again by the pool.
+    pub(crate) reuses: u32,
+    /// The identifier of the checkout, if it is tracked.
+    #[cfg(feature = "leak-detection")]
+    pub(crate) checkout: u64,
+    /// The address of the pool the object was checked out from, if it is
+    /// bound to it, as the pool can be replaced meanwhile (see
+    /// [`Pool::scoped`]).
+    #[cfg(feature = "std")]
+    owner: usize,
+}
+
+impl Stamp {
+    #[inline]
+    pub(crate) const fn new(epoch: u64, reuses: u32) -> Self {
+        Self {
+            epoch,
+            reuses,
+            #[cfg(feature = "leak-detection")]
+            checkout: 0,
+            #[cfg(feature = "std")]
+            owner: 0,
+        }
+    }
+
+    /// Takes the epoch and reuse count of a new object handed to the same
+    /// guard, which stays the same checkout.
+    #[inline]
+    pub(crate) fn renew(&mut self, stamp: Stamp) {
+        self.epoch = stamp.epoch;
+        self.reuses = stamp.reuses;
+    }
+
+    /// Binds the checkout to the pool the object was checked out from (as
+    /// returned by [`ObjectPool::pool`]), so it is returned to it.
+    #[inline]
+    pub(crate) fn bind<T: ObjectPool>(&mut self, pool: &Pool<T>) {
+        #[cfg(feature = "std")]
+        {
+            self.owner = pool as *const Pool<T> as usize;
+        }
+        #[cfg(not(feature = "std"))]
+        let _ = pool;
+    }
+
+    /// Returns the pool the object was checked out from, which is the current
+    /// pool of the objects if the checkout isn't bound to a pool.
+    #[inline]
+    pub(crate) fn owner<'a, T: ObjectPool>(&self) -> &'a Pool<T> {
+        #[cfg(feature = "std")]
+        if self.owner != 0 {
+            // SAFETY: Only the pools of the objects are bound, which live for
+            // the rest of the program (replacements included, as they are
+            // leaked), and the stamps only travel with objects of that type.
+            return unsafe { &*(self.owner as *const Pool<T>) };
+        }
+        T::pool()
+    }
+}
@@ -0,0 +1,260 @@
+use core::borrow::{Borrow, BorrowMut};
+use core::mem::{forget, ManuallyDrop, MaybeUninit};
+use core::ops::{Deref, DerefMut};
+
+use crate::sync::Mutex;
+
+/// The fixed-size storage of a [`StaticPool`].
+struct Storage<T, const N: usize> {
+    items: [MaybeUninit<T>; N],
+    len: usize,
+}
+
+impl<T, const N: usize> Storage<T, N> {
+    const fn new() -> Self {
+        Self {
+            items: [const { MaybeUninit::uninit() }; N],
+            len: 0,
+        }
+    }
+
+    /// Pushes an object, giving it back if the storage is full.
+    #[inline]
+    fn push(&mut self, item: T) -> Result<(), T> {
+        match self.items.get_mut(self.len) {
+            Some(slot) => {
+                slot.write(item);
+                self.len += 1;
+                Ok(())
+            }
+            None => Err(item),
+        }
+    }
+
+    /// Pops the last pushed object.
+    #[inline]
+    fn pop(&mut self) -> Option<T> {
+        self.len = self.len.checked_sub(1)?;
+        // SAFETY: The objects below `len` are initialized, and the popped one
+        // is no longer considered initialized.
+        Some(unsafe { self.items[self.len].assume_init_read() })
+    }
+
+    /// Drops all the objects.
+    fn clear(&mut self) {
+        while let Some(item) = self.pop() {
+            drop(item);
+        }
+    }
+}
+
+impl<T, const N: usize> Drop for Storage<T, N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+/// A pool backed by a fixed-size array instead of a [`Vec`](alloc::vec::Vec),
+/// which stores up to `N` idle objects without any heap allocation. This gives
+/// deterministic memory usage for embedded and firmware use cases (combined
+/// with the `spin` feature in `no_std` environments).
+///
+/// The pool can be derived for a type with
+/// [`#[pool(static_capacity = N)]`](derive@crate::ObjectPool), which implements
+/// [`StaticObjectPool`] for the type.
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::StaticPool;
+///
+/// static POOL: StaticPool<[u8; 64], 2> = StaticPool::new(|| [0; 64]);
+///
+/// fn main() {
+///     let objects = [POOL.acquire(), POOL.acquire(), POOL.acquire()];
+///     drop(objects);
+///     assert_eq!(POOL.len(), 2); // the third object didn't fit
+/// }
+/// ```
+pub struct StaticPool<T, const N: usize> {
+    storage: Mutex<Storage<T, N>>,
+    generator: fn() -> T,
+}
+
+impl<T, const N: usize> StaticPool<T, N> {
+    /// Creates a new empty pool. The pool will use the specified generator
+    /// function to create new objects.
+    #[must_use]
+    #[inline]
+    pub const fn new(generator: fn() -> T) -> Self {
+        Self {
+            storage: Mutex::new(Storage::new()),
+            generator,
+        }
+    }
+
+    /// Returns the maximum number of idle objects of the pool.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Acquires an object from the pool, or creates a new one if the pool is
+    /// empty. The object is returned to the pool when the wrapper is dropped.
+    #[inline]
+    pub fn acquire(&self) -> StaticReusable<'_, T, N> {
+        StaticReusable::new(self.take_or_generate(), self)
+    }
+
+    /// Removes an object from the pool or, if the pool is empty, creates a new
+    /// one using the generator function.
+    #[inline]
+    pub fn take_or_generate(&self) -> T {
+        // The lock must be released before calling the generator
+        let item = self.remove();
+        item.unwrap_or_else(self.generator)
+    }
+
+    /// Removes an object from the pool, if any.
+    #[inline]
+    pub fn remove(&self) -> Option<T> {
+        self.storage.lock().pop()
+    }
+
+    /// Inserts an object into the pool, giving it back if the pool is full.
+    #[inline]
+    pub fn try_insert(&self, item: T) -> Result<(), T> {
+        self.storage.lock().push(item)
+    }
+
+    /// Inserts an object into the pool, dropping it if the pool is full.
+    #[inline]
+    pub fn insert(&self, item: T) {
+        drop(self.try_insert(item));
+    }
+
+    /// Returns the number of objects in the pool.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.storage.lock().len
+    }
+
+    /// Returns `true` if the pool is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all objects from the pool.
+    #[inline]
+    pub fn clear(&self) {
+        self.storage.lock().clear();
+    }
+}
+
+/// Trait for types pooled in a [`StaticPool`], generated by
+/// [`#[pool(static_capacity = N)]`](derive@crate::ObjectPool).
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::prelude::*;
+///
+/// #[derive(Default, ObjectPool)]
+/// #[pool(static_capacity = 4)]
+/// struct Frame([u8; 32]);
+///
+/// fn main() {
+///     let mut frame = Frame::new();
+///     frame.0[0] = 1;
+///     drop(frame);
+///     assert_eq!(Frame::pool().len(), 1);
+/// }
+/// ```
+pub trait StaticObjectPool<const N: usize>: Sized + 'static {
+    /// Returns a reference to the pool of the type.
+    fn pool() -> &'static StaticPool<Self, N>;
+
+    /// Acquires an object from the pool of the type, or creates a new one if
+    /// the pool is empty.
+    #[inline]
+    fn new() -> StaticReusable<'static, Self, N> {
+        Self::pool().acquire()
+    }
+}
+
+/// A wrapper for an object acquired from a [`StaticPool`] that returns the
+/// object to the pool when it is dropped (or drops it if the pool is full).
+pub struct StaticReusable<'a, T, const N: usize> {
+    item: ManuallyDrop<T>,
+    pool: &'a StaticPool<T, N>,
+}
+
+impl<'a, T, const N: usize> StaticReusable<'a, T, N> {
+    #[inline]
+    fn new(item: T, pool: &'a StaticPool<T, N>) -> Self {
+        Self {
+            item: ManuallyDrop::new(item),
+            pool,
+        }
+    }
+
+    /// Returns the owned object inside the wrapper without returning it to
+    /// the pool.
+    #[inline]
+    pub fn into_inner(mut self) -> T {
+        let ret = unsafe { ManuallyDrop::take(&mut self.item) };
+        forget(self);
+        ret
+    }
+}
+
+impl<T, const N: usize> Borrow<T> for StaticReusable<'_, T, N> {
+    #[inline]
+    fn borrow(&self) -> &T {
+        &self.item
+    }
+}
+
+impl<T, const N: usize> BorrowMut<T> for StaticReusable<'_, T, N> {
+    #[inline]
+    fn borrow_mut(&mut self) -> &mut T {
+        &mut self.item
+    }
+}
+
+impl<T, const N: usize> AsRef<T> for StaticReusable<'_, T, N> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        &self.item
+    }
+}
+
+impl<T, const N: usize> AsMut<T> for StaticReusable<'_, T, N> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut T {
+        &mut self.item
+    }
+}
+
+impl<T, const N: usize> Deref for StaticReusable<'_, T, N> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.item
+    }
+}
+
+impl<T, const N: usize> DerefMut for StaticReusable<'_, T, N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.item
+    }
+}
+
+impl<T, const N: usize> Drop for StaticReusable<'_, T, N> {
+    #[inline]
+    fn drop(&mut self) {
+        let item = unsafe { ManuallyDrop::take(&mut self.item) };
+        self.pool.insert(item);
+    }
+}
@@ -0,0 +1,68 @@
+// The locks used internally by the pools, which are the ones of `std::sync`
+// or, with the `parking_lot` feature, the ones of the `parking_lot` crate
+// (smaller, faster under contention and without poisoning). Without the
+// standard library, the spin locks of the `spin` crate are used instead.
+
+#[cfg(feature = "parking_lot")]
+pub(crate) use parking_lot::{Mutex, MutexGuard, RwLock};
+
+#[cfg(all(feature = "spin", not(feature = "parking_lot")))]
+pub(crate) use spin::{Mutex, MutexGuard, RwLock};
+
+#[cfg(all(feature = "std", not(any(feature = "spin", feature = "parking_lot"))))]
+pub(crate) use self::std_sync::{Mutex, MutexGuard, RwLock};
+
+#[cfg(not(any(feature = "std", feature = "spin")))]
+compile_error!("`derivable-object-pool` requires either the `std` or the `spin` feature");
+
+#[cfg(all(feature = "std", not(any(feature = "spin", feature = "parking_lot"))))]
+mod std_sync {
+    pub(crate) use std::sync::MutexGuard;
+    use std::sync::{RwLockReadGuard, RwLockWriteGuard};
+
+    /// A [`std::sync::Mutex`] with the locking interface of `parking_lot`.
+    pub(crate) struct Mutex<T>(std::sync::Mutex<T>);
+
+    impl<T> Mutex<T> {
+        #[inline]
+        pub(crate) const fn new(value: T) -> Self {
+            Self(std::sync::Mutex::new(value))
+        }
+
+        /// Locks the mutex, panicking if it is poisoned.
+        #[inline]
+        pub(crate) fn lock(&self) -> MutexGuard<'_, T> {
+            self.0.lock().unwrap()
+        }
+
+        /// Tries to lock the mutex without blocking, returning `None` if it is
+        /// locked (or poisoned).
+        #[cfg(feature = "tracing")]
+        #[inline]
+        pub(crate) fn try_lock(&self) -> Option<MutexGuard<'_, T>> {
+            self.0.try_lock().ok()
+        }
+    }
+
+    /// A [`std::sync::RwLock`] with the locking interface of `parking_lot`.
+    pub(crate) struct RwLock<T>(std::sync::RwLock<T>);
+
+    impl<T> RwLock<T> {
+        #[inline]
+        pub(crate) const fn new(value: T) -> Self {
+            Self(std::sync::RwLock::new(value))
+        }
+
+        /// Locks the lock with shared access, panicking if it is poisoned.
+        #[inline]
+        pub(crate) fn read(&self) -> RwLockReadGuard<'_, T> {
+            self.0.read().unwrap()
+        }
+
+        /// Locks the lock with exclusive access, panicking if it is poisoned.
+        #[inline]
+        pub(crate) fn write(&self) -> RwLockWriteGuard<'_, T> {
+            self.0.write().unwrap()
+        }
+    }
+}
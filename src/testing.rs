@@ -0,0 +1,202 @@
+use core::any::type_name;
+#[cfg(feature = "leak-detection")]
+use core::fmt::Write;
+#[cfg(feature = "leak-detection")]
+use core::time::Duration;
+
+use crate::{ObjectPool, Pool};
+
+/// A pool checked by a [`PoolTestGuard`].
+struct Checked {
+    outstanding: fn() -> usize,
+    not_returned: fn(usize) -> String,
+    baseline: usize,
+}
+
+/// A guard for tests that use global derived pools. Each pool registered in
+/// the guard is cleared when it is registered, and the guard asserts that no
+/// objects acquired from it during the test are still outstanding (held in
+/// guards like [`Reusable`](crate::Reusable) or leaked) when the guard is
+/// dropped. The guard is usually created with [`pool_test_guard!`](crate::pool_test_guard!).
+///
+/// The assertion is skipped if the test is already panicking. As the pools
+/// are global, tests running in parallel that use the same pools can disturb
+/// each other.
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::prelude::*;
+/// use derivable_object_pool::pool_test_guard;
+///
+/// #[derive(Default, ObjectPool)]
+/// struct Buffer(Vec<u8>);
+///
+/// fn main() {
+///     let _guard = pool_test_guard!(Buffer);
+///     let buffer = Buffer::new();
+///     drop(buffer); // without this, the guard panics at the end of the scope
+/// }
+/// ```
+#[must_use = "the pools are only checked when the guard is dropped"]
+#[derive(Default)]
+pub struct PoolTestGuard {
+    pools: Vec<Checked>,
+}
+
+impl PoolTestGuard {
+    /// Creates a new guard that doesn't check any pool.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Clears the pool of the specified type and registers it to be checked
+    /// when the guard is dropped.
+    pub fn with<T: ObjectPool>(mut self) -> Self {
+        let pool = T::pool();
+        pool.clear();
+        self.pools.push(Checked {
+            outstanding: || T::pool().outstanding(),
+            not_returned: |outstanding| T::pool().not_returned(outstanding),
+            baseline: pool.outstanding(),
+        });
+        self
+    }
+}
+
+impl Drop for PoolTestGuard {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            return;
+        }
+        for checked in &self.pools {
+            let outstanding = (checked.outstanding)().saturating_sub(checked.baseline);
+            assert!(outstanding == 0, "{}", (checked.not_returned)(outstanding));
+        }
+    }
+}
+
+/// Creates a [`PoolTestGuard`] that clears the pools of the specified types
+/// and asserts that none of their objects are outstanding at the end of the
+/// test.
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::prelude::*;
+/// use derivable_object_pool::pool_test_guard;
+///
+/// #[derive(Default, ObjectPool)]
+/// struct Request(String);
+///
+/// #[derive(Default, ObjectPool)]
+/// struct Response(String);
+///
+/// fn main() {
+///     let _guard = pool_test_guard!(Request, Response);
+///     let request = Request::new();
+///     let response = Response::new();
+///     drop((request, response));
+/// }
+/// ```
+#[macro_export]
+macro_rules! pool_test_guard {
+    ($($ty:ty),* $(,)?) => {
+        $crate::PoolTestGuard::new()$(.with::<$ty>())*
+    };
+}
+
+impl<T> Pool<T> {
+    /// Asserts that all the objects acquired from the pool were returned to
+    /// it, for use at the end of tests to verify that no code path leaks
+    /// pooled objects (for example with [`Reusable::into_inner`] or
+    /// [`forget`]). The panic message lists the objects that are still
+    /// checked out with the `leak-detection` feature (see
+    /// `Pool::report_overdue`).
+    ///
+    /// [`Reusable::into_inner`]: crate::Reusable::into_inner
+    /// [`forget`]: core::mem::forget
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// #[derive(Default, ObjectPool)]
+    /// struct Buffer(Vec<u8>);
+    ///
+    /// fn main() {
+    ///     let buffer = Buffer::new();
+    ///     drop(buffer);
+    ///     Buffer::pool().assert_all_returned();
+    ///
+    ///     std::mem::forget(Buffer::new());
+    ///     let leaked = std::panic::catch_unwind(|| Buffer::pool().assert_all_returned());
+    ///     assert!(leaked.is_err());
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if any object acquired from the pool is still checked out.
+    #[track_caller]
+    pub fn assert_all_returned(&self) {
+        let outstanding = self.outstanding();
+        assert!(outstanding == 0, "{}", self.not_returned(outstanding));
+    }
+
+    /// Returns a guard that asserts that all the objects acquired from the
+    /// pool while it exists were returned to it when the guard is dropped,
+    /// like [`Pool::assert_all_returned`]. The assertion is skipped if the
+    /// thread is already panicking.
+    ///
+    /// # Example
+    /// ```
+    /// use derivable_object_pool::prelude::*;
+    ///
+    /// fn main() {
+    ///     let pool = PoolHandle::new(Pool::new(String::new));
+    ///     let _guard = pool.assert_all_returned_on_drop();
+    ///     let name = pool.acquire();
+    ///     drop(name); // without this, the guard panics at the end of the scope
+    /// }
+    /// ```
+    #[inline]
+    pub fn assert_all_returned_on_drop(&self) -> AllReturnedGuard<'_, T> {
+        AllReturnedGuard {
+            pool: self,
+            baseline: self.outstanding(),
+        }
+    }
+
+    /// Describes the objects of the pool that were not returned to it.
+    fn not_returned(&self, outstanding: usize) -> String {
+        #[allow(unused_mut)]
+        let mut message = format!(
+            "{outstanding} object(s) of {} were not returned to the pool",
+            type_name::<T>()
+        );
+        #[cfg(feature = "leak-detection")]
+        for overdue in self.report_overdue(Duration::ZERO) {
+            let _ = write!(message, "\n  - {overdue}");
+        }
+        message
+    }
+}
+
+/// A guard that asserts that all the objects acquired from a pool while it
+/// exists were returned to it when it is dropped, created with
+/// [`Pool::assert_all_returned_on_drop`].
+#[must_use = "the pool is only checked when the guard is dropped"]
+pub struct AllReturnedGuard<'a, T> {
+    pool: &'a Pool<T>,
+    baseline: usize,
+}
+
+impl<T> Drop for AllReturnedGuard<'_, T> {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            return;
+        }
+        let outstanding = self.pool.outstanding().saturating_sub(self.baseline);
+        assert!(outstanding == 0, "{}", self.pool.not_returned(outstanding));
+    }
+}
@@ -0,0 +1,86 @@
+use core::num::Wrapping;
+use core::ptr::write_bytes;
+
+/// Marks types for which the all-zero byte pattern is a valid value, so pools
+/// can zero their objects with [`Pool::zero_on_acquire`].
+///
+/// [`Pool::zero_on_acquire`]: crate::Pool::zero_on_acquire
+///
+/// # Safety
+///
+/// Implementors must guarantee that a value with all its bytes set to zero is
+/// a valid value of the type (e.g. this is not the case of references,
+/// [`NonZeroU32`](std::num::NonZeroU32) or most enums).
+///
+/// # Example
+/// ```
+/// use derivable_object_pool::prelude::*;
+///
+/// #[derive(Clone, Copy, Default, ObjectPool)]
+/// #[pool(zero_on_acquire)]
+/// struct Scratch {
+///     samples: [f32; 32],
+///     len: usize,
+/// }
+///
+/// // SAFETY: All the fields are valid when zeroed.
+/// unsafe impl Zeroable for Scratch {}
+///
+/// fn main() {
+///     let mut scratch = Scratch::new();
+///     scratch.samples[0] = 1.0;
+///     scratch.len = 1;
+///     drop(scratch);
+///
+///     let scratch = Scratch::new();
+///     assert_eq!(scratch.samples[0], 0.0);
+///     assert_eq!(scratch.len, 0);
+/// }
+/// ```
+pub unsafe trait Zeroable {}
+
+macro_rules! impl_zeroable {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            // SAFETY: The zero bit pattern is a valid value of the type.
+            unsafe impl Zeroable for $ty {}
+        )+
+    };
+}
+
+impl_zeroable!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64
+);
+
+// SAFETY: An array is zeroed if all its elements are zeroed.
+unsafe impl<T: Zeroable, const N: usize> Zeroable for [T; N] {}
+// SAFETY: `Wrapping` is transparent.
+unsafe impl<T: Zeroable> Zeroable for Wrapping<T> {}
+// SAFETY: Null pointers are valid pointers.
+unsafe impl<T> Zeroable for *const T {}
+// SAFETY: Null pointers are valid pointers.
+unsafe impl<T> Zeroable for *mut T {}
+
+/// Sets all the bytes of the object to zero.
+#[inline]
+pub(crate) fn zero<T: Zeroable + Copy>(item: &mut T) {
+    // SAFETY: The zero bit pattern is a valid value of `T` and, as `T` is
+    // `Copy`, the overwritten value doesn't need to be dropped.
+    unsafe { write_bytes(item as *mut T, 0, 1) };
+}
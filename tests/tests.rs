@@ -1,5 +1,33 @@
 #![allow(unused)]
+#[cfg(feature = "std")]
+use derivable_object_pool::pool_test_guard;
 use derivable_object_pool::prelude::*;
+use derivable_object_pool::{
+    AcquireErrorKind, BufferPool, MappedReusable, RestoreOrder, SharedReusable, WeakReusable,
+};
+
+#[derive(Default, ObjectPool)]
+#[pool(test_roundtrip)]
+struct RoundTrip(Vec<u8>);
+
+#[derive(Default, PartialEq, ObjectPool)]
+#[pool(recycle, test_roundtrip = |line| line.0.push_str("dirty"))]
+struct RecycledLine(String);
+
+impl Recycle for RecycledLine {
+    fn recycle(&mut self) {
+        self.0.clear();
+    }
+}
+
+#[derive(Default, PartialEq, ObjectPool)]
+struct DirtyLine(String);
+
+#[test]
+#[should_panic(expected = "returned object was not reset like a new object")]
+fn roundtrip_test_catches_objects_not_reset() {
+    DirtyLine::pool().roundtrip_test(Some(|line| line.0.push('x')), |item, new| item == new);
+}
 
 #[derive(Default, ObjectPool)]
 struct Test {
@@ -22,3 +50,1672 @@ fn new_objects() {
 
     assert_eq!(2, Test::pool().len());
 }
+
+#[test]
+fn handles_share_pool() {
+    let handle = Test::handle();
+    assert!(handle.same_pool(&Test::handle()));
+
+    let owned = PoolHandle::new(Pool::new(Test::default));
+    let clone = owned.clone();
+    assert!(owned.same_pool(&clone));
+    assert!(!owned.same_pool(&handle));
+
+    let obj = clone.acquire();
+    drop(clone);
+    drop(obj);
+    assert_eq!(1, owned.len());
+}
+
+#[global_pool]
+static BUF_POOL: Pool<Vec<u8>> = Pool::new(Vec::new);
+
+#[test]
+fn global_pool_accessors() {
+    let mut buf = buf_pool::handle().acquire();
+    buf.extend_from_slice(b"hello");
+    drop(buf);
+    assert_eq!(1, buf_pool::pool().len());
+    assert_eq!(b"hello", &buf_pool::handle().acquire()[..]);
+
+    let pool = derivable_object_pool::registry::iter()
+        .find(|pool| pool.name() == "BUF_POOL")
+        .unwrap();
+    assert_eq!(std::any::type_name::<Vec<u8>>(), pool.type_name());
+}
+
+#[derive(ObjectPool)]
+#[pool(reuse_only)]
+struct Handle(u32);
+
+#[test]
+fn reuse_only_pool() {
+    assert!(Handle::pool().is_reuse_only());
+    assert_eq!(
+        Err(AcquireErrorKind::Exhausted),
+        Handle::acquire().map(|h| h.0).map_err(|err| err.kind())
+    );
+
+    Handle::pool().insert(Handle(7));
+    let handle = Handle::acquire().unwrap();
+    assert_eq!(7, handle.0);
+}
+
+#[derive(Default, ObjectPool)]
+#[pool(capacity = 2)]
+struct Bounded(u32);
+
+#[test]
+fn bounded_capacity() {
+    let objs: Vec<_> = (0..4).map(|_| Bounded::new()).collect();
+    drop(objs);
+    assert_eq!(2, Bounded::pool().len());
+    assert_eq!(2, Bounded::pool().capacity());
+}
+
+#[derive(Default, ObjectPool)]
+#[pool(growth = 4, capacity = 3)]
+struct Grown(u32);
+
+#[test]
+fn growth_creates_objects_in_chunks() {
+    let first = Grown::new();
+    assert_eq!(3, Grown::pool().len());
+    #[cfg(feature = "counters")]
+    assert_eq!(4, Grown::pool().stats().created);
+    let rest: Vec<_> = (0..3).map(|_| Grown::new()).collect();
+    assert!(Grown::pool().is_empty());
+    // The batch acquisitions create exactly the missing objects
+    let batch = Grown::new_n(2);
+    assert!(Grown::pool().is_empty());
+    drop((first, rest, batch));
+    assert_eq!(3, Grown::pool().len());
+    assert_eq!(4, Grown::pool().debug_state().growth);
+}
+
+#[derive(Default, ObjectPool)]
+#[pool(prefill = 4)]
+struct Prefilled(u32);
+
+#[derive(Default, ObjectPool)]
+#[pool(prefill = 2)]
+struct Warmed(u32);
+
+#[test]
+fn prefill_fills_the_pool_on_first_use() {
+    assert_eq!(4, Prefilled::pool().len());
+    let first = Prefilled::new();
+    assert_eq!(3, Prefilled::pool().len());
+    drop(first);
+    assert_eq!(4, Prefilled::pool().debug_state().prefill);
+
+    Warmed::warm_pool();
+    let all: Vec<_> = (0..3).map(|_| Warmed::new()).collect();
+    drop(all);
+    // Filled only once
+    assert_eq!(3, Warmed::pool().len());
+}
+
+#[test]
+fn set_max_idle_trims_the_pool() {
+    let pool = Pool::new(Vec::<u8>::new).with_capacity(4);
+    pool.prefill(4);
+    assert_eq!(0, pool.set_max_idle(8));
+    pool.prefill(4);
+    assert_eq!(8, pool.len());
+    assert_eq!(5, pool.set_max_idle(3));
+    assert_eq!(3, pool.len());
+    pool.insert(Vec::new());
+    assert_eq!(3, pool.len());
+    assert_eq!(Some(3), pool.debug_state().capacity);
+}
+
+#[derive(ObjectPool)]
+#[generator_try(Connection::connect, error = String)]
+struct Connection(u32);
+
+impl Connection {
+    fn connect() -> Result<Self, String> {
+        Err(String::from("connection refused"))
+    }
+}
+
+#[test]
+fn fallible_generator() {
+    let err = Connection::try_new().err();
+    assert_eq!(Some("connection refused"), err.as_deref());
+
+    Connection::pool().insert(Connection(3));
+    assert_eq!(3, Connection::try_new().unwrap().0);
+}
+
+#[derive(ObjectPool)]
+#[pool(generator_try = Session::open, error = String, capacity = 1)]
+struct Session(u32);
+
+impl Session {
+    fn open() -> Result<Self, String> {
+        Ok(Session(5))
+    }
+}
+
+#[derive(ObjectPool)]
+#[pool(generator = Configured::make, capacity = 1)]
+#[pool(min_idle = 1)]
+struct Configured(u32);
+
+impl Configured {
+    fn make() -> Self {
+        Configured(9)
+    }
+}
+
+#[test]
+fn unified_pool_attribute() {
+    assert_eq!(5, Session::try_new().unwrap().0);
+    assert_eq!(1, Session::pool().capacity());
+
+    assert_eq!(9, Configured::new().0);
+    assert_eq!(1, Configured::pool().capacity());
+    assert_eq!(1, Configured::pool().min_idle());
+}
+
+#[derive(Default, ObjectPool)]
+#[pool(lock_free = 4)]
+struct Scratch(Vec<u8>);
+
+#[test]
+fn lock_free_pool() {
+    let workers: Vec<_> = (0..4)
+        .map(|_| {
+            std::thread::spawn(|| {
+                for i in 0..1000 {
+                    let mut scratch = Scratch::new();
+                    scratch.0.push(i as u8);
+                }
+            })
+        })
+        .collect();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+    assert!(Scratch::pool().len() <= 4);
+
+    Scratch::pool().invalidate();
+    assert_eq!(Scratch::pool().len(), 0);
+    let scratch = Scratch::new();
+    drop(scratch);
+    assert_eq!(Scratch::pool().len(), 1);
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ObjectPool)]
+#[generator(Slot::empty)]
+#[pool(const_items = [Slot::A, Slot::A, Slot::B])]
+enum Slot {
+    Empty,
+    A,
+    B,
+}
+
+impl Slot {
+    fn empty() -> Self {
+        Slot::Empty
+    }
+}
+
+#[test]
+fn const_items() {
+    assert_eq!(Slot::pool().len(), 3);
+    let slots = [Slot::new(), Slot::new(), Slot::new(), Slot::new()];
+    assert_eq!(
+        slots.map(|slot| *slot),
+        [Slot::B, Slot::A, Slot::A, Slot::Empty]
+    );
+}
+
+#[cfg(feature = "tokio")]
+#[derive(Default, ObjectPool)]
+#[pool(async)]
+struct AsyncBuffer(Vec<u8>);
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn async_pool() {
+    let mut buffer = AsyncBuffer::acquire().await;
+    buffer.0.push(1);
+    drop(buffer);
+    assert_eq!(AsyncBuffer::pool().len().await, 1);
+
+    let buffer = AsyncBuffer::acquire().await;
+    assert_eq!(buffer.0, [1]);
+}
+
+#[derive(Default, ObjectPool)]
+struct Guarded(u32);
+
+#[cfg(feature = "std")]
+#[test]
+fn pool_test_guard() {
+    let guard = pool_test_guard!(Guarded);
+    let obj = Guarded::new();
+    drop(obj);
+    drop(guard);
+
+    let leaked = std::panic::catch_unwind(|| {
+        let _guard = pool_test_guard!(Guarded);
+        Guarded::new().into_inner()
+    });
+    assert!(leaked.is_err());
+}
+
+#[test]
+fn buffer_pool_size_classes() {
+    let pool = BufferPool::new();
+
+    let mut huge = pool.get(4 * 1024 * 1024);
+    assert!(huge.capacity() >= 4 * 1024 * 1024);
+    huge.push(1);
+    drop(huge);
+
+    // The huge buffer was shrunk to the largest class
+    let buffer = pool.get(100 * 1024);
+    assert!(buffer.is_empty());
+    assert!((1024 * 1024..4 * 1024 * 1024).contains(&buffer.capacity()));
+    drop(buffer);
+
+    // Small requests don't take buffers from larger classes
+    let small = pool.get(10);
+    assert!(small.capacity() < 64 * 1024);
+    drop(small);
+    assert_eq!(pool.len(), 2);
+
+    drop(pool.get(10).into_inner());
+    assert_eq!(pool.len(), 1);
+}
+
+pool! {
+    static NAME_POOL: String = String::new;
+}
+
+#[test]
+fn pool_macro() {
+    let mut name = NAME_POOL.acquire();
+    name.push_str("name");
+    drop(name);
+    assert_eq!(NAME_POOL.len(), 1);
+    assert_eq!(*NAME_POOL.acquire(), "name");
+}
+
+#[derive(ObjectPool)]
+#[generator(Wrapper::empty)]
+#[pool(capacity = 1)]
+struct Wrapper<T: Clone, const N: usize>(Vec<[T; N]>);
+
+impl<T: Clone, const N: usize> Wrapper<T, N> {
+    fn empty() -> Self {
+        Wrapper(Vec::with_capacity(1))
+    }
+}
+
+#[test]
+fn generic_pools() {
+    let objects = [Wrapper::<u8, 2>::new(), Wrapper::<u8, 2>::new()];
+    drop(objects);
+    assert_eq!(Wrapper::<u8, 2>::pool().len(), 1);
+    assert_eq!(Wrapper::<u8, 3>::pool().len(), 0);
+    assert_eq!(Wrapper::<String, 2>::pool().len(), 0);
+    assert!(std::ptr::eq(
+        Wrapper::<u8, 2>::pool(),
+        Wrapper::<u8, 2>::pool()
+    ));
+}
+
+#[derive(Debug, PartialEq, ObjectPool)]
+#[generator(Event::placeholder)]
+enum Event {
+    Click { x: i32, y: i32 },
+    Key(char),
+}
+
+impl Event {
+    fn placeholder() -> Self {
+        Event::Key('\0')
+    }
+}
+
+#[test]
+fn enum_pool() {
+    let mut event = Event::new();
+    assert_eq!(*event, Event::Key('\0'));
+    *event = Event::Click { x: 1, y: 2 };
+    drop(event);
+    assert_eq!(Event::pool().len(), 1);
+    assert_eq!(*Event::new(), Event::Click { x: 1, y: 2 });
+}
+
+mod without_imports {
+    #[derive(Default, derivable_object_pool::ObjectPool)]
+    #[pool(capacity = 1, const_items = [Plain(1)])]
+    #[derive(Clone, Copy)]
+    pub struct Plain(pub u8);
+
+    #[derive(Default, derivable_object_pool::ObjectPool)]
+    pub struct Generic<T: Send + 'static>(pub Vec<T>);
+}
+
+mod renamed_crate {
+    use derivable_object_pool as reexported;
+
+    #[derive(Default, reexported::ObjectPool)]
+    #[pool(crate = reexported)]
+    pub struct Renamed(pub u8);
+
+    #[reexported::global_pool(crate = reexported)]
+    pub static RENAMED_POOL: reexported::Pool<String> = reexported::Pool::new(String::new);
+}
+
+#[test]
+fn hygienic_derive() {
+    use without_imports::{Generic, Plain};
+
+    assert_eq!(Plain::new().0, 1);
+    assert_eq!(Generic::<u8>::new().0.len(), 0);
+    assert_eq!(renamed_crate::Renamed::new().0, 0);
+    assert_eq!(renamed_crate::Renamed::pool().len(), 1);
+    drop(renamed_crate::renamed_pool::handle().acquire());
+    assert_eq!(renamed_crate::renamed_pool::pool().len(), 1);
+}
+
+mod named {
+    use derivable_object_pool::prelude::*;
+
+    #[derive(Default, ObjectPool)]
+    #[pool(static_name = "NAMED_POOL", vis = "pub(crate)")]
+    pub struct Named(pub u8);
+}
+
+#[test]
+fn static_name_and_vis() {
+    drop(named::Named::new());
+    assert_eq!(named::NAMED_POOL.len(), 1);
+}
+
+#[derive(ObjectPool)]
+#[generator(|| Seeded(42))]
+struct Seeded(u32);
+
+#[derive(ObjectPool)]
+#[pool(generator = Literal { a: 1, b: vec![2] })]
+struct Literal {
+    a: u32,
+    b: Vec<u8>,
+}
+
+#[test]
+fn expression_generators() {
+    assert_eq!(42, Seeded::new().0);
+    let literal = Literal::new();
+    assert_eq!((1, &[2][..]), (literal.a, &literal.b[..]));
+}
+
+#[derive(ObjectPool)]
+#[generator = "NameValue::make"]
+struct NameValue(u32);
+
+impl NameValue {
+    fn make() -> Self {
+        NameValue(3)
+    }
+}
+
+#[derive(ObjectPool)]
+#[pool(generator = "|| StringGenerator(4)")]
+struct StringGenerator(u32);
+
+#[test]
+fn name_value_generators() {
+    assert_eq!(3, NameValue::new().0);
+    assert_eq!(4, StringGenerator::new().0);
+}
+
+static HOOK_CALLS: std::sync::Mutex<Vec<&'static str>> = std::sync::Mutex::new(Vec::new());
+
+fn hook_call(name: &'static str) {
+    HOOK_CALLS.lock().unwrap().push(name);
+}
+
+#[derive(Default, ObjectPool)]
+#[pool(capacity = 1)]
+#[pool(on_create = |_| hook_call("create"), on_reuse = |_| hook_call("reuse"))]
+#[pool(on_return = |_| hook_call("return"), on_discard = |_| hook_call("discard"))]
+struct Hooked(u32);
+
+#[test]
+fn lifecycle_hooks() {
+    let objects = [Hooked::new(), Hooked::new()];
+    drop(objects);
+    drop(Hooked::new());
+    assert_eq!(
+        *HOOK_CALLS.lock().unwrap(),
+        ["create", "create", "return", "return", "discard", "reuse", "return"]
+    );
+}
+
+#[derive(ObjectPool)]
+#[generator(|| Conn { alive: true, id: 0 })]
+#[pool(validator = Conn::is_alive)]
+struct Conn {
+    alive: bool,
+    id: u32,
+}
+
+impl Conn {
+    fn is_alive(&self) -> bool {
+        self.alive
+    }
+}
+
+#[test]
+fn validator_discards_broken_objects() {
+    Conn::pool().insert(Conn { alive: true, id: 1 });
+    Conn::pool().insert(Conn {
+        alive: false,
+        id: 2,
+    });
+    let conn = Conn::new();
+    assert_eq!(1, conn.id);
+    assert_eq!(0, Conn::pool().len());
+    drop(conn);
+
+    Conn::pool().insert(Conn {
+        alive: false,
+        id: 3,
+    });
+    assert_eq!(1, Conn::new().id);
+}
+
+#[derive(Default, ObjectPool)]
+#[pool(max_reuses = 2)]
+struct Worn(u32);
+
+#[test]
+fn max_reuses_destroys_worn_objects() {
+    let mut obj = Worn::new();
+    obj.0 = 1;
+    assert_eq!(0, obj.reuses());
+    drop(obj);
+
+    for reuses in 1..=2 {
+        let obj = Worn::new();
+        assert_eq!(1, obj.0);
+        assert_eq!(reuses, obj.reuses());
+    }
+    // The object was reused twice, so it was destroyed on its last return
+    assert_eq!(0, Worn::pool().len());
+    assert_eq!(0, Worn::new().0);
+
+    // Objects inserted directly start their count from zero
+    Worn::pool().insert(Worn(2));
+    assert_eq!(1, Worn::new().reuses());
+    assert_eq!(
+        Some(1),
+        Worn::pool()
+            .debug_state()
+            .items
+            .last()
+            .map(|item| item.reuses)
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn evict_idle_keeps_recent_objects() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    static EVICTED: AtomicUsize = AtomicUsize::new(0);
+    let pool = Pool::new(Vec::<u8>::new).on_discard(|_| {
+        EVICTED.fetch_add(1, Ordering::Relaxed);
+    });
+    pool.insert(vec![1]);
+    pool.insert(vec![2]);
+    std::thread::sleep(Duration::from_millis(20));
+    pool.insert(vec![3]);
+
+    assert_eq!(0, pool.evict_idle(Duration::from_secs(60)));
+    assert_eq!(2, pool.evict_idle(Duration::from_millis(10)));
+    assert_eq!(2, EVICTED.load(Ordering::Relaxed));
+    assert_eq!(Some(vec![3]), pool.remove());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn dropped_idle_objects_are_discarded() {
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    static DISCARDED: Mutex<Vec<u8>> = Mutex::new(Vec::new());
+    let pool = Pool::new(Vec::<u8>::new)
+        .with_capacity(2)
+        .on_discard(|item| DISCARDED.lock().unwrap().extend(item.iter()));
+    let discarded = || std::mem::take(&mut *DISCARDED.lock().unwrap());
+
+    // The objects that would be handed out last are dropped over capacity
+    pool.restore_with_order([vec![1], vec![2], vec![3]], RestoreOrder::ReuseFirst);
+    assert_eq!(vec![1], discarded());
+
+    // The coldest objects are trimmed first
+    assert_eq!(1, pool.trim_some(1, Duration::from_secs(1)));
+    assert_eq!(vec![2], discarded());
+    assert_eq!(Some(vec![3]), pool.remove());
+
+    pool.insert(vec![4]);
+    pool.invalidate();
+    assert_eq!(vec![4], discarded());
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "pool self-check: returned object failed the validator of the pool")]
+fn self_check_catches_objects_failing_the_validator() {
+    let pool = Pool::new(|| 1u8).with_validator(|item| *item != 1);
+    pool.insert(2);
+    pool.debug_self_check();
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "pool self-check: returned object was dropped instead of being pooled")]
+fn self_check_catches_objects_dropped_on_return() {
+    let pool = Pool::new(Vec::<u8>::new).with_capacity(0);
+    pool.debug_self_check();
+}
+
+#[test]
+fn maintain_discards_invalid_objects() {
+    let pool = Pool::new(String::new).with_validator(|item| !item.is_empty());
+    pool.insert(String::from("valid"));
+    pool.insert(String::new());
+    pool.maintain();
+    assert_eq!(1, pool.len());
+    assert_eq!(Some(String::from("valid")), pool.remove());
+}
+
+#[test]
+fn retain_purges_matching_objects() {
+    let pool = Pool::new(String::new);
+    for host in ["a.example", "old.example", "b.example"] {
+        pool.insert(String::from(host));
+    }
+    assert_eq!(1, pool.retain(|host| host != "old.example"));
+    assert_eq!(0, pool.retain(|_| true));
+    assert_eq!(vec!["a.example", "b.example"], pool.take_all());
+}
+
+#[test]
+fn drain_moves_objects_between_pools() {
+    let old = Pool::new(Vec::<u8>::new);
+    let new = Pool::new(Vec::<u8>::new);
+    old.insert(vec![1]);
+    old.insert(vec![2]);
+    for buffer in old.drain() {
+        new.insert(buffer);
+    }
+    assert!(old.is_empty());
+    assert_eq!(vec![vec![1], vec![2]], new.take_all());
+}
+
+#[test]
+fn extend_and_collect_pools() {
+    let mut pool: Pool<Vec<u8>> = [vec![1], vec![2]].into_iter().collect();
+    pool.extend([vec![3]]);
+    assert_eq!(3, pool.len());
+    assert_eq!(Some(vec![3]), pool.remove());
+
+    let bounded = Pool::new(Vec::<u8>::new).with_capacity(2);
+    (&bounded).extend((0..4).map(|i| vec![i]));
+    assert_eq!(2, bounded.len());
+}
+
+#[test]
+fn for_each_idle_visits_pooled_objects() {
+    let pool = Pool::new(String::new);
+    pool.insert(String::from("a"));
+    pool.insert(String::from("bc"));
+    let mut visited = Vec::new();
+    pool.for_each_idle(|item| visited.push(item.clone()));
+    assert_eq!(vec!["a", "bc"], visited);
+    assert_eq!(2, pool.len());
+}
+
+#[test]
+fn apply_idle_mutates_objects_in_place() {
+    let pool = Pool::new(Vec::<u8>::new);
+    pool.insert(vec![1, 2]);
+    pool.insert(vec![3]);
+    pool.apply_idle(|buffer| buffer.push(0));
+    assert_eq!(vec![vec![1, 2, 0], vec![3, 0]], pool.take_all());
+}
+
+#[derive(Default, ObjectPool)]
+#[pool(max_bytes = 64)]
+struct Chunk(Vec<u8>);
+
+impl HeapSize for Chunk {
+    fn heap_size(&self) -> usize {
+        self.0.heap_size()
+    }
+}
+
+#[test]
+fn max_bytes_budget() {
+    let small = Chunk(Vec::with_capacity(32));
+    let huge = Chunk(Vec::with_capacity(1024));
+    Chunk::pool().insert(small);
+    Chunk::pool().insert(huge);
+    assert_eq!(1, Chunk::pool().len());
+    assert_eq!(32, Chunk::pool().idle_bytes());
+
+    let mut chunk = Chunk::new();
+    assert_eq!(0, Chunk::pool().idle_bytes());
+    chunk.0.reserve(128);
+    drop(chunk); // grew over the budget
+    assert!(Chunk::pool().is_empty());
+
+    // Bulk inserts drop the objects that would be handed out last
+    let pool = Pool::new(Vec::<u8>::new).with_max_bytes(100);
+    (&pool).extend([60, 30, 20].map(Vec::with_capacity));
+    assert_eq!(2, pool.len());
+    assert_eq!(50, pool.idle_bytes());
+}
+
+#[derive(Default, ObjectPool)]
+#[pool(min_idle = 1)]
+struct Registered(u8);
+
+#[test]
+fn registry_lists_derived_pools() {
+    Registered::pool().prefill(3);
+    let pool = derivable_object_pool::registry::iter()
+        .find(|pool| pool.name() == "REGISTERED_OBJECT_POOL")
+        .unwrap();
+    assert_eq!(std::any::type_name::<Registered>(), pool.type_name());
+    assert_eq!(3, pool.len());
+    assert_eq!(2, pool.trim());
+    assert_eq!(1, Registered::pool().len());
+}
+
+#[derive(Default, ObjectPool)]
+#[pool(name = "http_request_buffers")]
+struct RequestBuffer(Vec<u8>);
+
+#[test]
+fn named_pools_are_listed_by_name() {
+    drop(RequestBuffer::new());
+    assert_eq!(Some("http_request_buffers"), RequestBuffer::pool().name());
+    let pool = derivable_object_pool::registry::iter()
+        .find(|pool| pool.type_name() == std::any::type_name::<RequestBuffer>())
+        .unwrap();
+    assert_eq!("http_request_buffers", pool.name());
+    assert_eq!(Some("http_request_buffers"), pool.debug_state().name);
+    #[cfg(feature = "counters")]
+    assert_eq!(Some("http_request_buffers"), pool.stats().name);
+
+    let pool = Pool::new(Vec::<u8>::new);
+    assert_eq!(None, pool.name());
+    pool.set_name("response_buffers");
+    assert_eq!(Some("response_buffers"), pool.name());
+    assert!(pool
+        .debug_state()
+        .to_string()
+        .contains("\"response_buffers\""));
+}
+
+#[derive(Default, ObjectPool)]
+struct Detached(Vec<u8>);
+
+#[test]
+fn detach_and_attach() {
+    let mut detached = Detached::new().detach();
+    assert!(detached.is_owned());
+    detached.0.push(1);
+    assert_eq!(0, Detached::pool().outstanding());
+    drop(detached);
+    assert_eq!(0, Detached::pool().len());
+
+    let attached = Detached::new().detach().attach();
+    assert!(attached.is_pooled());
+    assert_eq!(1, Detached::pool().outstanding());
+    drop(attached);
+    assert_eq!(1, Detached::pool().len());
+    assert_eq!(0, Detached::pool().outstanding());
+}
+
+#[derive(Default, ObjectPool)]
+#[pool(max_reuses = 1)]
+struct Released(u8);
+
+#[test]
+fn release_reports_whether_pooled() {
+    let first = Released::new();
+    assert_eq!(0, first.reuses());
+    assert!(first.release());
+    assert_eq!(1, Released::pool().len());
+
+    let second = Released::new();
+    assert_eq!(1, second.reuses());
+    assert!(!second.release());
+    assert!(Released::pool().is_empty());
+
+    let handle = PoolHandle::new(Pool::new(|| 0u8));
+    assert!(handle.acquire().release());
+    assert_eq!(1, handle.len());
+}
+
+#[derive(Default, ObjectPool)]
+struct Mapped {
+    id: u32,
+    data: Vec<u8>,
+}
+
+#[test]
+fn map_projects_and_returns_whole_object() {
+    let mut mapped = Mapped::new();
+    mapped.id = 7;
+    let mut data = Reusable::map(mapped, |mapped| &mut mapped.data);
+    data.push(1);
+    // The projection follows the object when the guard is moved
+    let moved = vec![data];
+    let data = moved.into_iter().next().unwrap();
+    assert_eq!(*data, [1]);
+
+    let element = MappedReusable::map(data, |data| &mut data[0]);
+    assert_eq!(*element, 1);
+    drop(element);
+    assert_eq!(1, Mapped::pool().len());
+
+    let mapped = Mapped::new();
+    assert_eq!(7, mapped.id);
+    assert_eq!(mapped.data, [1]);
+    let mapped = Reusable::try_map(mapped, |mapped| mapped.data.get_mut(1)).unwrap_err();
+    assert!(Reusable::try_map(mapped, |mapped| mapped.data.first_mut()).is_ok());
+}
+
+#[derive(Default, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ObjectPool)]
+struct Key(u32);
+
+#[test]
+fn reusable_forwards_traits() {
+    use std::collections::HashSet;
+
+    let mut first = Key::new();
+    first.0 = 1;
+    let mut second = Key::new();
+    second.0 = 2;
+    assert_eq!("Key(1)", format!("{first:?}"));
+    assert!(first < second);
+    assert_ne!(first, second);
+
+    let keys: HashSet<Reusable<Key>> = [first, second].into_iter().collect();
+    // Lookups can borrow the key as the plain object
+    assert!(keys.contains(&Key(2)));
+}
+
+#[cfg(feature = "serde")]
+#[derive(Default, ObjectPool, serde::Serialize, serde::Deserialize)]
+struct Message(Vec<u8>);
+
+#[cfg(feature = "serde")]
+#[test]
+fn deserialize_reuses_pooled_objects() {
+    let mut message = Message::new();
+    message.0.reserve(64);
+    drop(message);
+
+    let message: Reusable<Message> = serde_json::from_str("[1,2,3]").unwrap();
+    assert_eq!(message.0, [1, 2, 3]);
+    // The buffer of the pooled object was reused
+    assert!(message.0.capacity() >= 64);
+    assert_eq!("[1,2,3]", serde_json::to_string(&message).unwrap());
+}
+
+#[cfg(feature = "std")]
+#[derive(Default, ObjectPool)]
+struct Cursor(std::io::Cursor<Vec<u8>>);
+
+#[cfg(feature = "std")]
+impl std::io::Read for Cursor {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::io::BufRead for Cursor {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.0.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.0.consume(amt);
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn reusable_forwards_io() {
+    use std::io::BufRead;
+
+    let mut cursor = Cursor::new();
+    cursor.0.get_mut().extend_from_slice(b"first\nsecond\n");
+    let lines: Vec<String> = cursor.lines().map(Result::unwrap).collect();
+    assert_eq!(lines, ["first", "second"]);
+    assert_eq!(1, Cursor::pool().len());
+}
+
+#[derive(Default, ObjectPool)]
+struct Drain(std::vec::IntoIter<u8>);
+
+impl Iterator for Drain {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Drain {}
+
+#[test]
+fn reusable_forwards_iterator() {
+    let mut drain = Drain::new();
+    drain.0 = vec![1, 2, 3].into_iter();
+    assert_eq!(3, drain.len());
+    let mut sum = 0;
+    for item in drain {
+        sum += item;
+    }
+    assert_eq!(6, sum);
+    assert_eq!(1, Drain::pool().len());
+}
+
+#[derive(Default, Debug, ObjectPool)]
+struct Shared(u32);
+
+#[test]
+fn shared_returns_with_last_clone() {
+    let shared = Shared::new().into_shared();
+    let clone = shared.clone();
+    assert_eq!(2, SharedReusable::strong_count(&shared));
+    assert!(SharedReusable::ptr_eq(&shared, &clone));
+
+    let shared = SharedReusable::try_unwrap(shared).unwrap_err();
+    drop(clone);
+    assert!(Shared::pool().is_empty());
+
+    let mut unique = SharedReusable::try_unwrap(shared).ok().unwrap();
+    unique.0 = 1;
+    drop(unique.into_shared());
+    assert_eq!(1, Shared::pool().len());
+}
+
+#[test]
+fn weak_observes_without_checking_out() {
+    let shared = Shared::new().into_shared();
+    let weak = SharedReusable::downgrade(&shared);
+    assert_eq!(1, weak.strong_count());
+    let upgraded = weak.upgrade().unwrap();
+    assert!(SharedReusable::ptr_eq(&shared, &upgraded));
+    drop((shared, upgraded));
+
+    assert!(weak.upgrade().is_none());
+    assert_eq!(0, weak.strong_count());
+    assert!(WeakReusable::<Shared>::new().upgrade().is_none());
+}
+
+#[derive(Default, ObjectPool)]
+struct Swapped(u32);
+
+#[test]
+fn take_and_replace_keep_guard_pooled() {
+    let mut idle = Swapped::new();
+    idle.0 = 1;
+    drop(idle);
+
+    let mut guard = Swapped::new();
+    guard.0 = 2;
+    let taken = Reusable::take(&mut guard);
+    assert_eq!(2, taken.0);
+    assert_eq!(0, guard.0);
+
+    let replaced = Reusable::replace(&mut guard, Swapped(3));
+    assert_eq!(0, replaced.0);
+    drop(guard);
+    assert_eq!(1, Swapped::pool().len());
+    assert_eq!(3, Swapped::new().0);
+}
+
+#[derive(Default, ObjectPool)]
+struct Scoped(u32);
+
+#[test]
+fn with_returns_object_even_on_panic() {
+    assert_eq!(1, Scoped::with(|scoped| scoped.0 + 1));
+    assert_eq!(1, Scoped::pool().len());
+
+    #[cfg(feature = "std")]
+    {
+        let result = std::panic::catch_unwind(|| Scoped::with(|_| panic!("boom")));
+        assert!(result.is_err());
+        assert_eq!(1, Scoped::pool().len());
+    }
+}
+
+#[derive(Default, ObjectPool)]
+struct Batched(u32);
+
+#[cfg(feature = "std")]
+#[test]
+fn acquire_all_waits_on_bounded_pools() {
+    use std::time::Duration;
+
+    struct Bounded(u8);
+
+    #[derive(Default, ObjectPool)]
+    struct Unbounded(u8);
+
+    static BOUNDED: Pool<Bounded> = Pool::new(|| Bounded(1)).with_max_outstanding(1);
+
+    impl ObjectPool for Bounded {
+        fn pool<'a>() -> &'a Pool<Self> {
+            &BOUNDED
+        }
+    }
+
+    let held = Bounded::new();
+    let waiter = std::thread::spawn(|| {
+        let (bounded, unbounded) = <(Bounded, Unbounded)>::acquire_all();
+        (bounded.0, unbounded.0)
+    });
+    std::thread::sleep(Duration::from_millis(20));
+    assert!(!waiter.is_finished());
+    drop(held);
+    assert_eq!((1, 0), waiter.join().unwrap());
+    assert_eq!(0, Bounded::pool().outstanding());
+}
+
+#[test]
+fn new_n_takes_idle_and_generates_rest() {
+    let mut idle = Batched::new_n(2);
+    idle[0].0 = 1;
+    idle[1].0 = 2;
+    drop(idle);
+    assert_eq!(2, Batched::pool().len());
+
+    let batch = Batched::new_n(5);
+    assert_eq!(5, batch.len());
+    assert_eq!(3, batch.iter().map(|obj| obj.0).sum::<u32>());
+    assert!(Batched::pool().is_empty());
+    drop(batch);
+    assert_eq!(5, Batched::pool().len());
+}
+
+#[derive(Default, ObjectPool)]
+#[pool(max_reuses = 1)]
+struct Churned(u32);
+
+#[test]
+fn return_batch_flushes_under_policies() {
+    use derivable_object_pool::ReturnBatch;
+
+    let mut batch = ReturnBatch::new();
+    batch.extend(Churned::new_n(3));
+    assert_eq!(3, batch.len());
+    assert!(Churned::pool().is_empty());
+    assert_eq!(3, batch.flush());
+    assert!(batch.is_empty());
+    assert_eq!(3, Churned::pool().len());
+
+    // The objects were already reused once, so they are discarded
+    batch.extend(Churned::new_n(3));
+    assert_eq!(0, batch.flush());
+
+    batch.push(Churned::new());
+    drop(batch);
+    assert_eq!(1, Churned::pool().len());
+}
+
+#[test]
+fn lock_guards_several_operations() {
+    let pool = Pool::new(|| 0u32).with_capacity(3);
+    let mut guard = pool.lock();
+    assert!(guard.push(1).is_ok());
+    assert!(guard.push(2).is_ok());
+    assert!(guard.push(3).is_ok());
+    assert_eq!(Err(4), guard.push(4));
+    assert_eq!(*guard, [1, 2, 3]);
+
+    assert_eq!(1, guard.retain(|&item| item != 2));
+    assert_eq!(1, guard.remove(0));
+    assert_eq!(Some(3), guard.pop());
+    assert!(guard.is_empty());
+    drop(guard);
+    assert!(pool.is_empty());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn acquire_blocking_bounds_live_objects() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static CREATED: AtomicUsize = AtomicUsize::new(0);
+    static POOL: Pool<u8> = Pool::new(|| {
+        CREATED.fetch_add(1, Ordering::Relaxed);
+        0
+    })
+    .with_max_outstanding(2);
+
+    let workers: Vec<_> = (0..8)
+        .map(|_| {
+            std::thread::spawn(|| {
+                for _ in 0..100 {
+                    let object = POOL.acquire_blocking();
+                    std::thread::yield_now();
+                    drop(object);
+                }
+            })
+        })
+        .collect();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+    assert!(CREATED.load(Ordering::Relaxed) <= 2);
+    assert_eq!(Some(2), POOL.debug_state().max_outstanding);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn set_max_outstanding_at_runtime() {
+    use std::time::Duration;
+
+    static POOL: Pool<u8> = Pool::new(|| 0).with_max_outstanding(2);
+
+    let first = POOL.acquire_timeout(Duration::ZERO).unwrap();
+    POOL.set_max_outstanding(1);
+    assert!(POOL.acquire_timeout(Duration::ZERO).is_err());
+    assert_eq!(Some(1), POOL.debug_state().max_outstanding);
+
+    let waiter = std::thread::spawn(|| *POOL.acquire_blocking());
+    std::thread::sleep(Duration::from_millis(10));
+    POOL.set_max_outstanding(2);
+    assert_eq!(0, waiter.join().unwrap());
+    drop(first);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn acquire_blocking_waits_for_reuse_only_pools() {
+    static POOL: Pool<u8> = Pool::reuse_only();
+
+    let waiter = std::thread::spawn(|| *POOL.acquire_blocking());
+    std::thread::sleep(std::time::Duration::from_millis(10));
+    POOL.insert(7);
+    assert_eq!(7, waiter.join().unwrap());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn acquire_timeout_sheds_load() {
+    use std::time::Duration;
+
+    static POOL: Pool<u8> = Pool::new(|| 0).with_max_outstanding(1);
+
+    let held = POOL.acquire_timeout(Duration::ZERO).unwrap();
+    let err = POOL
+        .acquire_timeout(Duration::from_millis(5))
+        .err()
+        .unwrap();
+    assert_eq!(AcquireErrorKind::TimedOut, err.kind());
+
+    let waiter = std::thread::spawn(|| POOL.acquire_timeout(Duration::from_secs(10)).is_ok());
+    std::thread::sleep(Duration::from_millis(5));
+    drop(held);
+    assert!(waiter.join().unwrap());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn fifo_fairness_serves_waiters_in_arrival_order() {
+    use derivable_object_pool::Fairness;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    static POOL: Pool<u8> = Pool::new(|| 0)
+        .with_max_outstanding(1)
+        .with_fairness(Fairness::Fifo);
+    static SERVED: Mutex<Vec<usize>> = Mutex::new(Vec::new());
+
+    let held = POOL.acquire_blocking();
+    let waiters: Vec<_> = (0..4)
+        .map(|i| {
+            let waiter = std::thread::spawn(move || {
+                let object = POOL.acquire_blocking();
+                SERVED.lock().unwrap().push(i);
+                std::thread::sleep(Duration::from_millis(1));
+                drop(object);
+            });
+            std::thread::sleep(Duration::from_millis(20));
+            waiter
+        })
+        .collect();
+    drop(held);
+    for waiter in waiters {
+        waiter.join().unwrap();
+    }
+    assert_eq!(vec![0, 1, 2, 3], *SERVED.lock().unwrap());
+}
+
+#[test]
+fn try_acquire_never_generates() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static CREATED: AtomicUsize = AtomicUsize::new(0);
+    static POOL: Pool<Vec<u8>> = Pool::new(|| {
+        CREATED.fetch_add(1, Ordering::Relaxed);
+        Vec::new()
+    });
+
+    let handle = PoolHandle::from_static(&POOL);
+    assert!(handle.try_acquire().is_none());
+    POOL.insert(vec![1]);
+    let object = handle.try_acquire().unwrap();
+    assert_eq!(vec![1], *object);
+    assert!(handle.try_acquire().is_none());
+    drop(object);
+    assert_eq!(1, POOL.len());
+    assert_eq!(0, CREATED.load(Ordering::Relaxed));
+}
+
+#[test]
+fn len_does_not_lock_the_pool() {
+    static POOL: Pool<u8> = Pool::new(|| 0).with_lock_free(2);
+
+    POOL.insert(1);
+    POOL.insert(2);
+    POOL.insert(3);
+    assert_eq!(3, POOL.len());
+
+    // The size is readable while the pool is locked
+    let guard = POOL.lock();
+    assert_eq!(POOL.len(), guard.len());
+    drop(guard);
+
+    let frozen = POOL.freeze();
+    assert!(!POOL.is_empty());
+    drop(frozen);
+    POOL.clear();
+    assert!(POOL.is_empty());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn len_stays_within_the_lock_free_slots() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    // Only the slots store objects, so they bound the size of the pool
+    static POOL: Pool<u8> = Pool::new(|| 0).with_lock_free(4).with_capacity(0);
+    static DONE: AtomicBool = AtomicBool::new(false);
+
+    let workers: Vec<_> = (0..8)
+        .map(|_| {
+            std::thread::spawn(|| {
+                for _ in 0..100_000 {
+                    drop(POOL.acquire());
+                }
+            })
+        })
+        .collect();
+    let checker = std::thread::spawn(|| {
+        while !DONE.load(Ordering::Relaxed) {
+            let len = POOL.len();
+            assert!(len <= 4, "{len} idle objects in 4 slots");
+        }
+    });
+    for worker in workers {
+        worker.join().unwrap();
+    }
+    DONE.store(true, Ordering::Relaxed);
+    checker.join().unwrap();
+    assert!(POOL.len() <= 4);
+}
+
+#[test]
+fn outstanding_counts_checked_out_objects() {
+    use derivable_object_pool::ReturnBatch;
+
+    #[derive(Default, ObjectPool)]
+    #[pool(capacity = 1)]
+    struct Tracked(u8);
+
+    let pool = Tracked::pool();
+    let objects: Vec<_> = (0..3).map(|_| Tracked::new()).collect();
+    assert_eq!(3, pool.outstanding());
+
+    let mut objects = objects.into_iter();
+    let staged = objects.next().unwrap().stage_return();
+    assert_eq!(3, pool.outstanding());
+    assert!(staged.commit());
+
+    assert_eq!(2, pool.outstanding());
+
+    // Discarded objects are no longer outstanding either
+    let mut batch = ReturnBatch::new();
+    batch.extend(objects);
+    assert_eq!(0, batch.flush());
+    assert_eq!(0, pool.outstanding());
+    assert_eq!(1, pool.len());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn dropped_staged_returns_end_the_checkout() {
+    use std::future::Future;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+    use std::pin::pin;
+    use std::task::{Context, Waker};
+    use std::time::Duration;
+
+    #[derive(Default, ObjectPool)]
+    struct Flushed(u8);
+
+    let pool = Flushed::pool();
+    pool.set_max_outstanding(1);
+    let staged = Flushed::new().stage_return();
+    let waiter = std::thread::spawn(|| Flushed::pool().acquire_blocking().0);
+    std::thread::sleep(Duration::from_millis(10));
+    drop(staged);
+    assert_eq!(0, waiter.join().unwrap());
+    assert_eq!(0, pool.outstanding());
+
+    // Dropping the commit before the cleanup finishes ends the checkout too
+    let staged = Flushed::new().stage_return();
+    {
+        let mut commit = pin!(staged.commit_after(|object| async move {
+            std::future::pending::<()>().await;
+            object
+        }));
+        let mut cx = Context::from_waker(Waker::noop());
+        assert!(commit.as_mut().poll(&mut cx).is_pending());
+    }
+    assert_eq!(0, pool.outstanding());
+
+    // And so does a cleanup that panics
+    let staged = Flushed::new().stage_return();
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        let mut commit = pin!(staged.commit_after(|_| async { panic!("cleanup failed") }));
+        let _ = commit
+            .as_mut()
+            .poll(&mut Context::from_waker(Waker::noop()));
+    }));
+    assert!(result.is_err());
+    assert_eq!(0, pool.outstanding());
+    assert_eq!(0, pool.staged());
+}
+
+#[cfg(feature = "counters")]
+#[test]
+fn stats_record_high_water_marks() {
+    #[derive(Default, ObjectPool)]
+    #[pool(lock_free = 2)]
+    struct Peaked(u8);
+
+    let objects: Vec<_> = (0..4).map(|_| Peaked::new()).collect();
+    drop(objects);
+    let objects = Peaked::new_n(3);
+    drop(objects);
+
+    let stats = Peaked::pool().stats();
+    assert_eq!(4, stats.peak_outstanding);
+    assert_eq!(4, stats.peak_idle);
+    assert_eq!(4, Peaked::pool().len());
+}
+
+#[cfg(feature = "leak-detection")]
+#[test]
+fn report_overdue_finds_unreturned_objects() {
+    use std::time::Duration;
+
+    #[derive(Default, ObjectPool)]
+    struct Leaky(u8);
+
+    let held = Leaky::new();
+    let leaked = Leaky::new().labeled("leaked");
+    std::mem::forget(leaked);
+    let taken = Leaky::new().into_inner();
+    let staged = Leaky::new().stage_return();
+    std::thread::sleep(Duration::from_millis(5));
+
+    let overdue = Leaky::pool().report_overdue(Duration::from_millis(1));
+    assert_eq!(3, overdue.len());
+    assert_eq!(None, overdue[0].label);
+    assert_eq!(Some("leaked"), overdue[1].label);
+    assert!(overdue[1].to_string().contains("Leaky (leaked) held for"));
+
+    drop((held, taken));
+    staged.commit();
+    assert_eq!(1, Leaky::pool().report_overdue(Duration::ZERO).len());
+}
+
+#[cfg(feature = "debug-tracking")]
+#[test]
+fn overdue_objects_tell_where_they_were_acquired() {
+    use std::time::Duration;
+
+    #[derive(Default, ObjectPool)]
+    struct Tracked(u8);
+
+    fn acquire_and_leak() {
+        std::mem::forget(Tracked::new());
+    }
+
+    acquire_and_leak();
+    let overdue = Tracked::pool().report_overdue(Duration::ZERO);
+    assert_eq!(1, overdue.len());
+    assert!(overdue[0].backtrace.contains("acquire_and_leak"));
+    assert!(overdue[0].to_string().contains("acquired at:"));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn assert_all_returned_lists_leaked_objects() {
+    #[derive(Default, ObjectPool)]
+    struct Audited(u8);
+
+    let guard = Audited::pool().assert_all_returned_on_drop();
+    drop(Audited::new());
+    drop(guard);
+    Audited::pool().assert_all_returned();
+
+    let object = Audited::new().labeled("escaped");
+    let err = std::panic::catch_unwind(|| Audited::pool().assert_all_returned()).unwrap_err();
+    let message = err.downcast_ref::<String>().unwrap();
+    assert!(message.starts_with("1 object(s) of"));
+    #[cfg(feature = "leak-detection")]
+    assert!(message.contains("Audited (escaped) held for"));
+
+    let leaked = std::panic::catch_unwind(|| {
+        let _guard = Audited::pool().assert_all_returned_on_drop();
+        std::mem::forget(Audited::new());
+    });
+    assert!(leaked.is_err());
+    drop(object);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn scoped_pool_isolates_the_current_thread() {
+    #[derive(Default, ObjectPool)]
+    #[pool(capacity = 4)]
+    struct Isolated(u8);
+
+    Isolated::pool().insert(Isolated(1));
+    let outer = Isolated::pool().scoped(|| {
+        assert!(Isolated::pool().is_empty());
+        assert_eq!(4, Isolated::pool().capacity());
+        let other = std::thread::spawn(|| Isolated::pool().len());
+        assert_eq!(1, other.join().unwrap());
+
+        drop(Isolated::new());
+        let inner = Isolated::pool().scoped(|| Isolated::pool().len());
+        assert_eq!(0, inner);
+        let held = Isolated::new();
+        assert_eq!(0, Isolated::pool().len());
+        held
+    });
+    assert_eq!(1, Isolated::pool().len());
+    assert_eq!(0, Isolated::pool().outstanding());
+    drop(outer); // returned to the replacement, which discards it
+    assert_eq!(1, Isolated::pool().len());
+    assert_eq!(0, Isolated::pool().outstanding());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn scoped_pool_is_invalidated_on_panic_and_reused() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+    use std::ptr;
+
+    #[derive(Default, ObjectPool)]
+    struct Unwound(u8);
+
+    let mut replaced = None;
+    let mut escaped = None;
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        Unwound::pool().scoped(|| {
+            replaced = Some(Unwound::pool());
+            drop(Unwound::new());
+            escaped = Some(Unwound::new());
+            panic!("test failed");
+        })
+    }));
+    assert!(result.is_err());
+    let replaced = replaced.unwrap();
+    assert_eq!(0, replaced.len());
+
+    // The replacement is only reused once its objects are returned
+    let other = Unwound::pool().scoped(|| Unwound::pool() as *const Pool<Unwound>);
+    assert!(!ptr::eq(replaced, other));
+    drop(escaped);
+    assert_eq!(0, replaced.len());
+    Unwound::pool().scoped(|| {
+        assert!(ptr::eq(replaced, Unwound::pool()));
+        assert_eq!(0, Unwound::pool().outstanding());
+        drop(Unwound::new());
+        assert_eq!(1, Unwound::pool().len());
+    });
+    assert_eq!(0, replaced.len());
+}
+
+#[test]
+fn snapshot_and_restore_reset_the_pool() {
+    let pool = Pool::new(Vec::<u8>::new).with_capacity(2);
+    pool.insert(vec![1]);
+    pool.insert(vec![2]);
+
+    let snapshot = pool.snapshot();
+    assert!(pool.is_empty());
+    assert_eq!(
+        vec![&vec![1], &vec![2]],
+        snapshot.iter().collect::<Vec<_>>()
+    );
+
+    pool.insert(vec![3]);
+    pool.restore(snapshot);
+    assert_eq!(Some(vec![2]), pool.remove());
+    assert_eq!(Some(vec![1]), pool.remove());
+    assert!(pool.is_empty());
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn tracing_reports_the_pool_events() {
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    #[derive(Default)]
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+
+    impl Visit for Recorder {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            if field.name() == "event" {
+                self.0.lock().unwrap().push(value.to_owned());
+            }
+        }
+
+        fn record_debug(&mut self, _: &Field, _: &dyn std::fmt::Debug) {}
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, _: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+        fn event(&self, event: &Event<'_>) {
+            event.record(&mut Recorder(self.0.clone()));
+        }
+        fn enter(&self, _: &Id) {}
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[derive(Default, ObjectPool)]
+    struct Traced(u8);
+
+    static POOL: Pool<Vec<u8>> = Pool::new(Vec::new).with_capacity(1);
+
+    let recorder = Recorder::default();
+    let events = recorder.0.clone();
+    tracing::subscriber::with_default(recorder, || {
+        drop(Traced::new());
+        drop(Traced::new());
+        POOL.insert(Vec::new());
+        POOL.insert(Vec::new());
+    });
+    assert_eq!(
+        [
+            "missed",
+            "created",
+            "returned",
+            "reused",
+            "returned",
+            "returned",
+            "discarded",
+            "rejected"
+        ],
+        events.lock().unwrap().as_slice()
+    );
+}
+
+#[cfg(feature = "log")]
+#[test]
+fn log_reports_notable_events() {
+    use std::sync::Mutex;
+
+    struct Recorder(Mutex<Vec<(log::Level, String)>>);
+
+    impl log::Log for Recorder {
+        fn enabled(&self, _: &log::Metadata<'_>) -> bool {
+            true
+        }
+        fn log(&self, record: &log::Record<'_>) {
+            let message = record.args().to_string();
+            if message.contains("Logged") {
+                self.0.lock().unwrap().push((record.level(), message));
+            }
+        }
+        fn flush(&self) {}
+    }
+
+    static RECORDER: Recorder = Recorder(Mutex::new(Vec::new()));
+
+    #[derive(Clone, Copy)]
+    struct Logged(bool);
+
+    static POOL: Pool<Logged> = Pool::new(|| Logged(true))
+        .with_validator(|item| item.0)
+        .with_soft_limit(1, |_| {});
+
+    log::set_logger(&RECORDER).unwrap();
+    log::set_max_level(log::LevelFilter::Debug);
+    POOL.insert(Logged(false));
+    POOL.insert(Logged(false));
+    drop(PoolHandle::from_static(&POOL).acquire());
+
+    let logged = RECORDER.0.lock().unwrap();
+    let levels: Vec<_> = logged.iter().map(|(level, _)| *level).collect();
+    assert_eq!(
+        vec![
+            log::Level::Warn,
+            log::Level::Debug,
+            log::Level::Debug,
+            log::Level::Debug
+        ],
+        levels,
+        "{logged:?}"
+    );
+    assert!(logged[0].1.contains("soft limit of 1"));
+    assert!(logged[1].1.contains("failed its validator"));
+    assert!(logged[3].1.contains("ran empty"));
+}
+
+#[cfg(feature = "metrics")]
+#[test]
+fn metrics_publishes_the_registered_pools() {
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+    static POOL: Pool<Vec<u8>> = Pool::new(Vec::new);
+
+    POOL.register("METRICS_POOL");
+    let held = PoolHandle::from_static(&POOL).acquire();
+    drop(PoolHandle::from_static(&POOL).acquire());
+    drop(PoolHandle::from_static(&POOL).acquire());
+
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+    metrics::with_local_recorder(&recorder, derivable_object_pool::registry::publish_metrics);
+
+    let published: std::collections::HashMap<_, _> = snapshotter
+        .snapshot()
+        .into_vec()
+        .into_iter()
+        .filter(|(key, ..)| {
+            key.key()
+                .labels()
+                .any(|label| label.key() == "pool" && label.value() == "METRICS_POOL")
+        })
+        .map(|(key, _, _, value)| (key.key().name().to_string(), value))
+        .collect();
+    let gauge = |value: f64| DebugValue::Gauge(value.into());
+    assert_eq!(4, published.len());
+    assert_eq!(
+        DebugValue::Counter(2),
+        published["object_pool_created_total"]
+    );
+    assert_eq!(gauge(1.0), published["object_pool_idle"]);
+    assert_eq!(gauge(1.0), published["object_pool_outstanding"]);
+    assert_eq!(gauge(1.0 / 3.0), published["object_pool_reuse_ratio"]);
+    drop(held);
+}
+
+#[test]
+fn registry_renders_prometheus_exposition() {
+    static POOL: Pool<Vec<u8>> = Pool::new(Vec::new);
+
+    POOL.register("PROMETHEUS \"POOL\"");
+    let held = PoolHandle::from_static(&POOL).acquire();
+    drop(PoolHandle::from_static(&POOL).acquire());
+
+    let exposition = derivable_object_pool::registry::render_prometheus();
+    let labels = format!(
+        "{{pool=\"PROMETHEUS \\\"POOL\\\"\",type=\"{}\"}}",
+        std::any::type_name::<Vec<u8>>()
+    );
+    assert!(exposition.starts_with(
+        "# HELP object_pool_idle The number of idle objects of the pool.\n\
+         # TYPE object_pool_idle gauge\n"
+    ));
+    assert!(exposition.contains(&format!("\nobject_pool_idle{labels} 1\n")));
+    assert!(exposition.contains(&format!("\nobject_pool_outstanding{labels} 1\n")));
+    #[cfg(feature = "counters")]
+    assert!(exposition.contains(&format!("\nobject_pool_created_total{labels} 2\n")));
+    drop(held);
+}
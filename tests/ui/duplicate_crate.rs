@@ -0,0 +1,7 @@
+use derivable_object_pool::prelude::*;
+
+#[derive(Default, ObjectPool)]
+#[pool(crate = derivable_object_pool, crate = ::derivable_object_pool)]
+struct Test(u32);
+
+fn main() {}
@@ -0,0 +1,14 @@
+use derivable_object_pool::prelude::*;
+
+#[derive(ObjectPool)]
+#[generator(Test::make)]
+#[pool(generator = Test::make)]
+struct Test(u32);
+
+impl Test {
+    fn make() -> Self {
+        Test(0)
+    }
+}
+
+fn main() {}
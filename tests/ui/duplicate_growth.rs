@@ -0,0 +1,8 @@
+use derivable_object_pool::prelude::*;
+
+#[derive(Default, ObjectPool)]
+#[pool(growth = 4)]
+#[pool(growth = 8)]
+struct Test(u32);
+
+fn main() {}
@@ -0,0 +1,7 @@
+use derivable_object_pool::prelude::*;
+
+#[derive(Default, ObjectPool)]
+#[pool(lock_free = 4, lock_free = 8)]
+struct Test(u32);
+
+fn main() {}
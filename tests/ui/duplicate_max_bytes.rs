@@ -0,0 +1,7 @@
+use derivable_object_pool::prelude::*;
+
+#[derive(Default, ObjectPool)]
+#[pool(max_bytes = 4, max_bytes = 8)]
+struct Test(u32);
+
+fn main() {}
@@ -0,0 +1,7 @@
+use derivable_object_pool::prelude::*;
+
+#[derive(Default, ObjectPool)]
+#[pool(max_reuses = 4, max_reuses = 8)]
+struct Test(u32);
+
+fn main() {}
@@ -0,0 +1,7 @@
+use derivable_object_pool::prelude::*;
+
+#[derive(Default, ObjectPool)]
+#[pool(min_idle = 4, min_idle = 8)]
+struct Test(u32);
+
+fn main() {}
@@ -0,0 +1,7 @@
+use derivable_object_pool::prelude::*;
+
+#[derive(Default, ObjectPool)]
+#[pool(prefill = 4, prefill = 8)]
+struct Test(u32);
+
+fn main() {}
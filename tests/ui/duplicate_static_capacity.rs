@@ -0,0 +1,7 @@
+use derivable_object_pool::prelude::*;
+
+#[derive(Default, ObjectPool)]
+#[pool(static_capacity = 4, static_capacity = 8)]
+struct Test(u32);
+
+fn main() {}
@@ -0,0 +1,7 @@
+use derivable_object_pool::prelude::*;
+
+#[derive(Default, ObjectPool)]
+#[pool(static_name = "TESTS", static_name = "OTHER_TESTS")]
+struct Test(u32);
+
+fn main() {}
@@ -0,0 +1,7 @@
+use derivable_object_pool::prelude::*;
+
+#[derive(Default, ObjectPool)]
+#[pool(vis = "pub", vis = "pub(crate)")]
+pub struct Test(u32);
+
+fn main() {}
@@ -0,0 +1,17 @@
+use derivable_object_pool::prelude::*;
+
+#[derive(ObjectPool)]
+#[pool(generator = Test::make, generator_try = Test::try_make, error = ())]
+struct Test(u32);
+
+impl Test {
+    fn make() -> Self {
+        Test(0)
+    }
+
+    fn try_make() -> Result<Self, ()> {
+        Ok(Test(0))
+    }
+}
+
+fn main() {}
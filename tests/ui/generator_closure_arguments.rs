@@ -0,0 +1,7 @@
+use derivable_object_pool::prelude::*;
+
+#[derive(ObjectPool)]
+#[generator(|value: u32| Test(value))]
+struct Test(u32);
+
+fn main() {}
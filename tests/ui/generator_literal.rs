@@ -0,0 +1,7 @@
+use derivable_object_pool::prelude::*;
+
+#[derive(ObjectPool)]
+#[generator = 42]
+struct Test(u32);
+
+fn main() {}
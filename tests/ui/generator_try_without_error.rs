@@ -0,0 +1,13 @@
+use derivable_object_pool::prelude::*;
+
+#[derive(ObjectPool)]
+#[pool(generator_try = Test::try_make)]
+struct Test(u32);
+
+impl Test {
+    fn try_make() -> Result<Self, ()> {
+        Ok(Test(0))
+    }
+}
+
+fn main() {}
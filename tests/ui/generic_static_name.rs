@@ -0,0 +1,7 @@
+use derivable_object_pool::prelude::*;
+
+#[derive(Default, ObjectPool)]
+#[pool(static_name = "BUF_POOL")]
+struct Buf<T>(Vec<T>);
+
+fn main() {}
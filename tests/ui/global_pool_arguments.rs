@@ -0,0 +1,6 @@
+use derivable_object_pool::prelude::*;
+
+#[global_pool(capacity = 4)]
+static BUF_POOL: Pool<Vec<u8>> = Pool::new(Vec::new);
+
+fn main() {}
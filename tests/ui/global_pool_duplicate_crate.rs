@@ -0,0 +1,6 @@
+use derivable_object_pool::prelude::*;
+
+#[global_pool(crate = derivable_object_pool, crate = ::derivable_object_pool)]
+static BUF_POOL: Pool<Vec<u8>> = Pool::new(Vec::new);
+
+fn main() {}
@@ -0,0 +1,6 @@
+use derivable_object_pool::prelude::*;
+
+#[global_pool]
+static BUF: Vec<u8> = Vec::new();
+
+fn main() {}
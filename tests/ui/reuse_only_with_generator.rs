@@ -0,0 +1,7 @@
+use derivable_object_pool::prelude::*;
+
+#[derive(ObjectPool)]
+#[pool(reuse_only, generator = || Test(0))]
+struct Test(u32);
+
+fn main() {}
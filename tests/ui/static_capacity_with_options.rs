@@ -0,0 +1,7 @@
+use derivable_object_pool::prelude::*;
+
+#[derive(Default, ObjectPool)]
+#[pool(static_capacity = 4, recycle)]
+struct Test(u32);
+
+fn main() {}
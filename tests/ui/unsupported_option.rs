@@ -0,0 +1,7 @@
+use derivable_object_pool::prelude::*;
+
+#[derive(Default, ObjectPool)]
+#[pool(capacity = 4, unknown)]
+struct Test(u32);
+
+fn main() {}